@@ -20,15 +20,27 @@ pub struct Cli {
     #[arg(long, value_name = "MODE", value_parser = ["always", "auto", "never"])]
     pub color: Option<String>,
 
+    /// Where the colors of the file names come from [default: ls-colors]
+    #[arg(long, value_name = "SOURCE", value_parser = ["ls-colors", "theme"])]
+    pub color_source: Option<String>,
+
+    /// How many colors the terminal can show [default: auto]
+    #[arg(long, value_name = "DEPTH", value_parser = ["auto", "24", "256", "16"])]
+    pub color_depth: Option<String>,
+
     /// When to print the icons [default: auto]
-    #[arg(long, value_name = "MODE", value_parser = ["always", "auto", "never"])]
+    #[arg(long, value_name = "MODE", value_parser = ["always", "auto", "never", "directories-only"])]
     pub icon: Option<String>,
 
-    /// Whether to use fancy or unicode icons [default: fancy]
-    #[arg(long, value_name = "THEME", value_parser = ["fancy", "unicode"])]
+    /// Whether to use fancy or unicode icons [default: auto]
+    #[arg(long, value_name = "THEME", value_parser = ["auto", "fancy", "unicode"])]
     pub icon_theme: Option<String>,
 
-    /// Append indicator (one of */=>@|) at the end of the file names
+    /// The string between the icons and the names [default: " "]
+    #[arg(long, value_name = "STR", allow_hyphen_values = true)]
+    pub icon_separator: Option<String>,
+
+    /// Append indicator (one of */=@|~) at the end of the file names
     #[arg(short = 'F', long = "classify")]
     pub indicators: bool,
 
@@ -48,6 +60,27 @@ pub struct Cli {
     #[arg(short = '1', long)]
     pub oneline: bool,
 
+    /// Fill the grid by rows rather than by columns. Same as `--grid-direction=across`
+    #[arg(short = 'x', long)]
+    pub across: bool,
+
+    /// In which order to fill the grid [default: down]
+    #[arg(long, value_name = "DIRECTION", value_parser = ["down", "across"])]
+    pub grid_direction: Option<String>,
+
+    /// Lay the grid out in N columns rather than in the width of the terminal, 0 listing one
+    /// entry per line
+    #[arg(short = 'w', long, value_name = "N")]
+    pub width: Option<usize>,
+
+    /// Truncate the names wider than N columns, ending them with an ellipsis
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub truncate: Option<usize>,
+
     /// Recurse into directories
     #[arg(short = 'R', long, conflicts_with = "tree")]
     pub recursive: bool,
@@ -60,6 +93,10 @@ pub struct Cli {
     #[arg(long)]
     pub tree: bool,
 
+    /// Which characters to draw the edges of the tree with [default: unicode]
+    #[arg(long, value_name = "CHARSET", value_parser = ["unicode", "ascii"])]
+    pub tree_charset: Option<String>,
+
     /// Stop recursing into directories after reaching specified depth
     #[arg(long, value_name = "NUM")]
     pub depth: Option<usize>,
@@ -72,47 +109,75 @@ pub struct Cli {
     #[arg(long, value_name = "MODE", value_parser = ["rwx", "octal", "attributes", "disable"])]
     pub permission: Option<String>,
 
-    /// How to display size [default: default]
-    #[arg(long, value_name = "MODE", value_parser = ["default", "short", "bytes"])]
+    /// How to display size, in powers of 1024 (binary) or 1000 (si) [default: default]
+    #[arg(long, value_name = "MODE", value_parser = ["default", "short", "bytes", "binary", "si"])]
     pub size: Option<String>,
 
-    /// Display the total size of directories
-    #[arg(long)]
+    /// How to group the digits of the sizes displayed in bytes [default: none]
+    #[arg(long, value_name = "MODE", value_parser = ["none", "comma", "underscore"])]
+    pub size_separator: Option<String>,
+
+    /// Display the total size of directories, walking all their content
+    #[arg(long, visible_alias = "du")]
     pub total_size: bool,
 
-    /// How to display date [default: date] [possible values: date, locale, relative, +date-time-format]
-    #[arg(long, value_parser = validate_date_argument)]
+    /// Display the total size of the listed entries before the listing
+    #[arg(long)]
+    pub size_summary: bool,
+
+    /// How to display date [default: date] [possible values: date, locale, relative, iso, iso-short, +date-time-format]
+    #[arg(long, value_parser = validate_date_argument, overrides_with = "time_style")]
     pub date: Option<String>,
 
+    /// How to display date, like the `--time-style` of `ls` [possible values: full-iso, long-iso, iso, locale, +date-time-format]
+    #[arg(long, value_name = "STYLE", value_parser = validate_time_style, overrides_with = "date")]
+    pub time_style: Option<String>,
+
+    /// Display the dates in UTC rather than in the local timezone
+    #[arg(long)]
+    pub utc: bool,
+
+    /// Display the dates without the colors of their age
+    #[arg(long)]
+    pub no_date_color: bool,
+
+    /// Which time stamp to display and sort by [default: modified]
+    #[arg(long, value_name = "WORD", value_parser = ["modified", "accessed", "created"])]
+    pub time: Option<String>,
+
     /// Sort by time modified
-    #[arg(short = 't', long)]
+    #[arg(short = 't', long, overrides_with_all = ["sizesort", "extensionsort", "gitsort", "versionsort"])]
     pub timesort: bool,
 
     /// Sort by size
-    #[arg(short = 'S', long)]
+    #[arg(short = 'S', long, overrides_with_all = ["timesort", "extensionsort", "gitsort", "versionsort"])]
     pub sizesort: bool,
 
     /// Sort by file extension
-    #[arg(short = 'X', long)]
+    #[arg(short = 'X', long, overrides_with_all = ["timesort", "sizesort", "gitsort", "versionsort"])]
     pub extensionsort: bool,
 
     /// Sort by git status
-    #[arg(short = 'G', long)]
+    #[arg(short = 'G', long, overrides_with_all = ["timesort", "sizesort", "extensionsort", "versionsort"])]
     pub gitsort: bool,
 
     /// Natural sort of (version) numbers within text
-    #[arg(short = 'v', long)]
+    #[arg(short = 'v', long, overrides_with_all = ["timesort", "sizesort", "extensionsort", "gitsort"])]
     pub versionsort: bool,
 
-    /// Sort by TYPE instead of name
+    /// Sort by TYPE instead of name [possible values: name, size, time, version, extension, git, entries, inode, target, permission, owner, custom:<name>, none]
     #[arg(
         long,
         value_name = "TYPE",
-        value_parser = ["size", "time", "version", "extension", "git", "none"],
+        value_parser = validate_sort_argument,
         overrides_with_all = ["timesort", "sizesort", "extensionsort", "versionsort", "gitsort", "no_sort"]
     )]
     pub sort: Option<String>,
 
+    /// Sort by several comma separated keys, a leading - reversing a key (e.g. name,-size,time)
+    #[arg(long, value_name = "SPEC", value_parser = validate_sort_spec)]
+    pub sort_by: Option<String>,
+
     /// Do not sort. List entries in directory order
     #[arg(short = 'U', long, overrides_with_all = ["timesort", "sizesort", "extensionsort", "versionsort", "gitsort", "sort"])]
     pub no_sort: bool,
@@ -121,6 +186,22 @@ pub struct Cli {
     #[arg(short, long)]
     pub reverse: bool,
 
+    /// When reversing the sort, keep the entries which are equal on the sorted column in name order
+    #[arg(long)]
+    pub stable_ties: bool,
+
+    /// Compare names case insensitively when sorting [default]
+    #[arg(long, overrides_with = "case_sensitive")]
+    pub ignore_case: bool,
+
+    /// Compare names case sensitively when sorting
+    #[arg(long, overrides_with = "ignore_case")]
+    pub case_sensitive: bool,
+
+    /// How to compare names when sorting by name [default: bytewise]
+    #[arg(long, value_name = "MODE", value_parser = ["bytewise", "unicode"])]
+    pub collation: Option<String>,
+
     /// Sort the directories then the files
     #[arg(long, value_name = "MODE", value_parser = ["none", "first", "last"])]
     pub group_dirs: Option<String>,
@@ -129,11 +210,24 @@ pub struct Cli {
     #[arg(long)]
     pub group_directories_first: bool,
 
-    /// Specify the blocks that will be displayed and in what order
+    /// Sort the directories among the files, without grouping them even in a tree
+    #[arg(long)]
+    pub sort_dirs_among_files: bool,
+
+    /// Sort the hidden files then the other ones, after grouping the directories and the symlinks
+    #[arg(long, value_name = "MODE", value_parser = ["none", "first", "last"])]
+    pub group_hidden: Option<String>,
+
+    /// Sort the symlinks then the other files, after grouping the directories
+    #[arg(long, value_name = "MODE", value_parser = ["none", "first", "last"])]
+    pub group_symlinks: Option<String>,
+
+    /// Specify the blocks that will be displayed and in what order, the name being added at the
+    /// end when it is not given
     #[arg(
     long,
     value_delimiter = ',',
-    value_parser = ["permission", "user", "group", "context", "size", "date", "name", "inode", "links", "git"],
+    value_parser = ["permission", "user", "group", "context", "size", "date", "name", "inode", "blocks", "links", "flags", "filesystem", "git"],
     )]
     pub blocks: Vec<String>,
 
@@ -145,15 +239,48 @@ pub struct Cli {
     #[arg(long)]
     pub no_symlink: bool,
 
+    /// Display the symlinks, their arrow and their target without colors
+    #[arg(long)]
+    pub no_symlink_color: bool,
+
+    /// Color the symlink names like their target rather than as links
+    #[arg(long)]
+    pub symlink_color_target: bool,
+
+    /// The arrow between a symlink and its target [default: ⇒]
+    #[arg(
+        long,
+        value_name = "STR",
+        allow_hyphen_values = true,
+        value_parser = validate_symlink_arrow
+    )]
+    pub symlink_arrow: Option<String>,
+
     /// Do not display files/directories with names matching the glob pattern(s).
     /// More than one can be specified by repeating the argument
     #[arg(short = 'I', long, value_name = "PATTERN")]
     pub ignore_glob: Vec<String>,
 
+    /// Match the ignore globs case insensitively
+    #[arg(long)]
+    pub ignore_glob_case_insensitive: bool,
+
+    /// Do not display the files ignored by git, inside a git repository
+    #[arg(long)]
+    pub gitignore: bool,
+
     /// Display the index number of each file
     #[arg(short, long)]
     pub inode: bool,
 
+    /// Display the number of 512-byte blocks allocated to each file, and their total
+    #[arg(short, long)]
+    pub size_blocks: bool,
+
+    /// Display the number of hard links of each file in the long format
+    #[arg(long)]
+    pub links: bool,
+
     /// Show git status on file and directory"
     /// Only when used with --long option
     #[arg(short, long)]
@@ -164,10 +291,22 @@ pub struct Cli {
     #[arg(short = 'L', long)]
     pub dereference: bool,
 
+    /// Like --dereference, but only for the symbolic links given on the command line
+    #[arg(short = 'H', long)]
+    pub dereference_command_line: bool,
+
+    /// Recurse into the symbolic links to directories
+    #[arg(long)]
+    pub follow_symlinks: bool,
+
     /// Print security context (label) of each file
     #[arg(short = 'Z', long)]
     pub context: bool,
 
+    /// List the names of the extended attributes under each file in the long format
+    #[arg(long)]
+    pub xattr: bool,
+
     /// Attach hyperlink to filenames [default: never]
     #[arg(long, value_name = "MODE", value_parser = ["always", "auto", "never"])]
     pub hyperlink: Option<String>,
@@ -176,6 +315,20 @@ pub struct Cli {
     #[arg(long)]
     pub header: bool,
 
+    /// How to print the entries [default: listing]
+    #[arg(long, value_name = "FORMAT", value_parser = ["listing", "json", "csv", "tsv", "zero"])]
+    pub output: Option<String>,
+
+    /// Print the entries as JSON, with the content of the directories in a "children" array.
+    /// Same as `--output=json`
+    #[arg(long, conflicts_with = "output")]
+    pub json: bool,
+
+    /// Print the names of the entries each followed by a NUL byte, for `xargs -0`.
+    /// Same as `--output=zero`
+    #[arg(short, long, conflicts_with_all = ["json", "output"])]
+    pub zero: bool,
+
     /// Truncate the user and group names if they exceed a certain number of characters
     #[arg(long, value_name = "NUM")]
     pub truncate_owner_after: Option<usize>,
@@ -184,6 +337,14 @@ pub struct Cli {
     #[arg(long, value_name = "STR")]
     pub truncate_owner_marker: Option<String>,
 
+    /// Like --long, but display the ids of the users and groups rather than their names
+    #[arg(short = 'n', long)]
+    pub numeric_uid_gid: bool,
+
+    /// How to align the user and group names in their columns [default: left]
+    #[arg(long, value_name = "ALIGN", value_parser = ["left", "right"])]
+    pub owner_align: Option<String>,
+
     /// Includes files with the windows system protection flag set.
     /// This is the same as --all on other platforms
     #[arg(long, hide = !cfg!(windows))]
@@ -193,6 +354,15 @@ pub struct Cli {
     #[arg(short = 'N', long)]
     pub literal: bool,
 
+    /// Name the entries by their whole path, starting with the path given, rather than by their
+    /// file name
+    #[arg(long)]
+    pub full_path: bool,
+
+    /// How to quote the entry names [default: shell-escape on a terminal, literal otherwise]
+    #[arg(long, value_name = "WORD", value_parser = ["literal", "shell", "shell-escape", "c"])]
+    pub quoting_style: Option<String>,
+
     /// Print help information
     #[arg(long, action = ArgAction::Help)]
     help: (),
@@ -201,11 +371,74 @@ pub struct Cli {
 fn validate_date_argument(arg: &str) -> Result<String, String> {
     if arg.starts_with('+') {
         validate_time_format(arg)
-    } else if arg == "date" || arg == "relative" || arg == "locale" {
+    } else if ["date", "relative", "locale", "iso", "iso-short"].contains(&arg) {
         Result::Ok(arg.to_owned())
     } else {
-        Result::Err("possible values: date, locale, relative, +date-time-format".to_owned())
+        Result::Err(
+            "possible values: date, locale, relative, iso, iso-short, +date-time-format".to_owned(),
+        )
+    }
+}
+
+fn validate_symlink_arrow(arg: &str) -> Result<String, String> {
+    if arg.is_empty() {
+        Result::Err("the arrow can not be empty".to_owned())
+    } else {
+        Result::Ok(arg.to_owned())
+    }
+}
+
+fn validate_time_style(arg: &str) -> Result<String, String> {
+    if arg.starts_with('+') {
+        validate_time_format(arg)
+    } else if ["full-iso", "long-iso", "iso", "locale"].contains(&arg) {
+        Result::Ok(arg.to_owned())
+    } else {
+        Result::Err(
+            "possible values: full-iso, long-iso, iso, locale, +date-time-format".to_owned(),
+        )
+    }
+}
+
+/// The keys of a sort specification, besides the `custom:<name>` ones.
+pub const SORT_KEYS: [&str; 11] = [
+    "name",
+    "size",
+    "time",
+    "version",
+    "extension",
+    "git",
+    "entries",
+    "inode",
+    "target",
+    "permission",
+    "owner",
+];
+
+fn validate_sort_key(key: &str) -> Result<(), String> {
+    match key.strip_prefix("custom:") {
+        Some("") => Err("missing name of the custom sort key".to_owned()),
+        Some(_) => Ok(()),
+        None if SORT_KEYS.contains(&key) => Ok(()),
+        None => Err(format!(
+            "unknown sort key '{key}' (possible keys: {}, custom:<name>)",
+            SORT_KEYS.join(", ")
+        )),
+    }
+}
+
+fn validate_sort_argument(arg: &str) -> Result<String, String> {
+    if arg != "none" {
+        validate_sort_key(arg)?;
+    }
+    Ok(arg.to_owned())
+}
+
+fn validate_sort_spec(arg: &str) -> Result<String, String> {
+    for key in arg.split(',') {
+        validate_sort_key(key.strip_prefix('-').unwrap_or(key))?;
     }
+    Ok(arg.to_owned())
 }
 
 pub fn validate_time_format(formatter: &str) -> Result<String, String> {
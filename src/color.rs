@@ -4,9 +4,10 @@ use lscolors::{Indicator, LsColors};
 use std::path::Path;
 
 pub use crate::flags::color::ThemeOption;
+use crate::flags::ColorDepth;
 use crate::git::GitStatus;
 use crate::print_output;
-use crate::theme::{color::ColorTheme, Theme};
+use crate::theme::{color, color::ColorTheme, Theme};
 
 #[allow(dead_code)]
 #[derive(Hash, Debug, Eq, PartialEq, Clone)]
@@ -19,6 +20,7 @@ pub enum Elem {
     SymLink,
     BrokenSymLink,
     MissingSymLinkTarget,
+    SymLinkArrow,
     Dir {
         uid: bool,
     },
@@ -73,6 +75,12 @@ pub enum Elem {
     GitStatus {
         status: GitStatus,
     },
+
+    /// Name of an entry changed in git
+    GitModified,
+    GitUntracked,
+    GitStaged,
+    GitConflicted,
 }
 
 impl Elem {
@@ -80,6 +88,17 @@ impl Elem {
         matches!(self, Elem::Dir { uid: true } | Elem::File { uid: true, .. })
     }
 
+    fn is_broken_symlink(&self) -> bool {
+        matches!(self, Elem::BrokenSymLink | Elem::MissingSymLinkTarget)
+    }
+
+    fn is_symlink(&self) -> bool {
+        matches!(
+            self,
+            Elem::SymLink | Elem::BrokenSymLink | Elem::MissingSymLinkTarget | Elem::SymLinkArrow
+        )
+    }
+
     pub fn get_color(&self, theme: &ColorTheme) -> Color {
         match self {
             Elem::File {
@@ -101,6 +120,7 @@ impl Elem {
             Elem::SymLink => theme.file_type.symlink.default,
             Elem::BrokenSymLink => theme.file_type.symlink.broken,
             Elem::MissingSymLinkTarget => theme.file_type.symlink.missing_target,
+            Elem::SymLinkArrow => theme.file_type.symlink.arrow,
             Elem::Dir { uid: true } => theme.file_type.dir.uid,
             Elem::Dir { uid: false } => theme.file_type.dir.no_uid,
             Elem::Pipe => theme.file_type.pipe,
@@ -169,6 +189,10 @@ impl Elem {
             Elem::GitStatus {
                 status: GitStatus::Conflicted,
             } => theme.git_status.conflicted,
+            Elem::GitModified => theme.git_name.modified,
+            Elem::GitUntracked => theme.git_name.untracked,
+            Elem::GitStaged => theme.git_name.staged,
+            Elem::GitConflicted => theme.git_name.conflicted,
         }
     }
 }
@@ -178,6 +202,9 @@ pub type ColoredString = StyledContent<String>;
 pub struct Colors {
     theme: Option<ColorTheme>,
     lscolors: Option<LsColors>,
+    depth: ColorDepth,
+    symlink_color: bool,
+    symlink_target: bool,
 }
 
 impl Colors {
@@ -209,21 +236,82 @@ impl Colors {
             _ => None,
         };
 
-        Self { theme, lscolors }
+        Self {
+            theme,
+            lscolors,
+            depth: ColorDepth::TrueColor,
+            symlink_color: true,
+            symlink_target: false,
+        }
+    }
+
+    /// Downgrade the colors to the nearest ones of this depth when they are output.
+    pub fn with_depth(mut self, depth: ColorDepth) -> Self {
+        self.depth = depth.resolve();
+        self
+    }
+
+    /// Drop the rules of `LS_COLORS`, so the file names are colored by the theme too.
+    pub fn without_lscolors(mut self) -> Self {
+        self.lscolors = None;
+        self
+    }
+
+    /// Leave the symlinks, their arrow and their target uncolored, from both the theme and
+    /// `LS_COLORS`.
+    pub fn without_symlink_color(mut self) -> Self {
+        self.symlink_color = false;
+        self
+    }
+
+    /// Color the symlink names like their target, unless the symlinks are left uncolored.
+    pub fn with_symlink_target_color(mut self) -> Self {
+        self.symlink_target = true;
+        self
+    }
+
+    /// Whether the symlink names take the color of their target.
+    pub fn symlink_target_color(&self) -> bool {
+        self.symlink_target && self.symlink_color
+    }
+
+    /// The color of a file size, by its magnitude against the thresholds of the theme.
+    pub fn size_elem(&self, bytes: u64) -> Elem {
+        let default = color::Size::default();
+        let size = self.theme.as_ref().map_or(&default, |theme| &theme.size);
+        if bytes >= size.large_threshold {
+            Elem::FileLarge
+        } else if bytes >= size.medium_threshold {
+            Elem::FileMedium
+        } else {
+            Elem::FileSmall
+        }
     }
 
     pub fn colorize<S: Into<String>>(&self, input: S, elem: &Elem) -> ColoredString {
-        self.style(elem).apply(input.into())
+        self.downgrade(self.style(elem)).apply(input.into())
     }
 
     pub fn colorize_using_path(&self, input: String, path: &Path, elem: &Elem) -> ColoredString {
+        if !self.symlink_color && elem.is_symlink() {
+            return Colors::default_style().apply(input);
+        }
+        if elem.is_broken_symlink() && !self.has_lscolors_rule(elem) {
+            return self.colorize(input, elem);
+        }
         let style_from_path = self.style_from_path(path);
         match style_from_path {
-            Some(style_from_path) => style_from_path.apply(input),
+            Some(style_from_path) => self.downgrade(style_from_path).apply(input),
             None => self.colorize(input, elem),
         }
     }
 
+    fn downgrade(&self, mut style: ContentStyle) -> ContentStyle {
+        style.foreground_color = style.foreground_color.map(|c| downgrade(c, self.depth));
+        style.background_color = style.background_color.map(|c| downgrade(c, self.depth));
+        style
+    }
+
     pub fn default_style() -> ContentStyle {
         ContentStyle::default()
     }
@@ -236,7 +324,13 @@ impl Colors {
     }
 
     fn style(&self, elem: &Elem) -> ContentStyle {
+        if !self.symlink_color && elem.is_symlink() {
+            return ContentStyle::default();
+        }
         match &self.lscolors {
+            Some(_) if elem.is_broken_symlink() && !self.has_lscolors_rule(elem) => {
+                self.style_default(elem)
+            }
             Some(lscolors) => match self.get_indicator_from_elem(elem) {
                 Some(style) => {
                     let style = lscolors.style_for_indicator(style);
@@ -248,11 +342,36 @@ impl Colors {
         }
     }
 
+    /// Whether `LS_COLORS` has a rule of its own for a broken link (`or`) or its missing target
+    /// (`mi`, or else `or`). Without one, lscolors falls back to the style of the valid links or
+    /// to the normal one, which are the very entries of its table, hence compared by address.
+    fn has_lscolors_rule(&self, elem: &Elem) -> bool {
+        let Some(lscolors) = &self.lscolors else {
+            return false;
+        };
+        let style = |indicator| {
+            lscolors
+                .style_for_indicator(indicator)
+                .map(|style| style as *const lscolors::Style)
+        };
+        let fallbacks = match elem {
+            Elem::BrokenSymLink => vec![style(Indicator::SymbolicLink), style(Indicator::Normal)],
+            _ => vec![style(Indicator::Normal)],
+        };
+        let own = match elem {
+            Elem::BrokenSymLink => style(Indicator::OrphanedSymbolicLink),
+            _ => style(Indicator::MissingFile),
+        };
+        own.is_some() && !fallbacks.contains(&own)
+    }
+
     fn style_default(&self, elem: &Elem) -> ContentStyle {
         if let Some(t) = &self.theme {
             let style_fg = ContentStyle::default().with(elem.get_color(t));
             if elem.has_suid() {
                 style_fg.on(Color::AnsiValue(124)) // Red3
+            } else if *elem == Elem::NoAccess {
+                style_fg.attribute(Attribute::Dim)
             } else {
                 style_fg
             }
@@ -292,6 +411,96 @@ impl Colors {
     }
 }
 
+/// The 16 basic colors, with their usual xterm values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Grey, (192, 192, 192)),
+    (Color::DarkGrey, (128, 128, 128)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The levels of the red, green and blue of the 6x6x6 cube of the 256 colors.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Replace a color by the nearest one of the depth.
+fn downgrade(color: Color, depth: ColorDepth) -> Color {
+    match (depth, color) {
+        (ColorDepth::Ansi256, Color::Rgb { r, g, b }) => Color::AnsiValue(nearest_ansi256(r, g, b)),
+        (ColorDepth::Ansi16, Color::Rgb { r, g, b }) => nearest_ansi16((r, g, b)),
+        (ColorDepth::Ansi16, Color::AnsiValue(value)) => match ANSI16.get(usize::from(value)) {
+            Some((color, _)) => *color,
+            None => nearest_ansi16(ansi256_rgb(value)),
+        },
+        _ => color,
+    }
+}
+
+/// The red, green and blue of one of the 256 colors.
+fn ansi256_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => ANSI16[usize::from(value)].1,
+        16..=231 => {
+            let value = value - 16;
+            (
+                CUBE_LEVELS[usize::from(value / 36)],
+                CUBE_LEVELS[usize::from(value / 6 % 6)],
+                CUBE_LEVELS[usize::from(value % 6)],
+            )
+        }
+        _ => {
+            let level = 8 + (value - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// The squared distance between two colors.
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    [(r1, r2), (g1, g2), (b1, b2)]
+        .iter()
+        .map(|&(a, b)| u32::from(a.abs_diff(b)).pow(2))
+        .sum()
+}
+
+/// The nearest of the 256 colors, in the 6x6x6 cube or on the grey ramp.
+fn nearest_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |value: u8| -> u8 {
+        (0..6u8)
+            .min_by_key(|&idx| CUBE_LEVELS[usize::from(idx)].abs_diff(value))
+            .unwrap_or(0)
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+
+    let average = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+    let grey = 232 + (average.saturating_sub(3) / 10).min(23);
+
+    if distance(ansi256_rgb(grey), (r, g, b)) < distance(ansi256_rgb(cube), (r, g, b)) {
+        grey
+    } else {
+        cube
+    }
+}
+
+/// The nearest of the 16 basic colors.
+fn nearest_ansi16(rgb: (u8, u8, u8)) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, value)| distance(*value, rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
 fn to_content_style(ls: &lscolors::Style) -> ContentStyle {
     let to_crossterm_color = |c: &lscolors::Color| match c {
         lscolors::style::Color::RGB(r, g, b) => Color::Rgb {
@@ -356,9 +565,14 @@ fn to_content_style(ls: &lscolors::Style) -> ContentStyle {
 
 #[cfg(test)]
 mod tests {
-    use super::Colors;
+    use super::{downgrade, Colors, Elem};
     use crate::color::ThemeOption;
+    use crate::flags::ColorDepth;
     use crate::theme::color::ColorTheme;
+    use crossterm::style::Color;
+    use lscolors::LsColors;
+    use std::path::Path;
+
     #[test]
     fn test_color_new_no_color_theme() {
         assert!(Colors::new(ThemeOption::NoColor).theme.is_none());
@@ -380,6 +594,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_color_without_lscolors() {
+        let mut colors = Colors::new(ThemeOption::Default);
+        colors.lscolors = Some(LsColors::from_string("*.tar=01;31"));
+        let archive = Path::new("archive.tar");
+        let elem = Elem::File {
+            exec: false,
+            uid: false,
+        };
+        let theme = ColorTheme::default_dark();
+
+        let from_lscolors = colors.colorize_using_path("archive.tar".into(), archive, &elem);
+        assert_eq!(Some(Color::DarkRed), from_lscolors.style().foreground_color);
+
+        let colors = colors.without_lscolors();
+        let from_theme = colors.colorize_using_path("archive.tar".into(), archive, &elem);
+        assert_eq!(
+            Some(theme.file_type.file.no_exec_no_uid),
+            from_theme.style().foreground_color
+        );
+    }
+
+    #[test]
+    fn test_color_broken_symlink_without_lscolors_rule() {
+        let mut colors = Colors::new(ThemeOption::Default);
+        let theme = ColorTheme::default_dark();
+        let link = Path::new("link.tar");
+
+        // the default `LS_COLORS` only has a rule for the valid links
+        colors.lscolors = Some(LsColors::default());
+        for (elem, color) in [
+            (Elem::BrokenSymLink, theme.file_type.symlink.broken),
+            (
+                Elem::MissingSymLinkTarget,
+                theme.file_type.symlink.missing_target,
+            ),
+        ] {
+            let colored = colors.colorize_using_path("link".into(), link, &elem);
+            assert_eq!(Some(color), colored.style().foreground_color);
+        }
+
+        // the missing targets take the style of the broken links without one of their own
+        colors.lscolors = Some(LsColors::from_string("or=01;35"));
+        for elem in [Elem::BrokenSymLink, Elem::MissingSymLinkTarget] {
+            let colored = colors.colorize_using_path("link".into(), link, &elem);
+            assert_eq!(Some(Color::DarkMagenta), colored.style().foreground_color);
+        }
+    }
+
+    #[test]
+    fn test_color_without_symlink_color() {
+        let mut colors = Colors::new(ThemeOption::Default);
+        colors.lscolors = Some(LsColors::from_string("ln=01;35:*.tar=01;31"));
+        let link = Path::new("link.tar");
+
+        let colors = colors.without_symlink_color();
+        for elem in [
+            Elem::SymLink,
+            Elem::BrokenSymLink,
+            Elem::MissingSymLinkTarget,
+            Elem::SymLinkArrow,
+        ] {
+            assert_eq!(
+                None,
+                colors.colorize("link", &elem).style().foreground_color
+            );
+            assert_eq!(
+                None,
+                colors
+                    .colorize_using_path("link.tar".into(), link, &elem)
+                    .style()
+                    .foreground_color
+            );
+        }
+        assert_eq!(
+            Some(Color::AnsiValue(230)),
+            colors
+                .colorize("user", &Elem::User)
+                .style()
+                .foreground_color
+        );
+    }
+
+    #[test]
+    fn test_color_symlink_target_color() {
+        let colors = Colors::new(ThemeOption::NoLscolors);
+        assert!(!colors.symlink_target_color());
+
+        let colors = colors.with_symlink_target_color();
+        assert!(colors.symlink_target_color());
+        assert!(!colors.without_symlink_color().symlink_target_color());
+    }
+
+    #[test]
+    fn test_downgrade() {
+        let red = Color::Rgb { r: 255, g: 0, b: 0 };
+        let grey = Color::Rgb {
+            r: 128,
+            g: 128,
+            b: 128,
+        };
+        assert_eq!(red, downgrade(red, ColorDepth::TrueColor));
+        assert_eq!(Color::AnsiValue(196), downgrade(red, ColorDepth::Ansi256));
+        assert_eq!(Color::AnsiValue(244), downgrade(grey, ColorDepth::Ansi256));
+        assert_eq!(
+            Color::AnsiValue(245),
+            downgrade(Color::AnsiValue(245), ColorDepth::Ansi256)
+        );
+
+        let light_red = Color::Rgb {
+            r: 250,
+            g: 10,
+            b: 10,
+        };
+        assert_eq!(Color::Red, downgrade(light_red, ColorDepth::Ansi16));
+        assert_eq!(
+            Color::DarkRed,
+            downgrade(Color::AnsiValue(1), ColorDepth::Ansi16)
+        );
+        assert_eq!(
+            Color::DarkGrey,
+            downgrade(Color::AnsiValue(245), ColorDepth::Ansi16)
+        );
+        assert_eq!(
+            Color::DarkGreen,
+            downgrade(Color::DarkGreen, ColorDepth::Ansi16)
+        );
+    }
+
+    #[test]
+    fn test_colorize_with_depth() {
+        let colors = Colors::new(ThemeOption::NoLscolors).with_depth(ColorDepth::Ansi16);
+        let user = colors.colorize("user", &Elem::User);
+        assert_eq!(Some(Color::White), user.style().foreground_color);
+    }
+
+    #[test]
+    fn test_size_elem_thresholds() {
+        let mut colors = Colors::new(ThemeOption::Default);
+        assert_eq!(Elem::FileSmall, colors.size_elem(1024 * 1024 - 1));
+        assert_eq!(Elem::FileMedium, colors.size_elem(1024 * 1024));
+        assert_eq!(Elem::FileLarge, colors.size_elem(1024 * 1024 * 1024));
+
+        let size = &mut colors.theme.as_mut().unwrap().size;
+        size.medium_threshold = 100;
+        size.large_threshold = 1000;
+        assert_eq!(Elem::FileSmall, colors.size_elem(99));
+        assert_eq!(Elem::FileMedium, colors.size_elem(100));
+        assert_eq!(Elem::FileLarge, colors.size_elem(1000));
+
+        let colors = Colors::new(ThemeOption::NoColor);
+        assert_eq!(Elem::FileLarge, colors.size_elem(1024 * 1024 * 1024));
+    }
+
     #[test]
     fn test_color_new_bad_legacy_custom_theme() {
         assert_eq!(
@@ -432,6 +800,7 @@ mod elem {
                     default: Color::AnsiValue(44),         // DarkTurquoise
                     broken: Color::AnsiValue(124),         // Red3
                     missing_target: Color::AnsiValue(124), // Red3
+                    arrow: Color::AnsiValue(245),          // Grey
                 },
                 block_device: Color::AnsiValue(44), // DarkTurquoise
                 char_device: Color::AnsiValue(172), // Orange3
@@ -448,6 +817,8 @@ mod elem {
                 small: Color::AnsiValue(229),  // Wheat1
                 medium: Color::AnsiValue(216), // LightSalmon1
                 large: Color::AnsiValue(172),  // Orange3
+                medium_threshold: 1024 * 1024,
+                large_threshold: 1024 * 1024 * 1024,
             },
             inode: color::INode {
                 valid: Color::AnsiValue(13),    // Pink
@@ -459,6 +830,7 @@ mod elem {
             },
             tree_edge: Color::AnsiValue(245), // Grey
             git_status: Default::default(),
+            git_name: Default::default(),
         }
     }
 
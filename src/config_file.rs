@@ -1,13 +1,21 @@
 //! This module provides methods to handle the program's config files and
 //! operations related to this.
+use crate::flags::date_source::DateSource;
 use crate::flags::display::Display;
+use crate::flags::grid_direction::GridDirection;
 use crate::flags::icons::{IconOption, IconTheme};
 use crate::flags::layout::Layout;
+use crate::flags::output::OutputFormat;
+use crate::flags::owner_align::OwnerAlign;
 use crate::flags::permission::PermissionFlag;
+use crate::flags::quoting_style::QuotingStyle;
 use crate::flags::size::SizeFlag;
-use crate::flags::sorting::{DirGrouping, SortColumn};
+use crate::flags::size_separator::SizeSeparator;
+use crate::flags::size_unit::SizeUnit;
+use crate::flags::sorting::{Collation, DirGrouping, HiddenGrouping, SortColumn, SymlinkGrouping};
+use crate::flags::tree_charset::TreeCharset;
 use crate::flags::HyperlinkOption;
-use crate::flags::{ColorOption, ThemeOption};
+use crate::flags::{ColorDepth, ColorOption, ColorSource, ThemeOption};
 use crate::print_error;
 
 use std::path::{Path, PathBuf};
@@ -27,29 +35,53 @@ pub struct Config {
     pub blocks: Option<Vec<String>>,
     pub color: Option<Color>,
     pub date: Option<String>,
+    pub time: Option<DateSource>,
     pub dereference: Option<bool>,
+    pub dereference_command_line: Option<bool>,
+    pub follow_symlinks: Option<bool>,
     pub display: Option<Display>,
     pub icons: Option<Icons>,
     pub ignore_globs: Option<Vec<String>>,
+    pub ignore_globs_case_insensitive: Option<bool>,
+    pub gitignore: Option<bool>,
     pub indicators: Option<bool>,
     pub layout: Option<Layout>,
+    pub grid_direction: Option<GridDirection>,
+    pub width: Option<usize>,
+    pub truncate: Option<usize>,
     pub recursion: Option<Recursion>,
+    pub tree_charset: Option<TreeCharset>,
     pub size: Option<SizeFlag>,
+    pub size_unit: Option<SizeUnit>,
+    pub size_separator: Option<SizeSeparator>,
     pub permission: Option<PermissionFlag>,
     pub sorting: Option<Sorting>,
     pub no_symlink: Option<bool>,
+    pub no_symlink_color: Option<bool>,
+    pub symlink_color_target: Option<bool>,
     pub total_size: Option<bool>,
+    pub size_summary: Option<bool>,
     pub symlink_arrow: Option<String>,
     pub hyperlink: Option<HyperlinkOption>,
     pub header: Option<bool>,
+    pub output: Option<OutputFormat>,
     pub literal: Option<bool>,
+    pub full_path: Option<bool>,
+    pub quoting_style: Option<QuotingStyle>,
+    pub utc: Option<bool>,
+    pub no_date_color: Option<bool>,
+    pub xattr: Option<bool>,
     pub truncate_owner: Option<TruncateOwner>,
+    pub numeric_uid_gid: Option<bool>,
+    pub owner_align: Option<OwnerAlign>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
 pub struct Color {
     pub when: Option<ColorOption>,
     pub theme: Option<ThemeOption>,
+    pub source: Option<ColorSource>,
+    pub depth: Option<ColorDepth>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
@@ -70,7 +102,14 @@ pub struct Recursion {
 pub struct Sorting {
     pub column: Option<SortColumn>,
     pub reverse: Option<bool>,
+    pub reverse_all: Option<bool>,
+    pub sort_by: Option<String>,
     pub dir_grouping: Option<DirGrouping>,
+    pub dirs_among_files: Option<bool>,
+    pub hidden_grouping: Option<HiddenGrouping>,
+    pub symlink_grouping: Option<SymlinkGrouping>,
+    pub case_sensitive: Option<bool>,
+    pub collation: Option<Collation>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
@@ -112,23 +151,45 @@ impl Config {
             blocks: None,
             color: None,
             date: None,
+            time: None,
             dereference: None,
+            dereference_command_line: None,
+            follow_symlinks: None,
             display: None,
             icons: None,
             ignore_globs: None,
+            ignore_globs_case_insensitive: None,
+            gitignore: None,
             indicators: None,
             layout: None,
+            grid_direction: None,
+            width: None,
+            truncate: None,
             recursion: None,
+            tree_charset: None,
             size: None,
+            size_unit: None,
+            size_separator: None,
             permission: None,
             sorting: None,
             no_symlink: None,
+            no_symlink_color: None,
+            symlink_color_target: None,
             total_size: None,
+            size_summary: None,
             symlink_arrow: None,
             hyperlink: None,
             header: None,
+            output: None,
             literal: None,
+            full_path: None,
+            quoting_style: None,
+            utc: None,
+            no_date_color: None,
+            xattr: None,
             truncate_owner: None,
+            numeric_uid_gid: None,
+            owner_align: None,
         }
     }
 
@@ -223,8 +284,8 @@ classic: false
 
 # == Blocks ==
 # This specifies the columns and their order when using the long and the tree
-# layout.
-# Possible values: permission, user, group, context, size, date, name, inode, git
+# layout. The name is added at the end when it is not listed.
+# Possible values: permission, user, group, context, size, date, name, inode, blocks, links, flags, filesystem, git
 blocks:
   - permission
   - user
@@ -247,19 +308,59 @@ color:
   # XDG Base Directory if relative
   # The file path if absolute
   theme: default
+  # Where the colors of the file names come from. With "ls-colors", the rules of
+  # LS_COLORS apply first, by extension then by file type, and the theme colors
+  # what they leave out. With "theme", LS_COLORS is ignored.
+  # Possible values: ls-colors, theme
+  source: ls-colors
+  # How many colors the terminal can show, the colors of the theme being
+  # downgraded to the nearest ones it has. With "auto", this is 24 when the
+  # COLORTERM environment variable is "truecolor" or "24bit", and 256 otherwise.
+  # Possible values: auto, 24, 256, 16
+  depth: auto
 
 # == Date ==
 # This specifies the date format for the date column. The freeform format
 # accepts an strftime like string.
 # When "classic" is set, this is set to "date".
-# Possible values: date, locale, relative, +<date_format>
+# Possible values: date, locale, relative, iso, iso-short, +<date_format>
 # date: date
 
+# == Time ==
+# This specifies which time stamp is displayed in the date column and used for
+# sorting by time.
+# Possible values: modified, accessed, created
+# time: modified
+
+# == UTC ==
+# Whether to display the dates in UTC rather than in the local timezone.
+# Possible values: false, true
+utc: false
+
+# == No date color ==
+# Whether to display the dates without the colors of their age, keeping the
+# colors of the other columns.
+# Possible values: false, true
+no-date-color: false
+
 # == Dereference ==
 # Whether to dereference symbolic links.
 # Possible values: false, true
 dereference: false
 
+# == Dereference command line ==
+# Whether to dereference the symbolic links given on the command line, but not
+# the ones found in the listed directories.
+# Possible values: false, true
+dereference-command-line: false
+
+# == Follow symlinks ==
+# Whether to recurse into the symbolic links to directories, which is always
+# done when "dereference" is set. The links leading back to a directory being
+# listed are marked with "[recursion]".
+# Possible values: false, true
+follow-symlinks: false
+
 # == Display ==
 # What items to display. Do not specify this for the default behavior.
 # Possible values: all, almost-all, directory-only
@@ -268,21 +369,35 @@ dereference: false
 # == Icons ==
 icons:
   # When to use icons.
-  # When "classic" is set, this is set to "never".
-  # Possible values: always, auto, never
+  # When "classic" is set, this is set to "never". With "directories-only", only
+  # the directories have an icon, the names of the other entries staying aligned.
+  # Possible values: always, auto, never, directories-only
   when: auto
-  # Which icon theme to use.
-  # Possible values: fancy, unicode
-  theme: fancy
+  # Which icon theme to use. "fancy" needs a Nerd Font, "auto" uses it when the
+  # NERD_FONT environment variable is set and the unicode icons otherwise.
+  # Possible values: auto, fancy, unicode
+  theme: auto
   # The string between the icons and the name.
   # Possible values: any string (eg: " |")
   separator: " "
 
 # == Ignore Globs ==
-# A list of globs to ignore when listing.
+# A list of globs to ignore when listing. A glob ending with "/" only matches
+# the directories, and a glob holding another "/" matches the end of the paths.
 # ignore-globs:
 #   - .git
 
+# == Ignore Globs Case Insensitive ==
+# Whether to match the ignore globs case insensitively.
+# Possible values: false, true
+ignore-globs-case-insensitive: false
+
+# == Gitignore ==
+# Whether to hide the files ignored by git, inside a git repository. The
+# explicitly listed files are still displayed.
+# Possible values: false, true
+gitignore: false
+
 # == Indicators ==
 # Whether to add indicator characters to certain listed files.
 # Possible values: false, true
@@ -294,6 +409,22 @@ indicators: false
 # Possible values: grid, tree, oneline
 layout: grid
 
+# == Grid direction ==
+# In which order to fill the grid layout, by columns like ls or by rows like
+# ls -x.
+# Possible values: down, across
+grid-direction: down
+
+# == Width ==
+# The width, in columns, to lay the grid layout out in, rather than the width
+# of the terminal. 0 lists one entry per line.
+# width: 80
+
+# == Truncate ==
+# The width, in columns, to truncate the longer names to, ending them with an
+# ellipsis. By default, no truncation is done.
+# truncate: 40
+
 # == Recursion ==
 recursion:
   # Whether to enable recursion.
@@ -303,11 +434,29 @@ recursion:
   # it unspecified for (virtually) infinite.
   # depth: 3
 
+# == Tree charset ==
+# Which characters to draw the edges of the tree layout with, the box-drawing
+# ones or their ASCII fallback for the terminals without Unicode.
+# Possible values: unicode, ascii
+tree-charset: unicode
+
 # == Size ==
 # Specifies the format of the size column.
 # Possible values: default, short, bytes
 size: default
 
+# == Size unit ==
+# Specifies the multiple of bytes the sizes are displayed in: powers of 1024
+# (KiB, MiB...), powers of 1000 (kB, MB...) or the raw number of bytes.
+# Possible values: binary, si, bytes
+size-unit: binary
+
+# == Size separator ==
+# Specifies how to group the digits of the sizes displayed in bytes, e.g.
+# 1,048,576 with comma.
+# Possible values: none, comma, underscore
+size-separator: none
+
 # == Permission ==
 # Specify the format of the permission column.
 # Possible value: rwx, octal, attributes, disable
@@ -316,33 +465,89 @@ size: default
 # == Sorting ==
 sorting:
   # Specify what to sort by.
-  # Possible values: extension, name, time, size, version
+  # Possible values: extension, name, time, size, version, git, entries, inode, target, permission, owner, none
   column: name
   # Whether to reverse the sorting.
   # Possible values: false, true
   reverse: false
+  # Whether reversing also reverses the name order of the entries which are
+  # equal on the sorted column.
+  # Possible values: false, true
+  reverse-all: true
+  # Sort by several keys one after the other, a leading "-" reversing a key.
+  # This replaces "column" when set.
+  # sort-by: name,-size,time
   # Whether to group directories together and where.
   # When "classic" is set, this is set to "none".
   # Possible values: first, last, none
   dir-grouping: none
+  # Whether to sort the directories among the files, without grouping them
+  # even in a tree, whose children keep the sort options rather than being
+  # sorted by name with the directories first.
+  # Possible values: false, true
+  dirs-among-files: false
+  # Whether to group hidden files together and where, after the directories
+  # and the symlinks.
+  # When "classic" is set, this is set to "none".
+  # Possible values: first, last, none
+  hidden-grouping: none
+  # Whether to group symlinks together and where, after the directories.
+  # When "classic" is set, this is set to "none".
+  # Possible values: first, last, none
+  symlink-grouping: none
+  # Whether to compare names case sensitively.
+  # Possible values: false, true
+  case-sensitive: false
+  # How to collate names when sorting by name. "unicode" folds accents and
+  # ligatures so that "café" sorts next to "cafe", at the cost of speed.
+  # Possible values: bytewise, unicode
+  collation: bytewise
 
 # == No Symlink ==
 # Whether to omit showing symlink targets
 # Possible values: false, true
 no-symlink: false
 
+# == No symlink color ==
+# Whether to display the symlinks, their arrow and their target without
+# colors, keeping the colors of the other entries.
+# Possible values: false, true
+no-symlink-color: false
+
+# == Symlink color target ==
+# Whether to color the symlink names like their target, so a link to an
+# executable looks executable, rather than with the colors of the links.
+# The broken links keep the colors of the links.
+# Possible values: false, true
+symlink-color-target: false
+
 # == Total size ==
-# Whether to display the total size of directories.
+# Whether to display the total size of directories, walking all their content.
+# This can be slow on large trees.
 # Possible values: false, true
 total-size: false
 
+# == Size summary ==
+# Whether to display the total size of the listed entries before the listing.
+# The directories count their own entry only, unless "total-size" is set.
+# Possible values: false, true
+size-summary: false
+
 # == Hyperlink ==
-# Whether to display the total size of directories.
+# Whether to display the total size of directories, walking all their content.
+# This can be slow on large trees.
 # Possible values: always, auto, never
 hyperlink: never
 
+# == Extended attributes ==
+# Whether to list the names of the extended attributes under the files in the
+# long format.
+# Possible values: false, true
+xattr: false
+
 # == Symlink arrow ==
 # Specifies how the symlink arrow display, chars in both ascii and utf8
+# When "classic" is set, this is set to "->".
 symlink-arrow: ⇒
 
 # == Literal ==
@@ -350,6 +555,12 @@ symlink-arrow: ⇒
 # Possible values: false, true
 literal: false
 
+# == Full path ==
+# Whether to name the entries by their whole path, starting with the path given,
+# rather than by their file name.
+# Possible values: false, true
+full-path: false
+
 # == Truncate owner ==
 # How to truncate the username and group name for the file if they exceed a
 # certain number of characters.
@@ -358,6 +569,16 @@ truncate-owner:
   after:
   # String to be appended to a name if truncated.
   marker: ""
+
+# == Numeric uid gid ==
+# Whether to display the ids of the users and groups rather than their names.
+# Possible values: false, true
+numeric-uid-gid: false
+
+# == Owner align ==
+# How to align the user and group names in their columns.
+# Possible values: left, right
+owner-align: left
 "#;
 
 #[cfg(test)]
@@ -371,11 +592,18 @@ impl Config {
 mod tests {
     use super::Config;
     use crate::config_file;
-    use crate::flags::color::{ColorOption, ThemeOption};
+    use crate::flags::color::{ColorDepth, ColorOption, ColorSource, ThemeOption};
+    use crate::flags::grid_direction::GridDirection;
     use crate::flags::icons::{IconOption, IconTheme};
     use crate::flags::layout::Layout;
+    use crate::flags::owner_align::OwnerAlign;
     use crate::flags::size::SizeFlag;
-    use crate::flags::sorting::{DirGrouping, SortColumn};
+    use crate::flags::size_separator::SizeSeparator;
+    use crate::flags::size_unit::SizeUnit;
+    use crate::flags::sorting::{
+        Collation, DirGrouping, HiddenGrouping, SortColumn, SymlinkGrouping,
+    };
+    use crate::flags::tree_charset::TreeCharset;
     use crate::flags::HyperlinkOption;
 
     #[test]
@@ -394,40 +622,71 @@ mod tests {
                 ]),
                 color: Some(config_file::Color {
                     when: Some(ColorOption::Auto),
-                    theme: Some(ThemeOption::Default)
+                    theme: Some(ThemeOption::Default),
+                    source: Some(ColorSource::LsColors),
+                    depth: Some(ColorDepth::Auto),
                 }),
                 date: None,
+                time: None,
                 dereference: Some(false),
+                dereference_command_line: Some(false),
+                follow_symlinks: Some(false),
                 display: None,
                 icons: Some(config_file::Icons {
                     when: Some(IconOption::Auto),
-                    theme: Some(IconTheme::Fancy),
+                    theme: Some(IconTheme::Auto),
                     separator: Some(" ".to_string()),
                 }),
                 ignore_globs: None,
+                ignore_globs_case_insensitive: Some(false),
+                gitignore: Some(false),
                 indicators: Some(false),
                 layout: Some(Layout::Grid),
+                grid_direction: Some(GridDirection::Down),
+                width: None,
+                truncate: None,
                 recursion: Some(config_file::Recursion {
                     enabled: Some(false),
                     depth: None,
                 }),
+                tree_charset: Some(TreeCharset::Unicode),
                 size: Some(SizeFlag::Default),
+                size_unit: Some(SizeUnit::Binary),
+                size_separator: Some(SizeSeparator::None),
                 permission: None,
                 sorting: Some(config_file::Sorting {
                     column: Some(SortColumn::Name),
                     reverse: Some(false),
+                    reverse_all: Some(true),
+                    sort_by: None,
                     dir_grouping: Some(DirGrouping::None),
+                    dirs_among_files: Some(false),
+                    hidden_grouping: Some(HiddenGrouping::None),
+                    symlink_grouping: Some(SymlinkGrouping::None),
+                    case_sensitive: Some(false),
+                    collation: Some(Collation::Bytewise),
                 }),
                 no_symlink: Some(false),
+                no_symlink_color: Some(false),
+                symlink_color_target: Some(false),
                 total_size: Some(false),
+                size_summary: Some(false),
                 symlink_arrow: Some("⇒".into()),
                 hyperlink: Some(HyperlinkOption::Never),
                 header: None,
+                output: None,
                 literal: Some(false),
+                full_path: Some(false),
+                quoting_style: None,
+                utc: Some(false),
+                no_date_color: Some(false),
+                xattr: Some(false),
                 truncate_owner: Some(config_file::TruncateOwner {
                     after: None,
                     marker: Some("".to_string()),
                 }),
+                numeric_uid_gid: Some(false),
+                owner_align: Some(OwnerAlign::Left),
             },
             c
         );
@@ -1,46 +1,40 @@
 use crate::color::Colors;
 use crate::display;
 use crate::flags::{
-    ColorOption, Display, Flags, HyperlinkOption, Layout, Literal, SortOrder, ThemeOption,
+    ColorOption, ColorSource, Display, Flags, HyperlinkOption, Layout, OutputFormat, QuotingStyle,
+    SortColumn, ThemeOption,
 };
-use crate::git::GitCache;
+use crate::git::GitCaches;
 use crate::icon::Icons;
 
-use crate::meta::Meta;
-use crate::{print_error, print_output, sort, ExitCode};
-use std::path::PathBuf;
-
-#[cfg(not(target_os = "windows"))]
-use std::io;
-#[cfg(not(target_os = "windows"))]
-use std::os::unix::io::AsRawFd;
+use crate::meta::{FileType, Meta, MetaRequest, SymLink};
+use crate::{print_error, sort, ExitCode};
+use std::collections::HashSet;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 use crate::flags::blocks::Block;
 use crate::git_theme::GitTheme;
-#[cfg(target_os = "windows")]
-use terminal_size::terminal_size;
 
 pub struct Core {
     flags: Flags,
     icons: Icons,
     colors: Colors,
     git_theme: GitTheme,
-    sorters: Vec<(SortOrder, sort::SortFn)>,
+    sorter: sort::Sorter,
+    tree_sorter: Option<sort::Sorter>,
 }
 
 impl Core {
     pub fn new(mut flags: Flags) -> Self {
-        // Check through libc if stdout is a tty. Unix specific so not on windows.
+        // Check if stdout, where the listing is written, is a terminal: when it is piped, no
+        // escape sequence is written for the colors, the icons or the hyperlinks.
         // Determine color output availability (and initialize color output (for Windows 10))
-        #[cfg(not(target_os = "windows"))]
-        let tty_available = unsafe { libc::isatty(io::stdout().as_raw_fd()) == 1 };
+        let tty_available = io::stdout().is_terminal();
 
         #[cfg(not(target_os = "windows"))]
         let console_color_ok = true;
 
-        #[cfg(target_os = "windows")]
-        let tty_available = terminal_size().is_some(); // terminal_size allows us to know if the stdout is a tty or not.
-
         #[cfg(target_os = "windows")]
         let console_color_ok = crossterm::ansi_support::supports_ansi();
 
@@ -64,41 +58,208 @@ impl Core {
 
         let icon_separator = flags.icons.separator.0.clone();
 
-        if !tty_available {
+        // a grid of a given width is laid out as asked
+        let fixed_grid = flags.layout == Layout::Grid && flags.width.0.is_some();
+        if !tty_available && !fixed_grid {
             // The output is not a tty, this means the command is piped. (ex: lsd -l | less)
             //
             // Most of the programs does not handle correctly the ansi colors
             // or require a raw output (like the `wc` command).
             flags.layout = Layout::OneLine;
+        };
+
+        if flags.literal.0 {
+            flags.quoting_style = QuotingStyle::Literal;
+        } else if flags.quoting_style == QuotingStyle::Auto {
+            flags.quoting_style = if tty_available {
+                QuotingStyle::ShellEscape
+            } else {
+                QuotingStyle::Literal
+            };
+        }
 
-            flags.literal = Literal(true);
+        let colors = match flags.color.source {
+            ColorSource::LsColors => Colors::new(color_theme),
+            ColorSource::Theme => Colors::new(color_theme).without_lscolors(),
+        }
+        .with_depth(flags.color.depth);
+        let colors = if flags.no_symlink_color.0 {
+            colors.without_symlink_color()
+        } else {
+            colors
+        };
+        let colors = if flags.symlink_color_target.0 {
+            colors.with_symlink_target_color()
+        } else {
+            colors
         };
 
-        let sorters = sort::assemble_sorters(&flags);
+        let sorter = sort::assemble_sorters(&flags);
+        // The children of a tree keep a consistent order, the sort options only apply to the
+        // listed paths. Not sorting at all still applies everywhere.
+        let tree_sorter = (flags.layout == Layout::Tree && !sorter.is_noop())
+            .then(|| sort::assemble_sorters_with(&flags, &sort::SortOverride::tree(&flags)));
 
         Self {
             flags,
-            colors: Colors::new(color_theme),
+            colors,
             icons: Icons::new(tty_available, icon_when, icon_theme, icon_separator),
             git_theme: GitTheme::new(),
-            sorters,
+            sorter,
+            tree_sorter,
         }
     }
 
     pub fn run(self, paths: Vec<PathBuf>) -> ExitCode {
+        let mut out = io::BufWriter::new(io::stdout().lock());
+        match self.write(paths, &mut out).and_then(|exit_code| {
+            out.flush()?;
+            Ok(exit_code)
+        }) {
+            Ok(exit_code) => exit_code,
+            // like print_output, stop quietly when stdout is closed
+            Err(_) => std::process::exit(0),
+        }
+    }
+
+    /// List the paths like [run](Core::run), returning the listing rather than printing it.
+    ///
+    /// The errors met on the way are still printed.
+    pub fn render(&self, paths: Vec<PathBuf>) -> (String, ExitCode) {
+        let mut out = Vec::new();
+        // writing into a vector does not fail
+        let exit_code = self.write(paths, &mut out).unwrap_or(ExitCode::OK);
+        (String::from_utf8_lossy(&out).into_owned(), exit_code)
+    }
+
+    /// List the paths, writing the listing into `out`.
+    pub fn write(&self, paths: Vec<PathBuf>, out: &mut impl Write) -> io::Result<ExitCode> {
+        if let [path] = paths.as_slice() {
+            if self.streams() {
+                if let Some(exit_code) = self.stream(path, out)? {
+                    return Ok(exit_code);
+                }
+            }
+        }
+
         let (mut meta_list, exit_code) = self.fetch(paths);
 
-        self.sort(&mut meta_list);
-        self.display(&meta_list);
-        exit_code
+        sort::cache_sort_keys(&self.flags, &mut meta_list);
+        self.sort(&mut meta_list, &self.sorter);
+        self.display(&meta_list, out)?;
+        Ok(exit_code)
+    }
+
+    /// Whether a directory is listed while it is read rather than once all of it is, so that a
+    /// huge one is not held in memory: in the order of the directory, with the names alone one
+    /// per line, or as the rows of the csv and tsv outputs.
+    fn streams(&self) -> bool {
+        let flags = &self.flags;
+        let unsorted_content = self.sorter.is_noop()
+            && !flags.recursion.enabled
+            && flags.display != Display::DirectoryOnly;
+        unsorted_content
+            && match flags.output {
+                OutputFormat::Listing => {
+                    flags.layout == Layout::OneLine
+                        && flags.blocks.0 == [Block::Name]
+                        && !flags.header.0
+                        && !flags.size_summary.0
+                }
+                // the tree lists the nested entries too
+                OutputFormat::Csv | OutputFormat::Tsv => flags.layout != Layout::Tree,
+                OutputFormat::Json | OutputFormat::Zero => false,
+            }
+    }
+
+    /// List the content of the directory at `path` while it is read, or return `None` when it is
+    /// not a directory, to be listed like any other path.
+    fn stream(&self, path: &Path, out: &mut impl Write) -> io::Result<Option<ExitCode>> {
+        let meta = match self.command_line_meta(path, &self.command_line_request()) {
+            Ok(meta) if matches!(meta.file_type, FileType::Directory { .. }) => meta,
+            _ => return Ok(None),
+        };
+
+        let separator = match self.flags.output {
+            OutputFormat::Csv => Some(','),
+            OutputFormat::Tsv => Some('\t'),
+            _ => None,
+        };
+        if let Some(separator) = separator {
+            display::csv_header(&self.flags, separator, out)?;
+        }
+
+        let mut git_caches = GitCaches::default();
+        let cache = self
+            .flags
+            .blocks
+            .0
+            .contains(&Block::GitStatus)
+            .then(|| git_caches.get(path));
+        let result = meta.stream_content(&self.flags, cache, |mut entry| {
+            match separator {
+                Some(separator) => {
+                    self.calculate(&mut entry);
+                    display::csv_row(
+                        &entry,
+                        &meta.path,
+                        &self.flags,
+                        &self.git_theme,
+                        separator,
+                        out,
+                    )
+                }
+                None => display::line(
+                    &entry,
+                    &meta.path,
+                    &self.flags,
+                    &self.colors,
+                    &self.icons,
+                    &self.git_theme,
+                    out,
+                ),
+            }
+            .map_err(StreamError::Write)
+        });
+        match result {
+            Ok(exit_code) => Ok(Some(exit_code)),
+            Err(StreamError::Read(err)) => {
+                print_error!("{}: {}.", path.display(), err);
+                Ok(Some(ExitCode::MajorIssue))
+            }
+            Err(StreamError::Write(err)) => Err(err),
+        }
+    }
+
+    /// What to read of the paths given on the command line, which are dereferenced by both
+    /// `--dereference` and `--dereference-command-line`.
+    fn command_line_request(&self) -> MetaRequest {
+        MetaRequest {
+            dereference: self.flags.dereference.0 || self.flags.dereference_command_line.0,
+            ..MetaRequest::from(&self.flags)
+        }
+    }
+
+    /// Read a path given on the command line.
+    fn command_line_meta(&self, path: &Path, request: &MetaRequest) -> io::Result<Meta> {
+        let mut meta = Meta::from_path_for(path, request)?;
+        // like `ls -H`, a dereferenced link is shown as its target, without an arrow
+        if self.flags.dereference_command_line.0
+            && !matches!(meta.file_type, FileType::SymLink { .. })
+        {
+            meta.symlink = SymLink::default();
+        }
+        Ok(meta)
     }
 
     fn fetch(&self, paths: Vec<PathBuf>) -> (Vec<Meta>, ExitCode) {
         let mut exit_code = ExitCode::OK;
         let mut meta_list = Vec::with_capacity(paths.len());
+        let mut git_caches = GitCaches::default();
         let depth = match self.flags.layout {
-            Layout::Tree { .. } => self.flags.recursion.depth,
-            _ if self.flags.recursion.enabled => self.flags.recursion.depth,
+            // the top level is still listed with a depth of 0
+            Layout::Tree { .. } => self.flags.recursion.depth.max(1),
+            _ if self.flags.recursion.enabled => self.flags.recursion.depth.max(1),
             _ => 1,
         };
 
@@ -110,19 +271,21 @@ impl Core {
             .filter_map(config_file::expand_home)
             .collect();
 
-        for path in paths {
-            let mut meta =
-                match Meta::from_path(&path, self.flags.dereference.0, self.flags.permission) {
-                    Ok(meta) => meta,
-                    Err(err) => {
-                        print_error!("{}: {}.", path.display(), err);
-                        exit_code.set_if_greater(ExitCode::MajorIssue);
-                        continue;
-                    }
-                };
+        let request = self.command_line_request();
+        for path in dedup_paths(paths, request.dereference) {
+            let mut meta = match self.command_line_meta(&path, &request) {
+                Ok(meta) => meta,
+                Err(err) => {
+                    print_error!("{}: {}.", path.display(), err);
+                    exit_code.set_if_greater(ExitCode::MajorIssue);
+                    continue;
+                }
+            };
 
-            let cache = if self.flags.blocks.0.contains(&Block::GitStatus) {
-                Some(GitCache::new(&path))
+            let cache = if self.flags.blocks.0.contains(&Block::GitStatus)
+                || self.flags.sorting.sorts_by(SortColumn::GitStatus)
+            {
+                Some(git_caches.get(&path))
             } else {
                 None
             };
@@ -130,7 +293,7 @@ impl Core {
             let recurse =
                 self.flags.layout == Layout::Tree || self.flags.display != Display::DirectoryOnly;
             if recurse {
-                match meta.recurse_into(depth, &self.flags, cache.as_ref()) {
+                match meta.recurse_into(depth, &self.flags, cache) {
                     Ok((content, path_exit_code)) => {
                         meta.content = content;
                         meta.git_status = cache.and_then(|cache| cache.get(&meta.path, true));
@@ -138,8 +301,8 @@ impl Core {
                         exit_code.set_if_greater(path_exit_code);
                     }
                     Err(err) => {
-                        print_error!("lsd: {}: {}\n", path.display(), err);
-                        exit_code.set_if_greater(ExitCode::MinorIssue);
+                        print_error!("{}: {}.", path.display(), err);
+                        exit_code.set_if_greater(ExitCode::MajorIssue);
                         continue;
                     }
                 };
@@ -148,28 +311,64 @@ impl Core {
                 meta_list.push(meta);
             };
         }
-        // Only calculate the total size of a directory if it will be displayed
-        if self.flags.total_size.0 && self.flags.blocks.displays_size() {
-            for meta in &mut meta_list.iter_mut() {
-                meta.calculate_total_size();
-            }
+        for meta in &mut meta_list {
+            self.calculate(meta);
         }
 
         (meta_list, exit_code)
     }
 
-    fn sort(&self, metas: &mut Vec<Meta>) {
-        metas.sort_unstable_by(|a, b| sort::by_meta(&self.sorters, a, b));
+    /// Compute the values of `meta` which are only read when they are used.
+    fn calculate(&self, meta: &mut Meta) {
+        // Only calculate the total size of a directory if it will be displayed
+        if self.flags.total_size.0
+            && (self.flags.blocks.displays_size() || self.flags.size_summary.0)
+        {
+            meta.calculate_total_size();
+        }
+        // Only count the entries of directories if they are used for sorting
+        if self.flags.sorting.sorts_by(SortColumn::Entries) {
+            meta.calculate_entry_count();
+        }
+        // Only query the file flags if they will be displayed
+        if self.flags.blocks.0.contains(&Block::FileFlags) {
+            meta.calculate_file_flags();
+        }
+        // Only look up the filesystems if they will be displayed
+        if self.flags.blocks.0.contains(&Block::Filesystem) {
+            meta.calculate_filesystem();
+        }
+    }
+
+    fn sort(&self, metas: &mut Vec<Meta>, sorter: &sort::Sorter) {
+        if sorter.is_noop() {
+            return;
+        }
+
+        metas.sort_unstable_by(|a, b| sort::by_meta(sorter, a, b));
 
+        let content_sorter = self.tree_sorter.as_ref().unwrap_or(sorter);
         for meta in metas {
             if let Some(ref mut content) = meta.content {
-                self.sort(content);
+                self.sort(content, content_sorter);
             }
         }
     }
 
-    fn display(&self, metas: &[Meta]) {
-        let output = if self.flags.layout == Layout::Tree {
+    fn display(&self, metas: &[Meta], out: &mut impl Write) -> io::Result<()> {
+        match self.flags.output {
+            OutputFormat::Listing => {}
+            OutputFormat::Json => return write!(out, "{}", display::json(metas, &self.flags)),
+            OutputFormat::Csv => {
+                return display::csv(metas, &self.flags, &self.git_theme, ',', out);
+            }
+            OutputFormat::Tsv => {
+                return display::csv(metas, &self.flags, &self.git_theme, '\t', out);
+            }
+            OutputFormat::Zero => return display::zero(metas, &self.flags, out),
+        }
+
+        let mut output = if self.flags.layout == Layout::Tree {
             display::tree(
                 metas,
                 &self.flags,
@@ -187,6 +386,126 @@ impl Core {
             )
         };
 
-        print_output!("{}", output);
+        if self.flags.size_summary.0 {
+            output.insert_str(0, &display::size_summary(metas, &self.flags, &self.colors));
+        }
+
+        out.write_all(output.as_bytes())
+    }
+}
+
+/// The error of a directory listed while it is read.
+enum StreamError {
+    /// The directory could not be read.
+    Read(io::Error),
+    /// The listing could not be written.
+    Write(io::Error),
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        Self::Read(err)
+    }
+}
+
+/// Remove the paths leading to an entry given before, keeping the first-seen order.
+///
+/// The symlinks are only resolved with `dereference` or a trailing slash, like the listing does,
+/// so that two symlinks to the same target are both listed otherwise. The paths which can not be
+/// resolved are kept as they are.
+fn dedup_paths(paths: Vec<PathBuf>, dereference: bool) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    paths
+        .into_iter()
+        .filter(|path| {
+            let trailing_slash = path.to_string_lossy().ends_with(std::path::is_separator);
+            let canonical = match (
+                dereference || trailing_slash,
+                path.parent(),
+                path.file_name(),
+            ) {
+                (false, Some(parent), Some(name)) => {
+                    // a relative path like `file` has an empty parent
+                    let parent = if parent.as_os_str().is_empty() {
+                        Path::new(".")
+                    } else {
+                        parent
+                    };
+                    parent.canonicalize().map(|parent| parent.join(name))
+                }
+                _ => path.canonicalize(),
+            };
+            seen.insert(canonical.unwrap_or_else(|_| path.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Core;
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+    use crate::ExitCode;
+    use assert_fs::prelude::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_render() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one").touch().unwrap();
+        dir.child("two").touch().unwrap();
+
+        let argv = ["lsd", "--oneline", "--color", "never", "--icon", "never"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let core = Core::new(flags);
+
+        let (output, exit_code) = core.render(vec![dir.path().to_path_buf()]);
+        assert_eq!("one\ntwo\n", output);
+        assert!(exit_code == ExitCode::OK);
+
+        let (output, exit_code) = core.render(vec![dir.path().join("none")]);
+        assert_eq!("", output);
+        assert!(exit_code == ExitCode::MajorIssue);
+    }
+
+    #[test]
+    fn test_render_unsorted_streams() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one").touch().unwrap();
+        dir.child("two").touch().unwrap();
+
+        let argv = ["lsd", "-1U", "--color", "never", "--icon", "never"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let core = Core::new(flags);
+        assert!(core.streams());
+
+        let (output, exit_code) = core.render(vec![dir.path().to_path_buf()]);
+        let mut names: Vec<&str> = output.lines().collect();
+        names.sort_unstable();
+        assert_eq!(vec!["one", "two"], names);
+        assert!(exit_code == ExitCode::OK);
+    }
+
+    #[test]
+    fn test_render_unsorted_csv_streams() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one").write_str("1").unwrap();
+        dir.child("two").write_str("22").unwrap();
+
+        let argv = ["lsd", "-U", "--output", "csv", "--blocks", "size,name"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let core = Core::new(flags);
+        assert!(core.streams());
+
+        let (output, exit_code) = core.render(vec![dir.path().to_path_buf()]);
+        let mut lines: Vec<&str> = output.lines().collect();
+        assert_eq!("size,name", lines.remove(0));
+        lines.sort_unstable();
+        assert_eq!(vec!["1,one", "2,two"], lines);
+        assert!(exit_code == ExitCode::OK);
     }
 }
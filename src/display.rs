@@ -1,11 +1,14 @@
-use crate::color::{Colors, Elem};
+use crate::color::{ColoredString, Colors, Elem, ThemeOption};
 use crate::flags::blocks::Block;
-use crate::flags::{Display, Flags, HyperlinkOption, Layout};
+use crate::flags::{Display, Flags, HyperlinkOption, Layout, OwnerAlign, SizeFlag, TreeCharset};
 use crate::git_theme::GitTheme;
 use crate::icon::Icons;
 use crate::meta::name::DisplayOption;
-use crate::meta::{FileType, Meta, OwnerCache};
+use crate::meta::{FileType, Meta, OwnerCache, Size};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
 use term_grid::{Cell, Direction, Filling, Grid, GridOptions};
 use terminal_size::terminal_size;
 use unicode_width::UnicodeWidthStr;
@@ -15,6 +18,10 @@ const LINE: &str = "\u{2502}  "; // "│  "
 const CORNER: &str = "\u{2514}\u{2500}\u{2500}"; // "└──"
 const BLANK: &str = "   ";
 
+const ASCII_EDGE: &str = "|--";
+const ASCII_LINE: &str = "|  ";
+const ASCII_CORNER: &str = "`--";
+
 pub fn grid(
     metas: &[Meta],
     flags: &Flags,
@@ -22,13 +29,18 @@ pub fn grid(
     icons: &Icons,
     git_theme: &GitTheme,
 ) -> String {
-    let term_width = terminal_size().map(|(w, _)| w.0 as usize);
-    let owner_cache = OwnerCache::default();
+    let term_width = match flags.width.0 {
+        // no width fits the grid, which lists one entry per line
+        Some(0) => None,
+        Some(width) => Some(width),
+        None => terminal_width(),
+    };
+    let owner_cache = OwnerCache::shared();
 
     inner_display_grid(
         &DisplayOption::None,
         metas,
-        &owner_cache,
+        owner_cache,
         flags,
         colors,
         icons,
@@ -38,6 +50,42 @@ pub fn grid(
     )
 }
 
+/// The width to lay the grid out in: like `ls`, the `COLUMNS` environment variable if it is set
+/// to a number, or else the width of the terminal.
+fn terminal_width() -> Option<usize> {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+        .or_else(|| terminal_size().map(|(w, _)| w.0 as usize))
+}
+
+/// Write an entry of the directory `base_path` on its own line, for the listing of the names
+/// alone which is written while the directory is read: the other blocks would need the following
+/// entries to be padded.
+pub fn line(
+    meta: &Meta,
+    base_path: &Path,
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    git_theme: &GitTheme,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let blocks = get_output(
+        meta,
+        OwnerCache::shared(),
+        colors,
+        icons,
+        git_theme,
+        flags,
+        &DisplayOption::Relative { base_path },
+        &HashMap::new(),
+        (0, ""),
+    );
+    writeln!(out, "{}", blocks.concat())
+}
+
 pub fn tree(
     metas: &[Meta],
     flags: &Flags,
@@ -50,7 +98,8 @@ pub fn tree(
         direction: Direction::LeftToRight,
     });
 
-    let padding_rules = get_padding_rules(metas, flags);
+    let owner_cache = OwnerCache::shared();
+    let padding_rules = get_padding_rules(metas, flags, owner_cache);
     let mut index = 0;
     for (i, block) in flags.blocks.0.iter().enumerate() {
         if block == &Block::Name {
@@ -59,11 +108,9 @@ pub fn tree(
         }
     }
 
-    let owner_cache = OwnerCache::default();
-
     for cell in inner_display_tree(
         metas,
-        &owner_cache,
+        owner_cache,
         flags,
         colors,
         icons,
@@ -78,6 +125,408 @@ pub fn tree(
     grid.fit_into_columns(flags.blocks.0.len()).to_string()
 }
 
+/// A [Meta] as printed in the JSON output.
+#[derive(Serialize)]
+struct JsonEntry {
+    name: String,
+    path: String,
+    #[serde(rename = "type")]
+    file_type: &'static str,
+    size: Option<JsonSize>,
+    permissions: Option<String>,
+    user: Option<String>,
+    group: Option<String>,
+    date: Option<String>,
+    symlink_target: Option<String>,
+    sparse: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    children: Option<Vec<JsonEntry>>,
+}
+
+#[derive(Serialize)]
+struct JsonSize {
+    bytes: u64,
+    formatted: String,
+}
+
+/// Print the entries as a JSON array, the content of the directories being nested in their
+/// `children` array. The values are formatted like in the listing, without colors.
+pub fn json(metas: &[Meta], flags: &Flags) -> String {
+    let colors = Colors::new(ThemeOption::NoColor);
+    let owner_cache = OwnerCache::shared();
+    let entries = json_entries(metas, flags, &colors, owner_cache);
+
+    // the entries only hold strings and numbers, which always serialize
+    let mut output = serde_json::to_string_pretty(&entries).unwrap();
+    output.push('\n');
+    output
+}
+
+fn json_entries(
+    metas: &[Meta],
+    flags: &Flags,
+    colors: &Colors,
+    owner_cache: &OwnerCache,
+) -> Vec<JsonEntry> {
+    metas
+        .iter()
+        .map(|meta| JsonEntry {
+            name: meta.name.name.clone(),
+            path: meta.path.to_string_lossy().to_string(),
+            file_type: meta.file_type.name(),
+            size: meta.size.as_ref().map(|size| JsonSize {
+                bytes: size.get_bytes(),
+                formatted: size.render(colors, flags, None).to_string(),
+            }),
+            permissions: meta
+                .permissions_or_attributes
+                .as_ref()
+                .map(|permissions| permissions.render(colors, flags).to_string()),
+            user: meta
+                .owner
+                .as_ref()
+                .map(|owner| owner.user_string(owner_cache, flags)),
+            group: meta
+                .owner
+                .as_ref()
+                .map(|owner| owner.group_string(owner_cache, flags)),
+            date: meta
+                .date
+                .as_ref()
+                .map(|date| date.render(colors, flags, None).to_string()),
+            symlink_target: meta.symlink.symlink_string(),
+            sparse: meta.sparse,
+            children: meta
+                .content
+                .as_ref()
+                .map(|content| json_entries(content, flags, colors, owner_cache)),
+        })
+        .collect()
+}
+
+/// Write a header row then a row per entry, with a column per block and the values separated
+/// by `separator`. The rows are not laid out in a grid, so the unsorted content of a directory
+/// is written with [csv_header] and [csv_row] while it is read.
+///
+/// The content of the directories given by the user is listed in their place, its nested
+/// entries being named by their path relative to that directory.
+pub fn csv(
+    metas: &[Meta],
+    flags: &Flags,
+    git_theme: &GitTheme,
+    separator: char,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    csv_header(flags, separator, out)?;
+
+    let colors = Colors::new(ThemeOption::NoColor);
+    let owner_cache = OwnerCache::shared();
+    for meta in metas {
+        if flags.display != Display::DirectoryOnly && meta.file_type.is_dirlike() {
+            if let Some(content) = &meta.content {
+                let base = meta.path.as_path();
+                write_csv_rows(
+                    content,
+                    base,
+                    flags,
+                    git_theme,
+                    &colors,
+                    owner_cache,
+                    separator,
+                    out,
+                )?;
+                continue;
+            }
+        }
+        let base = meta.path.parent().unwrap_or(&meta.path);
+        write_csv_rows(
+            std::slice::from_ref(meta),
+            base,
+            flags,
+            git_theme,
+            &colors,
+            owner_cache,
+            separator,
+            out,
+        )?;
+    }
+    out.flush()
+}
+
+/// Write the header row of the [csv] output, the name of each block.
+pub fn csv_header(flags: &Flags, separator: char, out: &mut impl Write) -> io::Result<()> {
+    let header = flags
+        .blocks
+        .0
+        .iter()
+        .map(|block| block.csv_name())
+        .collect::<Vec<_>>();
+    write_csv_row(out, &header, separator)
+}
+
+/// Write the row of an entry of the directory `base` of the [csv] output.
+pub fn csv_row(
+    meta: &Meta,
+    base: &Path,
+    flags: &Flags,
+    git_theme: &GitTheme,
+    separator: char,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let colors = Colors::new(ThemeOption::NoColor);
+    write_csv_entry(
+        meta,
+        base,
+        flags,
+        git_theme,
+        &colors,
+        OwnerCache::shared(),
+        separator,
+        out,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_csv_entry(
+    meta: &Meta,
+    base: &Path,
+    flags: &Flags,
+    git_theme: &GitTheme,
+    colors: &Colors,
+    owner_cache: &OwnerCache,
+    separator: char,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let row = flags
+        .blocks
+        .0
+        .iter()
+        .map(|block| csv_value(meta, block, base, flags, git_theme, colors, owner_cache))
+        .collect::<Vec<_>>();
+    write_csv_row(out, &row, separator)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_csv_rows(
+    metas: &[Meta],
+    base: &Path,
+    flags: &Flags,
+    git_theme: &GitTheme,
+    colors: &Colors,
+    owner_cache: &OwnerCache,
+    separator: char,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    for meta in metas {
+        write_csv_entry(
+            meta,
+            base,
+            flags,
+            git_theme,
+            colors,
+            owner_cache,
+            separator,
+            out,
+        )?;
+
+        if let Some(content) = &meta.content {
+            write_csv_rows(
+                content,
+                base,
+                flags,
+                git_theme,
+                colors,
+                owner_cache,
+                separator,
+                out,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// The raw value of a block: the size in bytes, the date in seconds since the epoch, the octal
+/// permissions... An unavailable value is left empty.
+fn csv_value(
+    meta: &Meta,
+    block: &Block,
+    base: &Path,
+    flags: &Flags,
+    git_theme: &GitTheme,
+    colors: &Colors,
+    owner_cache: &OwnerCache,
+) -> String {
+    match block {
+        Block::Permission => match &meta.permissions_or_attributes {
+            Some(permissions) => match permissions.mode() {
+                Some(mode) => format!("{mode:04o}"),
+                None => permissions.render(colors, flags).to_string(),
+            },
+            None => String::new(),
+        },
+        Block::User => meta
+            .owner
+            .as_ref()
+            .map_or_else(String::new, |owner| owner.user(owner_cache, flags)),
+        Block::Group => meta
+            .owner
+            .as_ref()
+            .map_or_else(String::new, |owner| owner.group(owner_cache, flags)),
+        Block::Context => meta
+            .access_control
+            .as_ref()
+            .map_or_else(String::new, |acl| acl.render_context(colors).to_string()),
+        Block::Size | Block::SizeValue => meta
+            .size
+            .as_ref()
+            .map_or_else(String::new, |size| size.get_bytes().to_string()),
+        Block::Date => meta
+            .date
+            .as_ref()
+            .and_then(|date| date.timestamp())
+            .map_or_else(String::new, |timestamp| timestamp.to_string()),
+        Block::Name if flags.full_path.0 => meta.path.to_string_lossy().to_string(),
+        Block::Name => meta.path.strip_prefix(base).map_or_else(
+            |_| meta.name.name.clone(),
+            |path| path.to_string_lossy().to_string(),
+        ),
+        Block::INode => meta
+            .inode
+            .as_ref()
+            .map_or_else(String::new, |inode| inode.value_string()),
+        Block::BlockCount => meta
+            .block_count
+            .as_ref()
+            .map_or_else(String::new, |count| count.value_string()),
+        Block::Links => meta
+            .links
+            .as_ref()
+            .map_or_else(String::new, |links| links.value_string()),
+        Block::FileFlags => meta
+            .file_flags
+            .as_ref()
+            .map_or_else(String::new, |file_flags| file_flags.render().to_string()),
+        Block::Filesystem => meta
+            .filesystem
+            .as_ref()
+            .map_or_else(String::new, |filesystem| filesystem.render().to_string()),
+        Block::GitStatus => meta.git_status.as_ref().map_or_else(String::new, |status| {
+            status.render(colors, git_theme).to_string()
+        }),
+    }
+}
+
+/// Write the values of a row, quoting them like RFC 4180 for the comma separated values and
+/// escaping the tabs and line breaks for the tab separated ones.
+fn write_csv_row(
+    out: &mut impl Write,
+    values: &[impl AsRef<str>],
+    separator: char,
+) -> io::Result<()> {
+    let row = values
+        .iter()
+        .map(|value| {
+            let value = value.as_ref();
+            if separator == '\t' {
+                value
+                    .replace('\\', "\\\\")
+                    .replace('\t', "\\t")
+                    .replace('\n', "\\n")
+                    .replace('\r', "\\r")
+            } else if value.contains([separator, '"', '\n', '\r']) {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&separator.to_string());
+    writeln!(out, "{row}")
+}
+
+/// Write the name of each entry followed by a NUL byte, for `xargs -0` and the like. Like the
+/// CSV output, the content of the directories given by the user is listed in their place, its
+/// nested entries being named by their path relative to that directory, or by their whole path
+/// with `--full-path`, while the files are named by their path as given.
+pub fn zero(metas: &[Meta], flags: &Flags, out: &mut impl Write) -> io::Result<()> {
+    for meta in metas {
+        if flags.display != Display::DirectoryOnly && meta.file_type.is_dirlike() {
+            if let Some(content) = &meta.content {
+                let base = if flags.full_path.0 {
+                    Path::new("")
+                } else {
+                    meta.path.as_path()
+                };
+                write_zero_names(content, base, out)?;
+                continue;
+            }
+        }
+        // the other entries given by the user are named as given, like `ls` does
+        write_zero_names(std::slice::from_ref(meta), Path::new(""), out)?;
+    }
+    out.flush()
+}
+
+fn write_zero_names(metas: &[Meta], base: &Path, out: &mut impl Write) -> io::Result<()> {
+    for meta in metas {
+        let name = meta
+            .path
+            .strip_prefix(base)
+            .unwrap_or_else(|_| Path::new(&meta.name.name));
+        // the names are written as they are, even when they are not valid UTF-8
+        #[cfg(unix)]
+        out.write_all(std::os::unix::ffi::OsStrExt::as_bytes(name.as_os_str()))?;
+        #[cfg(not(unix))]
+        out.write_all(name.to_string_lossy().as_bytes())?;
+        out.write_all(b"\0")?;
+
+        if let Some(content) = &meta.content {
+            write_zero_names(content, base, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// The line summing the sizes of the listed entries, across the whole tree when recursing.
+pub fn size_summary(metas: &[Meta], flags: &Flags, colors: &Colors) -> String {
+    let size = Size::new(listed_size(metas, flags, 0));
+    let mut summary = format!("total {}", size.render_value(colors, flags));
+    if flags.size != SizeFlag::Bytes {
+        if flags.size != SizeFlag::Short {
+            summary.push(' ');
+        }
+        summary.push_str(&size.render_unit(colors, flags).to_string());
+    }
+    summary.push('\n');
+    summary
+}
+
+fn listed_size(metas: &[Meta], flags: &Flags, depth: usize) -> u64 {
+    let mut total = 0;
+    for meta in metas {
+        // the directories given by the user are not listed themselves, only their content
+        let skipped = depth == 0
+            && flags.layout != Layout::Tree
+            && flags.display != Display::DirectoryOnly
+            && (matches!(meta.file_type, FileType::Directory { .. })
+                || (matches!(meta.file_type, FileType::SymLink { is_dir: true, .. })
+                    && flags.layout != Layout::OneLine));
+
+        if !skipped {
+            total += meta.size.as_ref().map_or(0, Size::get_bytes);
+            // the size of the directory already accumulates its content
+            if flags.total_size.0 && matches!(meta.file_type, FileType::Directory { .. }) {
+                continue;
+            }
+        }
+
+        if let Some(content) = &meta.content {
+            total += listed_size(content, flags, depth + 1);
+        }
+    }
+    total
+}
+
 #[allow(clippy::too_many_arguments)] // should wrap flags, colors, icons, git_theme into one struct
 fn inner_display_grid(
     display_option: &DisplayOption,
@@ -93,7 +542,16 @@ fn inner_display_grid(
     let mut output = String::new();
     let mut cells = Vec::new();
 
-    let padding_rules = get_padding_rules(metas, flags);
+    // like `ls -s`, the content of a directory starts with the sum of its blocks
+    if depth > 0 && flags.blocks.0.contains(&Block::BlockCount) {
+        let total: u64 = metas
+            .iter()
+            .filter_map(|meta| meta.block_count.and_then(|count| count.count()))
+            .sum();
+        output += &format!("total {total}\n");
+    }
+
+    let padding_rules = get_padding_rules(metas, flags, owner_cache);
     let mut grid = match flags.layout {
         Layout::OneLine => Grid::new(GridOptions {
             filling: Filling::Spaces(1),
@@ -101,7 +559,7 @@ fn inner_display_grid(
         }),
         _ => Grid::new(GridOptions {
             filling: Filling::Spaces(2),
-            direction: Direction::TopToBottom,
+            direction: flags.grid_direction.direction(),
         }),
     };
 
@@ -115,7 +573,7 @@ fn inner_display_grid(
         // Maybe skip showing the directory meta now; show its contents later.
         if skip_dirs
             && (matches!(meta.file_type, FileType::Directory { .. })
-                || (matches!(meta.file_type, FileType::SymLink { is_dir: true })
+                || (matches!(meta.file_type, FileType::SymLink { is_dir: true, .. })
                     && flags.layout != Layout::OneLine))
         {
             continue;
@@ -134,8 +592,10 @@ fn inner_display_grid(
         );
 
         for block in blocks {
+            // The extended attributes are listed on the lines following the name
+            let first_line = block.lines().next().unwrap_or_default();
             cells.push(Cell {
-                width: get_visible_width(&block, flags.hyperlink == HyperlinkOption::Always),
+                width: get_visible_width(first_line, flags.hyperlink == HyperlinkOption::Always),
                 contents: block,
             });
         }
@@ -173,7 +633,13 @@ fn inner_display_grid(
     for meta in metas {
         if let Some(content) = &meta.content {
             if should_display_folder_path {
-                output += &display_folder_path(meta);
+                let folder_path = display_folder_path(meta);
+                // only the sections after another one are separated by a blank line
+                if output.is_empty() {
+                    output += folder_path.trim_start_matches('\n');
+                } else {
+                    output += &folder_path;
+                }
             }
 
             let display_option = DisplayOption::Relative {
@@ -242,14 +708,18 @@ fn inner_display_tree(
 ) -> Vec<Cell> {
     let mut cells = Vec::new();
     let last_idx = metas.len();
+    let (edge, line, corner) = match flags.tree_charset {
+        TreeCharset::Unicode => (EDGE, LINE, CORNER),
+        TreeCharset::Ascii => (ASCII_EDGE, ASCII_LINE, ASCII_CORNER),
+    };
 
     for (idx, meta) in metas.iter().enumerate() {
         let current_prefix = if tree_depth_prefix.0 > 0 {
             if idx + 1 != last_idx {
                 // is last folder elem
-                format!("{}{} ", tree_depth_prefix.1, EDGE)
+                format!("{}{} ", tree_depth_prefix.1, edge)
             } else {
-                format!("{}{} ", tree_depth_prefix.1, CORNER)
+                format!("{}{} ", tree_depth_prefix.1, corner)
             }
         } else {
             tree_depth_prefix.1.to_string()
@@ -276,7 +746,7 @@ fn inner_display_tree(
             let new_prefix = if tree_depth_prefix.0 > 0 {
                 if idx + 1 != last_idx {
                     // is last folder elem
-                    format!("{}{} ", tree_depth_prefix.1, LINE)
+                    format!("{}{} ", tree_depth_prefix.1, line)
                 } else {
                     format!("{}{} ", tree_depth_prefix.1, BLANK)
                 }
@@ -309,12 +779,13 @@ fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bo
             .iter()
             .filter(|x| {
                 matches!(x.file_type, FileType::Directory { .. })
-                    || (matches!(x.file_type, FileType::SymLink { is_dir: true })
+                    || (matches!(x.file_type, FileType::SymLink { is_dir: true, .. })
                         && flags.layout != Layout::OneLine)
             })
             .count();
 
-        folder_number > 1 || folder_number < metas.len()
+        // like `ls -R`, a recursive listing heads every directory with its path
+        flags.recursion.enabled || folder_number > 1 || folder_number < metas.len()
     }
 }
 
@@ -346,13 +817,27 @@ fn get_output(
 
         match block {
             Block::INode => block_vec.push(match &meta.inode {
-                Some(inode) => inode.render(colors),
+                Some(inode) => inode.render(colors, padding_rules.get(&Block::INode).copied()),
+                None => colorize_missing("?"),
+            }),
+            Block::BlockCount => block_vec.push(match &meta.block_count {
+                Some(count) => count.render(padding_rules.get(&Block::BlockCount).copied()),
                 None => colorize_missing("?"),
             }),
             Block::Links => block_vec.push(match &meta.links {
-                Some(links) => links.render(colors),
+                Some(links) => links.render(colors, padding_rules.get(&Block::Links).copied()),
                 None => colorize_missing("?"),
             }),
+            Block::FileFlags => block_vec.push(match &meta.file_flags {
+                Some(file_flags) => file_flags.render(),
+                // the flags could not be queried on this file
+                None => ColoredString::new(Colors::default_style(), String::new()),
+            }),
+            Block::Filesystem => block_vec.push(match &meta.filesystem {
+                Some(filesystem) => filesystem.render(),
+                // the filesystem is not in the mount table, or not available on this platform
+                None => ColoredString::new(Colors::default_style(), String::new()),
+            }),
             Block::Permission => {
                 block_vec.extend([
                     meta.file_type.render(colors),
@@ -369,11 +854,21 @@ fn get_output(
                 ]);
             }
             Block::User => block_vec.push(match &meta.owner {
-                Some(owner) => owner.render_user(colors, owner_cache, flags),
+                Some(owner) => owner.render_user(
+                    colors,
+                    owner_cache,
+                    flags,
+                    padding_rules.get(&Block::User).copied(),
+                ),
                 None => colorize_missing("?"),
             }),
             Block::Group => block_vec.push(match &meta.owner {
-                Some(owner) => owner.render_group(colors, owner_cache, flags),
+                Some(owner) => owner.render_group(
+                    colors,
+                    owner_cache,
+                    flags,
+                    padding_rules.get(&Block::Group).copied(),
+                ),
                 None => colorize_missing("?"),
             }),
             Block::Context => block_vec.push(match &meta.access_control {
@@ -396,23 +891,52 @@ fn get_output(
                 None => colorize_missing("?"),
             }),
             Block::Date => block_vec.push(match &meta.date {
-                Some(date) => date.render(colors, flags),
+                Some(date) => date.render(colors, flags, padding_rules.get(&Block::Date).copied()),
                 None => colorize_missing("?"),
             }),
             Block::Name => {
-                block_vec.extend([
-                    meta.name.render(
-                        colors,
-                        icons,
-                        display_option,
-                        flags.hyperlink,
-                        flags.literal.0,
-                    ),
-                    meta.indicator.render(flags),
-                ]);
+                let display_option = if flags.full_path.0 {
+                    &DisplayOption::None
+                } else {
+                    display_option
+                };
+                let name = meta.name.render(
+                    colors,
+                    icons,
+                    display_option,
+                    flags.hyperlink,
+                    flags.quoting_style,
+                    flags.truncate.0,
+                );
+                // with the git block, the names of the changed entries take the color of the change
+                let name = match meta.git_status.and_then(|status| status.name_elem()) {
+                    Some(elem) if flags.blocks.0.contains(&Block::GitStatus) => {
+                        colors.colorize(name.content().clone(), &elem)
+                    }
+                    _ => name,
+                };
+                block_vec.extend([name, meta.indicator.render(flags)]);
                 if !(flags.no_symlink.0 || flags.dereference.0 || flags.layout == Layout::Grid) {
                     block_vec.push(meta.symlink.render(colors, flags))
                 }
+                // the tree does not show the content of this directory
+                if meta.truncated && flags.layout == Layout::Tree {
+                    let marker = match flags.tree_charset {
+                        TreeCharset::Unicode => " \u{2026}",
+                        TreeCharset::Ascii => " ...",
+                    };
+                    block_vec.push(colors.colorize(marker, &Elem::TreeEdge));
+                }
+                if meta.recursion {
+                    block_vec.push(colors.colorize(" [recursion]", &Elem::TreeEdge));
+                }
+                if flags.xattr.0 && flags.layout == Layout::OneLine {
+                    if let Some(access_control) = &meta.access_control {
+                        block_vec.extend(access_control.xattrs().iter().map(|name| {
+                            ColoredString::new(Colors::default_style(), format!("\n\t{name}"))
+                        }));
+                    }
+                }
             }
             Block::GitStatus => {
                 if let Some(_s) = &meta.git_status {
@@ -440,7 +964,7 @@ fn get_visible_width(input: &str, hyperlink: bool) -> usize {
 
         let m_pos = s.find('m');
         if let Some(len) = m_pos {
-            nb_invisible_char += len
+            nb_invisible_char += UnicodeWidthStr::width(&s[..=len])
         }
     }
 
@@ -450,7 +974,7 @@ fn get_visible_width(input: &str, hyperlink: bool) -> usize {
 
             let m_pos = s.find("\x1B\x5C");
             if let Some(len) = m_pos {
-                nb_invisible_char += len
+                nb_invisible_char += UnicodeWidthStr::width(&s[..len + 2])
             }
         }
     }
@@ -484,15 +1008,100 @@ fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
     max_value_length
 }
 
-fn get_padding_rules(metas: &[Meta], flags: &Flags) -> HashMap<Block, usize> {
+/// Get the length of the widest value of a numeric block, given by `value_length`.
+fn detect_numeric_lengths(
+    metas: &[Meta],
+    flags: &Flags,
+    value_length: &dyn Fn(&Meta) -> Option<usize>,
+) -> usize {
+    let mut max_value_length: usize = 0;
+
+    for meta in metas {
+        if let Some(len) = value_length(meta) {
+            max_value_length = max_value_length.max(len);
+        }
+
+        if Layout::Tree == flags.layout {
+            if let Some(subs) = &meta.content {
+                max_value_length =
+                    max_value_length.max(detect_numeric_lengths(subs, flags, value_length));
+            }
+        }
+    }
+
+    max_value_length
+}
+
+fn get_padding_rules(
+    metas: &[Meta],
+    flags: &Flags,
+    owner_cache: &OwnerCache,
+) -> HashMap<Block, usize> {
     let mut padding_rules: HashMap<Block, usize> = HashMap::new();
 
+    if flags.owner_align == OwnerAlign::Right {
+        if flags.blocks.0.contains(&Block::User) {
+            let user_len = detect_numeric_lengths(metas, flags, &|meta| {
+                meta.owner
+                    .as_ref()
+                    .map(|owner| owner.user_string(owner_cache, flags).width())
+            });
+
+            padding_rules.insert(Block::User, user_len);
+        }
+
+        if flags.blocks.0.contains(&Block::Group) {
+            let group_len = detect_numeric_lengths(metas, flags, &|meta| {
+                meta.owner
+                    .as_ref()
+                    .map(|owner| owner.group_string(owner_cache, flags).width())
+            });
+
+            padding_rules.insert(Block::Group, group_len);
+        }
+    }
+
+    if flags.blocks.0.contains(&Block::INode) {
+        let inode_len = detect_numeric_lengths(metas, flags, &|meta| {
+            meta.inode.map(|inode| inode.value_string().len())
+        });
+
+        padding_rules.insert(Block::INode, inode_len);
+    }
+
+    if flags.blocks.0.contains(&Block::BlockCount) {
+        let count_len = detect_numeric_lengths(metas, flags, &|meta| {
+            meta.block_count.map(|count| count.value_string().len())
+        });
+
+        padding_rules.insert(Block::BlockCount, count_len);
+    }
+
+    if flags.blocks.0.contains(&Block::Links) {
+        let links_len = detect_numeric_lengths(metas, flags, &|meta| {
+            meta.links.map(|links| links.value_string().len())
+        });
+
+        padding_rules.insert(Block::Links, links_len);
+    }
+
     if flags.blocks.0.contains(&Block::Size) {
         let size_val = detect_size_lengths(metas, flags);
 
         padding_rules.insert(Block::SizeValue, size_val);
     }
 
+    // the dates are right-aligned, like the sizes, when their formats are not all as wide
+    if flags.blocks.0.contains(&Block::Date) {
+        let date_len = detect_numeric_lengths(metas, flags, &|meta| {
+            meta.date
+                .as_ref()
+                .map(|date| date.date_string(flags).width())
+        });
+
+        padding_rules.insert(Block::Date, date_len);
+    }
+
     padding_rules
 }
 
@@ -502,11 +1111,14 @@ mod tests {
     use crate::app::Cli;
     use crate::color;
     use crate::color::Colors;
-    use crate::flags::{HyperlinkOption, IconOption, IconTheme as FlagTheme, PermissionFlag};
+    use crate::config_file::Config;
+    use crate::flags::{
+        Blocks, DateSource, GridDirection, HyperlinkOption, IconOption, IconTheme as FlagTheme,
+        PermissionFlag, QuotingStyle, TotalSize,
+    };
     use crate::icon::Icons;
     use crate::meta::{FileType, Name};
-    use crate::Config;
-    use crate::{flags, sort};
+    use crate::sort;
     use assert_fs::prelude::*;
     use clap::Parser;
     use std::path::Path;
@@ -515,13 +1127,16 @@ mod tests {
     #[test]
     fn test_display_get_visible_width_without_icons() {
         for (s, l) in [
-            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 22),
+            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 24),
             ("ASCII1234-_", 11),
             ("制作样本。", 10),
             ("日本語", 6),
             ("샘플은 무료로 드리겠습니다", 28),
             ("👩🐩", 4),
             ("🔬", 2),
+            // an emoji sequence joined by zero width joiners is shown as one emoji
+            ("👨\u{200d}👩\u{200d}👧.txt", 6),
+            ("e\u{301}.txt", 5),
         ] {
             let path = Path::new(s);
             let name = Name::new(
@@ -537,7 +1152,8 @@ mod tests {
                     &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
                     &DisplayOption::FileName,
                     HyperlinkOption::Never,
-                    false,
+                    QuotingStyle::ShellEscape,
+                    None,
                 )
                 .to_string();
 
@@ -549,7 +1165,7 @@ mod tests {
     fn test_display_get_visible_width_with_icons() {
         for (s, l) in [
             // Add 3 characters for the icons.
-            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 24),
+            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 26),
             ("ASCII1234-_", 13),
             ("File with space", 19),
             ("制作样本。", 12),
@@ -572,7 +1188,38 @@ mod tests {
                     &Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string()),
                     &DisplayOption::FileName,
                     HyperlinkOption::Never,
-                    false,
+                    QuotingStyle::ShellEscape,
+                    None,
+                )
+                .to_string();
+
+            assert_eq!(get_visible_width(&output, false), l);
+        }
+    }
+
+    #[test]
+    fn test_display_get_visible_width_with_icon_separator() {
+        let name = Name::new(
+            Path::new("file"),
+            FileType::File {
+                exec: false,
+                uid: false,
+            },
+        );
+        for (separator, l) in [("", 5), (" ", 6), (" | ", 8)] {
+            let output = name
+                .render(
+                    &Colors::new(color::ThemeOption::NoColor),
+                    &Icons::new(
+                        false,
+                        IconOption::Always,
+                        FlagTheme::Fancy,
+                        separator.to_string(),
+                    ),
+                    &DisplayOption::FileName,
+                    HyperlinkOption::Never,
+                    QuotingStyle::ShellEscape,
+                    None,
                 )
                 .to_string();
 
@@ -583,7 +1230,7 @@ mod tests {
     #[test]
     fn test_display_get_visible_width_with_colors() {
         for (s, l) in [
-            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 22),
+            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 24),
             ("ASCII1234-_", 11),
             ("File with space", 17),
             ("制作样本。", 10),
@@ -606,7 +1253,8 @@ mod tests {
                     &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
                     &DisplayOption::FileName,
                     HyperlinkOption::Never,
-                    false,
+                    QuotingStyle::ShellEscape,
+                    None,
                 )
                 .to_string();
 
@@ -624,7 +1272,7 @@ mod tests {
     #[test]
     fn test_display_get_visible_width_without_colors() {
         for (s, l) in [
-            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 22),
+            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 24),
             ("ASCII1234-_", 11),
             ("File with space", 17),
             ("制作样本。", 10),
@@ -647,7 +1295,8 @@ mod tests {
                     &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
                     &DisplayOption::FileName,
                     HyperlinkOption::Never,
-                    false,
+                    QuotingStyle::ShellEscape,
+                    None,
                 )
                 .to_string();
 
@@ -677,12 +1326,12 @@ mod tests {
         }
     }
 
-    fn sort(metas: &mut Vec<Meta>, sorters: &Vec<(flags::SortOrder, sort::SortFn)>) {
-        metas.sort_unstable_by(|a, b| sort::by_meta(sorters, a, b));
+    fn sort(metas: &mut Vec<Meta>, sorter: &sort::Sorter) {
+        metas.sort_unstable_by(|a, b| sort::by_meta(sorter, a, b));
 
         for meta in metas {
             if let Some(ref mut content) = meta.content {
-                sort(content, sorters);
+                sort(content, sorter);
             }
         }
     }
@@ -697,12 +1346,17 @@ mod tests {
         dir.child("one.d").create_dir_all().unwrap();
         dir.child("one.d/two").touch().unwrap();
         dir.child("one.d/.hidden").touch().unwrap();
-        let mut metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
-            .unwrap()
-            .recurse_into(42, &flags, None)
-            .unwrap()
-            .0
-            .unwrap();
+        let mut metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(42, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
         sort(&mut metas, &sort::assemble_sorters(&flags));
         let output = tree(
             &metas,
@@ -730,12 +1384,17 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("dir").create_dir_all().unwrap();
         dir.child("dir/file").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
-            .unwrap()
-            .recurse_into(42, &flags, None)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(42, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
         let output = tree(
             &metas,
             &flags,
@@ -764,19 +1423,28 @@ mod tests {
     #[test]
     #[cfg(unix)]
     fn test_tree_size_first_without_name() {
-        let argv = ["lsd", "--tree", "--blocks", "size,permission"];
+        let argv = ["lsd", "--tree"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        // a name block is always configured, the blocks are set to lay a tree out without one
+        let flags = Flags {
+            blocks: Blocks(vec![Block::Size, Block::Permission]),
+            ..Flags::configure_from(&cli, &Config::with_none()).unwrap()
+        };
 
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("dir").create_dir_all().unwrap();
         dir.child("dir/file").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
-            .unwrap()
-            .recurse_into(42, &flags, None)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(42, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
         let output = tree(
             &metas,
             &flags,
@@ -802,6 +1470,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_long_aligns_dates_right() {
+        let argv = [
+            "lsd",
+            "--long",
+            "--blocks",
+            "date,name",
+            "--date",
+            "+%-d %b",
+        ];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a").touch().unwrap();
+        dir.child("b").touch().unwrap();
+        let mut metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(1, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
+        metas.sort_by(|a, b| a.name.name.cmp(&b.name.name));
+        let day = |day| {
+            use chrono::TimeZone;
+            let date = chrono::Local.with_ymd_and_hms(2024, 1, day, 12, 0, 0);
+            Some(crate::meta::Date::Date(date.unwrap()))
+        };
+        metas[0].date = day(5);
+        metas[1].date = day(25);
+
+        let output = grid(
+            &metas,
+            &Flags {
+                layout: Layout::OneLine,
+                ..flags
+            },
+            &Colors::new(color::ThemeOption::NoColor),
+            &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+            &GitTheme::new(),
+        );
+        let dates: Vec<&str> = output.lines().map(|line| &line[..7]).collect();
+        assert_eq!(vec![" 5 Jan ", "25 Jan "], dates);
+    }
+
     #[test]
     fn test_tree_edge_before_name() {
         let argv = ["lsd", "--tree", "--long"];
@@ -811,12 +1529,17 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("one.d").create_dir_all().unwrap();
         dir.child("one.d/two").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
-            .unwrap()
-            .recurse_into(42, &flags, None)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(42, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
         let output = tree(
             &metas,
             &flags,
@@ -828,6 +1551,44 @@ mod tests {
         assert!(output.ends_with("└── two\n"));
     }
 
+    #[test]
+    fn test_tree_ascii_edges() {
+        let argv = ["lsd", "--tree", "--tree-charset", "ascii"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d/sub").create_dir_all().unwrap();
+        dir.child("one.d/sub/x").touch().unwrap();
+        dir.child("one.d/two").touch().unwrap();
+        let mut metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(42, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
+        // the entries are not sorted by recurse_into
+        metas[0]
+            .content
+            .as_mut()
+            .unwrap()
+            .sort_by(|a, b| a.name.name.cmp(&b.name.name));
+        let output = tree(
+            &metas,
+            &flags,
+            &Colors::new(color::ThemeOption::NoColor),
+            &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+            &GitTheme::new(),
+        );
+
+        assert_eq!("one.d\n|-- sub\n|   `-- x\n`-- two\n", output);
+    }
+
     #[test]
     fn test_grid_all_block_headers() {
         let argv = [
@@ -842,12 +1603,17 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("testdir").create_dir_all().unwrap();
         dir.child("test").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
-            .unwrap()
-            .recurse_into(1, &flags, None)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(1, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
         let output = grid(
             &metas,
             &flags,
@@ -876,12 +1642,17 @@ mod tests {
 
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("testdir").create_dir_all().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
-            .unwrap()
-            .recurse_into(1, &flags, None)
-            .unwrap()
-            .0
-            .unwrap();
+        let metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(1, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
         let output = grid(
             &metas,
             &flags,
@@ -906,11 +1677,13 @@ mod tests {
 
         let file_path = tmp_dir.path().join("file");
         std::fs::File::create(&file_path).expect("failed to create the file");
-        let file = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let file =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let dir_path = tmp_dir.path().join("dir");
         std::fs::create_dir(&dir_path).expect("failed to create the dir");
-        let dir = Meta::from_path(&dir_path, false, PermissionFlag::Rwx).unwrap();
+        let dir =
+            Meta::from_path(&dir_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         assert_eq!(
             display_folder_path(&dir),
@@ -956,15 +1729,18 @@ mod tests {
 
         let file_path = tmp_dir.path().join("file");
         std::fs::File::create(&file_path).expect("failed to create the file");
-        let file = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let file =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let dir_path = tmp_dir.path().join("dir");
         std::fs::create_dir(&dir_path).expect("failed to create the dir");
-        let dir = Meta::from_path(&dir_path, false, PermissionFlag::Rwx).unwrap();
+        let dir =
+            Meta::from_path(&dir_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let link_path = tmp_dir.path().join("link");
         std::os::unix::fs::symlink("dir", &link_path).unwrap();
-        let link = Meta::from_path(&link_path, false, PermissionFlag::Rwx).unwrap();
+        let link =
+            Meta::from_path(&link_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let grid_flags = Flags {
             layout: Layout::Grid,
@@ -1010,4 +1786,293 @@ mod tests {
         drop(file);
         drop(link);
     }
+
+    #[test]
+    fn test_size_summary() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let file_path = tmp_dir.path().join("file");
+        std::fs::write(&file_path, "1234").expect("failed to write the file");
+        let file =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
+
+        let mut dir = Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        dir.size = Some(Size::new(10));
+        let mut accumulated_dir = dir.clone();
+        dir.content = Some(vec![file.clone(), file.clone()]);
+        accumulated_dir.content = Some(vec![file.clone()]);
+        accumulated_dir.calculate_total_size();
+
+        let colors = Colors::new(color::ThemeOption::NoColor);
+        let flags = Flags {
+            size: SizeFlag::Bytes,
+            ..Flags::default()
+        };
+
+        // only the content of the given directory is listed
+        assert_eq!(8, listed_size(&[dir.clone()], &flags, 0));
+        assert_eq!("total 8\n", size_summary(&[dir.clone()], &flags, &colors));
+
+        // the nested directory counts its own entry
+        assert_eq!(18, listed_size(&[dir], &flags, 1));
+
+        // unless its size accumulates its content
+        let flags = Flags {
+            total_size: TotalSize(true),
+            ..flags
+        };
+        assert_eq!(14, listed_size(&[accumulated_dir], &flags, 1));
+    }
+
+    #[test]
+    fn test_json_nests_the_children() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        std::fs::write(tmp_dir.path().join("file"), "1234").expect("failed to write the file");
+
+        let mut dir = Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        dir.content = Some(vec![Meta::from_path(
+            &tmp_dir.path().join("file"),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()]);
+
+        let flags = Flags::default();
+        let output: serde_json::Value = serde_json::from_str(&json(&[dir], &flags)).unwrap();
+
+        assert_eq!("directory", output[0]["type"]);
+        let child = &output[0]["children"][0];
+        assert_eq!("file", child["name"]);
+        assert_eq!("file", child["type"]);
+        assert_eq!(4, child["size"]["bytes"]);
+        assert_eq!("4 B", child["size"]["formatted"]);
+        assert!(child["symlink_target"].is_null());
+        assert_eq!(false, child["sparse"]);
+        assert!(child.get("children").is_none());
+    }
+
+    #[test]
+    fn test_csv_row_quoting() {
+        let mut out = Vec::new();
+        write_csv_row(&mut out, &["a", "b,c", "say \"hi\"", "new\nline"], ',').unwrap();
+        assert_eq!(
+            "a,\"b,c\",\"say \"\"hi\"\"\",\"new\nline\"\n",
+            String::from_utf8(out).unwrap()
+        );
+
+        let mut out = Vec::new();
+        write_csv_row(&mut out, &["a b", "tab\there", "new\nline"], '\t').unwrap();
+        assert_eq!(
+            "a b\ttab\\there\tnew\\nline\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_csv_lists_the_content_of_directories() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let sub_path = tmp_dir.path().join("sub");
+        std::fs::create_dir(&sub_path).expect("failed to create the dir");
+        std::fs::write(sub_path.join("file"), "1234").expect("failed to write the file");
+
+        let meta = |path: &Path| {
+            Meta::from_path(path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap()
+        };
+        let mut sub = meta(&sub_path);
+        sub.content = Some(vec![meta(&sub_path.join("file"))]);
+        let mut dir = meta(tmp_dir.path());
+        dir.content = Some(vec![sub]);
+
+        let flags = Flags {
+            blocks: Blocks(vec![Block::Name, Block::Size]),
+            ..Flags::default()
+        };
+        let sub_size = sub_path.symlink_metadata().unwrap().len();
+
+        let mut out = Vec::new();
+        csv(&[dir], &flags, &GitTheme::new(), ',', &mut out).unwrap();
+        assert_eq!(
+            format!(
+                "name,size\nsub,{sub_size}\nsub{}file,4\n",
+                std::path::MAIN_SEPARATOR
+            ),
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_zero_lists_the_content_of_directories() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let sub_path = tmp_dir.path().join("sub");
+        std::fs::create_dir(&sub_path).expect("failed to create the dir");
+        std::fs::write(sub_path.join("file with\nnewline"), "").expect("failed to write");
+        std::fs::write(tmp_dir.path().join("file"), "").expect("failed to write the file");
+
+        let meta = |path: &Path| {
+            Meta::from_path(path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap()
+        };
+        let mut sub = meta(&sub_path);
+        sub.content = Some(vec![meta(&sub_path.join("file with\nnewline"))]);
+        let mut dir = meta(tmp_dir.path());
+        dir.content = Some(vec![sub]);
+        let file = meta(&tmp_dir.path().join("file"));
+
+        let mut out = Vec::new();
+        zero(&[dir, file], &Flags::default(), &mut out).unwrap();
+        assert_eq!(
+            format!(
+                "sub\0sub{}file with\nnewline\0{}\0",
+                std::path::MAIN_SEPARATOR,
+                tmp_dir.path().join("file").display()
+            ),
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_full_path_names_the_nested_entries_by_their_path() {
+        let argv = ["lsd", "--full-path", "--recursive"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("sub").create_dir_all().unwrap();
+        dir.child("sub/file").touch().unwrap();
+        let metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(2, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
+        let sub_file = dir.path().join("sub").join("file");
+
+        let output = grid(
+            &metas,
+            &Flags {
+                layout: Layout::OneLine,
+                ..flags.clone()
+            },
+            &Colors::new(color::ThemeOption::NoColor),
+            &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+            &GitTheme::new(),
+        );
+        assert!(
+            output.contains(&format!("{}\n", sub_file.display())),
+            "{output}"
+        );
+
+        let mut out = Vec::new();
+        zero(&metas, &flags, &mut out).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with(&format!("{}\0", sub_file.display())));
+    }
+
+    #[test]
+    fn test_grid_packs_wide_names_into_the_width() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        for name in ["日本語", "abc", "制作样本", "d"] {
+            dir.child(name).touch().unwrap();
+        }
+        let flags = Flags::default();
+        let mut metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(1, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
+        metas.sort_by(|a, b| a.name.name.cmp(&b.name.name));
+
+        let display = |term_width, flags: &Flags| {
+            inner_display_grid(
+                &DisplayOption::FileName,
+                &metas,
+                &OwnerCache::default(),
+                flags,
+                &Colors::new(color::ThemeOption::NoColor),
+                &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+                &GitTheme::new(),
+                1,
+                Some(term_width),
+            )
+        };
+
+        // the CJK names are two columns wide per character
+        assert_eq!("abc  制作样本\nd    日本語\n", display(14, &flags));
+        assert_eq!("abc\nd\n制作样本\n日本語\n", display(13, &flags));
+
+        let flags = Flags {
+            grid_direction: GridDirection::Across,
+            ..flags
+        };
+        assert_eq!("abc       d\n制作样本  日本語\n", display(17, &flags));
+        assert_eq!("abc\nd\n制作样本\n日本語\n", display(16, &flags));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_long_aligns_wide_names() {
+        let argv = ["lsd", "--long", "--blocks", "name,size"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        for name in [
+            "日本語.txt",
+            "a.txt",
+            "👨\u{200d}👩\u{200d}👧.txt",
+            "e\u{301}.txt",
+        ] {
+            dir.child(name).touch().unwrap();
+        }
+        let metas = Meta::from_path(
+            Path::new(dir.path()),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap()
+        .recurse_into(1, &flags, None)
+        .unwrap()
+        .0
+        .unwrap();
+        let output = grid(
+            &metas,
+            &flags,
+            &Colors::new(color::ThemeOption::NoColor),
+            &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+            &GitTheme::new(),
+        );
+
+        // the sizes start in the same column after the names of any width
+        let widths: Vec<usize> = output
+            .lines()
+            .map(|line| get_visible_width(line, false))
+            .collect();
+        assert_eq!(4, widths.len());
+        assert!(widths.iter().all(|width| *width == widths[0]), "{output}");
+    }
 }
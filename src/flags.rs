@@ -1,8 +1,15 @@
 pub mod blocks;
 pub mod color;
 pub mod date;
+pub mod date_color;
+pub mod date_source;
 pub mod dereference;
+pub mod dereference_command_line;
 pub mod display;
+pub mod follow_symlinks;
+pub mod full_path;
+pub mod gitignore;
+pub mod grid_direction;
 pub mod header;
 pub mod hyperlink;
 pub mod icons;
@@ -10,21 +17,42 @@ pub mod ignore_globs;
 pub mod indicators;
 pub mod layout;
 pub mod literal;
+pub mod numeric_uid_gid;
+pub mod output;
+pub mod owner_align;
 pub mod permission;
+pub mod quoting_style;
 pub mod recursion;
 pub mod size;
+pub mod size_separator;
+pub mod size_summary;
+pub mod size_unit;
 pub mod sorting;
 pub mod symlink_arrow;
+pub mod symlink_color;
+pub mod symlink_color_target;
 pub mod symlinks;
 pub mod total_size;
+pub mod tree_charset;
+pub mod truncate;
 pub mod truncate_owner;
+pub mod utc;
+pub mod width;
+pub mod xattr;
 
 pub use blocks::Blocks;
 pub use color::Color;
-pub use color::{ColorOption, ThemeOption};
+pub use color::{ColorDepth, ColorOption, ColorSource, ThemeOption};
 pub use date::DateFlag;
+pub use date_color::NoDateColor;
+pub use date_source::DateSource;
 pub use dereference::Dereference;
+pub use dereference_command_line::DereferenceCommandLine;
 pub use display::Display;
+pub use follow_symlinks::FollowSymlinks;
+pub use full_path::FullPath;
+pub use gitignore::Gitignore;
+pub use grid_direction::GridDirection;
 pub use header::Header;
 pub use hyperlink::HyperlinkOption;
 pub use icons::IconOption;
@@ -34,17 +62,37 @@ pub use ignore_globs::IgnoreGlobs;
 pub use indicators::Indicators;
 pub use layout::Layout;
 pub use literal::Literal;
+pub use numeric_uid_gid::NumericUidGid;
+pub use output::OutputFormat;
+pub use owner_align::OwnerAlign;
 pub use permission::PermissionFlag;
+pub use quoting_style::QuotingStyle;
 pub use recursion::Recursion;
 pub use size::SizeFlag;
+pub use size_separator::SizeSeparator;
+pub use size_summary::SizeSummary;
+pub use size_unit::SizeUnit;
+pub use sorting::CaseSensitivity;
+pub use sorting::Collation;
 pub use sorting::DirGrouping;
+pub use sorting::DirsAmongFiles;
+pub use sorting::HiddenGrouping;
+pub use sorting::ReverseAll;
 pub use sorting::SortColumn;
 pub use sorting::SortOrder;
 pub use sorting::Sorting;
+pub use sorting::SymlinkGrouping;
 pub use symlink_arrow::SymlinkArrow;
+pub use symlink_color::NoSymlinkColor;
+pub use symlink_color_target::SymlinkColorTarget;
 pub use symlinks::NoSymlink;
 pub use total_size::TotalSize;
+pub use tree_charset::TreeCharset;
+pub use truncate::Truncate;
 pub use truncate_owner::TruncateOwner;
+pub use utc::Utc;
+pub use width::Width;
+pub use xattr::Xattr;
 
 use crate::app::Cli;
 use crate::config_file::Config;
@@ -60,23 +108,44 @@ pub struct Flags {
     pub blocks: Blocks,
     pub color: Color,
     pub date: DateFlag,
+    pub date_source: DateSource,
     pub dereference: Dereference,
+    pub dereference_command_line: DereferenceCommandLine,
+    pub follow_symlinks: FollowSymlinks,
     pub display: Display,
     pub display_indicators: Indicators,
     pub icons: Icons,
     pub ignore_globs: IgnoreGlobs,
+    pub gitignore: Gitignore,
     pub layout: Layout,
+    pub grid_direction: GridDirection,
+    pub width: Width,
+    pub truncate: Truncate,
     pub no_symlink: NoSymlink,
     pub recursion: Recursion,
+    pub tree_charset: TreeCharset,
     pub size: SizeFlag,
+    pub size_unit: SizeUnit,
+    pub size_separator: SizeSeparator,
     pub permission: PermissionFlag,
     pub sorting: Sorting,
     pub total_size: TotalSize,
+    pub size_summary: SizeSummary,
     pub symlink_arrow: SymlinkArrow,
     pub hyperlink: HyperlinkOption,
     pub header: Header,
+    pub output: OutputFormat,
     pub literal: Literal,
+    pub full_path: FullPath,
+    pub quoting_style: QuotingStyle,
     pub truncate_owner: TruncateOwner,
+    pub numeric_uid_gid: NumericUidGid,
+    pub owner_align: OwnerAlign,
+    pub utc: Utc,
+    pub no_date_color: NoDateColor,
+    pub no_symlink_color: NoSymlinkColor,
+    pub symlink_color_target: SymlinkColorTarget,
+    pub xattr: Xattr,
 }
 
 impl Flags {
@@ -91,23 +160,44 @@ impl Flags {
             blocks: Blocks::configure_from(cli, config),
             color: Color::configure_from(cli, config),
             date: DateFlag::configure_from(cli, config),
+            date_source: DateSource::configure_from(cli, config),
             dereference: Dereference::configure_from(cli, config),
+            dereference_command_line: DereferenceCommandLine::configure_from(cli, config),
+            follow_symlinks: FollowSymlinks::configure_from(cli, config),
             display: Display::configure_from(cli, config),
             layout: Layout::configure_from(cli, config),
+            grid_direction: GridDirection::configure_from(cli, config),
+            width: Width::configure_from(cli, config),
+            truncate: Truncate::configure_from(cli, config),
             size: SizeFlag::configure_from(cli, config),
+            size_unit: SizeUnit::configure_from(cli, config),
+            size_separator: SizeSeparator::configure_from(cli, config),
             permission: PermissionFlag::configure_from(cli, config),
             display_indicators: Indicators::configure_from(cli, config),
             icons: Icons::configure_from(cli, config),
             ignore_globs: IgnoreGlobs::configure_from(cli, config)?,
+            gitignore: Gitignore::configure_from(cli, config),
             no_symlink: NoSymlink::configure_from(cli, config),
             recursion: Recursion::configure_from(cli, config),
+            tree_charset: TreeCharset::configure_from(cli, config),
             sorting: Sorting::configure_from(cli, config),
             total_size: TotalSize::configure_from(cli, config),
+            size_summary: SizeSummary::configure_from(cli, config),
             symlink_arrow: SymlinkArrow::configure_from(cli, config),
             hyperlink: HyperlinkOption::configure_from(cli, config),
             header: Header::configure_from(cli, config),
+            output: OutputFormat::configure_from(cli, config),
             literal: Literal::configure_from(cli, config),
+            full_path: FullPath::configure_from(cli, config),
+            quoting_style: QuotingStyle::configure_from(cli, config),
             truncate_owner: TruncateOwner::configure_from(cli, config),
+            numeric_uid_gid: NumericUidGid::configure_from(cli, config),
+            owner_align: OwnerAlign::configure_from(cli, config),
+            utc: Utc::configure_from(cli, config),
+            no_date_color: NoDateColor::configure_from(cli, config),
+            no_symlink_color: NoSymlinkColor::configure_from(cli, config),
+            symlink_color_target: SymlinkColorTarget::configure_from(cli, config),
+            xattr: Xattr::configure_from(cli, config),
         })
     }
 }
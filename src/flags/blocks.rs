@@ -46,6 +46,14 @@ impl Blocks {
         }
     }
 
+    /// Prepends a [Block] of variant [BlockCount](Block::BlockCount), if `self` does not already
+    /// contain a Block of that variant.
+    fn optional_prepend_block_count(&mut self) {
+        if !self.0.contains(&Block::BlockCount) {
+            self.0.insert(0, Block::BlockCount);
+        }
+    }
+
     pub fn displays_size(&self) -> bool {
         self.0.contains(&Block::Size)
     }
@@ -67,6 +75,27 @@ impl Blocks {
         }
     }
 
+    /// Inserts a [Block] of variant [Links](Block::Links) after the
+    /// [Permission](Block::Permission) one like in coreutils, or at the start if there is none,
+    /// if `self` does not already contain a [Block] of that variant.
+    fn optional_insert_links(&mut self) {
+        if self.0.contains(&Block::Links) {
+            return;
+        }
+        match self.0.iter().position(|elem| *elem == Block::Permission) {
+            Some(pos) => self.0.insert(pos + 1, Block::Links),
+            None => self.0.insert(0, Block::Links),
+        }
+    }
+
+    /// Appends a [Block] of variant [Name](Block::Name), if `self` does not already contain a
+    /// [Block] of that variant: whatever the blocks chosen, the entries are always named.
+    fn optional_append_name(&mut self) {
+        if !self.0.contains(&Block::Name) {
+            self.0.push(Block::Name);
+        }
+    }
+
     /// Checks whether `self` already contains a [Block] of variant [GitStatus](Block::GitStatus).
     fn contains_git_status(&self) -> bool {
         self.0.contains(&Block::GitStatus)
@@ -98,17 +127,23 @@ impl Configurable<Self> for Blocks {
     /// - [from_config](Blocks::from_config)
     /// - [long](Blocks::long)
     ///
+    /// The "numeric-uid-gid" argument implies the "long" one, like in `ls`.
+    ///
     /// No matter if the "long" argument was passed, if the "inode" argument is passed and the
     /// `Blocks` does not contain a [Block] of variant [INode](Block::INode) yet, one is prepended
-    /// to the returned value.
+    /// to the returned value, and the "size-blocks" argument prepends a [Block] of variant
+    /// [BlockCount](Block::BlockCount) the same way, after it. With the "long" argument, the
+    /// "links" argument inserts a [Block] of variant [Links](Block::Links) the same way. A [Block]
+    /// of variant [Name](Block::Name) is appended when there is none.
     fn configure_from(cli: &Cli, config: &Config) -> Self {
-        let mut blocks = if cli.long {
+        let long = cli.long || cli.numeric_uid_gid;
+        let mut blocks = if long {
             Self::long()
         } else {
             Default::default()
         };
 
-        if cli.long {
+        if long {
             if let Some(value) = Self::from_config(config) {
                 blocks = value;
             }
@@ -121,14 +156,22 @@ impl Configurable<Self> for Blocks {
         if cli.context {
             blocks.optional_insert_context();
         }
+        if cli.size_blocks {
+            blocks.optional_prepend_block_count();
+        }
         if cli.inode {
             blocks.optional_prepend_inode();
         }
 
-        if !cfg!(feature = "no-git") && cli.git && cli.long {
+        if cli.links && long {
+            blocks.optional_insert_links();
+        }
+
+        if !cfg!(feature = "no-git") && cli.git && long {
             blocks.optional_add_git_status();
         }
 
+        blocks.optional_append_name();
         blocks
     }
 
@@ -193,7 +236,10 @@ pub enum Block {
     Date,
     Name,
     INode,
+    BlockCount,
     Links,
+    FileFlags,
+    Filesystem,
     GitStatus,
 }
 
@@ -201,7 +247,10 @@ impl Block {
     pub fn get_header(&self) -> &'static str {
         match self {
             Block::INode => "INode",
+            Block::BlockCount => "Blocks",
             Block::Links => "Links",
+            Block::FileFlags => "Flags",
+            Block::Filesystem => "Filesystem",
             Block::Permission => "Permissions",
             Block::User => "User",
             Block::Group => "Group",
@@ -215,6 +264,27 @@ impl Block {
     }
 }
 
+impl Block {
+    /// The name of the column of the block in the CSV and TSV outputs.
+    pub fn csv_name(&self) -> &'static str {
+        match self {
+            Block::INode => "inode",
+            Block::BlockCount => "blocks",
+            Block::Links => "links",
+            Block::FileFlags => "flags",
+            Block::Filesystem => "filesystem",
+            Block::Permission => "permissions",
+            Block::User => "user",
+            Block::Group => "group",
+            Block::Context => "context",
+            Block::Size | Block::SizeValue => "size",
+            Block::Date => "date",
+            Block::Name => "name",
+            Block::GitStatus => "git",
+        }
+    }
+}
+
 impl TryFrom<&str> for Block {
     type Error = String;
 
@@ -229,7 +299,10 @@ impl TryFrom<&str> for Block {
             "date" => Ok(Self::Date),
             "name" => Ok(Self::Name),
             "inode" => Ok(Self::INode),
+            "blocks" => Ok(Self::BlockCount),
             "links" => Ok(Self::Links),
+            "flags" => Ok(Self::FileFlags),
+            "filesystem" => Ok(Self::Filesystem),
             "git" => Ok(Self::GitStatus),
             _ => Err(format!("Not a valid block name: {string}")),
         }
@@ -269,10 +342,21 @@ mod test_blocks {
         assert_eq!(result, target);
     }
 
+    #[test]
+    fn test_configure_from_with_numeric_uid_gid() {
+        let argv = ["lsd", "-n"];
+        let target = Blocks::long();
+
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+
+        assert_eq!(result, target);
+    }
+
     #[test]
     fn test_configure_from_with_blocks_and_without_long() {
         let argv = ["lsd", "--blocks", "permission"];
-        let target = Blocks(vec![Block::Permission]);
+        let target = Blocks(vec![Block::Permission, Block::Name]);
 
         let cli = Cli::try_parse_from(argv).unwrap();
         let result = Blocks::configure_from(&cli, &Config::with_none());
@@ -283,7 +367,7 @@ mod test_blocks {
     #[test]
     fn test_configure_from_with_blocks_and_long() {
         let argv = ["lsd", "--long", "--blocks", "permission"];
-        let target = Blocks(vec![Block::Permission]);
+        let target = Blocks(vec![Block::Permission, Block::Name]);
 
         let cli = Cli::try_parse_from(argv).unwrap();
         let result = Blocks::configure_from(&cli, &Config::with_none());
@@ -291,6 +375,20 @@ mod test_blocks {
         assert_eq!(result, target);
     }
 
+    #[test]
+    fn test_configure_from_append_name() {
+        let argv = ["lsd", "--blocks", "name,size"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+        assert_eq!(Blocks(vec![Block::Name, Block::Size]), result);
+
+        let mut c = Config::with_none();
+        c.blocks = Some(vec!["date".into()]);
+        let cli = Cli::try_parse_from(["lsd", "--long"]).unwrap();
+        let result = Blocks::configure_from(&cli, &c);
+        assert_eq!(Blocks(vec![Block::Date, Block::Name]), result);
+    }
+
     #[test]
     fn test_configure_from_with_inode() {
         let argv = ["lsd", "--inode"];
@@ -305,7 +403,7 @@ mod test_blocks {
     #[test]
     fn test_configure_from_prepend_inode_without_long() {
         let argv = ["lsd", "--blocks", "permission", "--inode"];
-        let target = Blocks(vec![Block::INode, Block::Permission]);
+        let target = Blocks(vec![Block::INode, Block::Permission, Block::Name]);
 
         let cli = Cli::try_parse_from(argv).unwrap();
         let result = Blocks::configure_from(&cli, &Config::with_none());
@@ -316,7 +414,7 @@ mod test_blocks {
     #[test]
     fn test_configure_from_prepend_inode_with_long() {
         let argv = ["lsd", "--long", "--blocks", "permission", "--inode"];
-        let target = Blocks(vec![Block::INode, Block::Permission]);
+        let target = Blocks(vec![Block::INode, Block::Permission, Block::Name]);
 
         let cli = Cli::try_parse_from(argv).unwrap();
         let result = Blocks::configure_from(&cli, &Config::with_none());
@@ -327,7 +425,7 @@ mod test_blocks {
     #[test]
     fn test_configure_from_ignore_prepend_inode_without_long() {
         let argv = ["lsd", "--blocks", "permission,inode", "--inode"];
-        let target = Blocks(vec![Block::Permission, Block::INode]);
+        let target = Blocks(vec![Block::Permission, Block::INode, Block::Name]);
 
         let cli = Cli::try_parse_from(argv).unwrap();
         let result = Blocks::configure_from(&cli, &Config::with_none());
@@ -338,7 +436,61 @@ mod test_blocks {
     #[test]
     fn test_configure_from_ignore_prepend_inode_with_long() {
         let argv = ["lsd", "--long", "--blocks", "permission,inode", "--inode"];
-        let target = Blocks(vec![Block::Permission, Block::INode]);
+        let target = Blocks(vec![Block::Permission, Block::INode, Block::Name]);
+
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+
+        assert_eq!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_prepend_block_count() {
+        let argv = ["lsd", "--long", "--size-blocks", "--inode"];
+        let mut target = Blocks::long();
+        target.0.insert(0, Block::BlockCount);
+        target.0.insert(0, Block::INode);
+
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+
+        assert_eq!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_ignore_prepend_block_count() {
+        let argv = ["lsd", "--blocks", "name,blocks", "-s"];
+        let target = Blocks(vec![Block::Name, Block::BlockCount]);
+
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+
+        assert_eq!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_insert_links_with_long() {
+        let argv = ["lsd", "--long", "--links"];
+        let target = Blocks(vec![
+            Block::Permission,
+            Block::Links,
+            Block::User,
+            Block::Group,
+            Block::Size,
+            Block::Date,
+            Block::Name,
+        ]);
+
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let result = Blocks::configure_from(&cli, &Config::with_none());
+
+        assert_eq!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_ignore_links_without_long() {
+        let argv = ["lsd", "--links"];
+        let target = Blocks::default();
 
         let cli = Cli::try_parse_from(argv).unwrap();
         let result = Blocks::configure_from(&cli, &Config::with_none());
@@ -575,6 +727,8 @@ mod test_block {
     #[test]
     fn test_links() {
         assert_eq!(Ok(Block::Links), Block::try_from("links"));
+        assert_eq!(Ok(Block::FileFlags), Block::try_from("flags"));
+        assert_eq!(Ok(Block::Filesystem), Block::try_from("filesystem"));
     }
 
     #[test]
@@ -586,6 +740,8 @@ mod test_block {
     fn test_block_headers() {
         assert_eq!(Block::INode.get_header(), "INode");
         assert_eq!(Block::Links.get_header(), "Links");
+        assert_eq!(Block::FileFlags.get_header(), "Flags");
+        assert_eq!(Block::Filesystem.get_header(), "Filesystem");
         assert_eq!(Block::Permission.get_header(), "Permissions");
         assert_eq!(Block::User.get_header(), "User");
         assert_eq!(Block::Group.get_header(), "Group");
@@ -17,16 +17,28 @@ pub struct Color {
     /// When to use color.
     pub when: ColorOption,
     pub theme: ThemeOption,
+    /// Where the colors of the file names come from.
+    pub source: ColorSource,
+    /// How many colors the terminal can show.
+    pub depth: ColorDepth,
 }
 
 impl Color {
     /// Get a `Color` struct from [Cli], a [Config] or the [Default] values.
     ///
-    /// The [ColorOption] is configured with their respective [Configurable] implementation.
+    /// The [ColorOption], [ColorSource] and [ColorDepth] are configured with their respective
+    /// [Configurable] implementation.
     pub fn configure_from(cli: &Cli, config: &Config) -> Self {
         let when = ColorOption::configure_from(cli, config);
         let theme = ThemeOption::from_config(config);
-        Self { when, theme }
+        let source = ColorSource::configure_from(cli, config);
+        let depth = ColorDepth::configure_from(cli, config);
+        Self {
+            when,
+            theme,
+            source,
+            depth,
+        }
     }
 }
 
@@ -88,6 +100,142 @@ impl<'de> de::Deserialize<'de> for ThemeOption {
     }
 }
 
+/// The flag showing where the colors of the file names come from.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorSource {
+    /// The rules of `LS_COLORS`, by extension then by file type, the theme coloring the file
+    /// types it has no rule for.
+    #[default]
+    LsColors,
+    /// The theme only, `LS_COLORS` being ignored.
+    Theme,
+}
+
+impl ColorSource {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "ls-colors" => Self::LsColors,
+            "theme" => Self::Theme,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'color-source'"),
+        }
+    }
+}
+
+impl Configurable<Self> for ColorSource {
+    /// Get a potential `ColorSource` variant from [Cli].
+    ///
+    /// If the "color-source" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.color_source.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `ColorSource` variant from a [Config].
+    ///
+    /// If the `Config::color::source` has value and is one of "ls-colors" or "theme",
+    /// this returns the corresponding variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.color.as_ref().and_then(|c| c.source)
+    }
+}
+
+/// The flag showing how many colors the terminal can show, the colors of the theme being
+/// downgraded to the nearest ones it has.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    /// The 24-bit colors when `COLORTERM` is `truecolor` or `24bit`, the 256 colors otherwise.
+    #[default]
+    Auto,
+    /// The 24-bit colors, as they are.
+    TrueColor,
+    /// The 256 colors of the xterm palette.
+    Ansi256,
+    /// The 16 basic colors.
+    Ansi16,
+}
+
+impl ColorDepth {
+    fn from_arg_str(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "24" => Some(Self::TrueColor),
+            "256" => Some(Self::Ansi256),
+            "16" => Some(Self::Ansi16),
+            _ => None,
+        }
+    }
+
+    /// Resolve [ColorDepth::Auto] with the `COLORTERM` environment variable.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => match env::var("COLORTERM").as_deref() {
+                Ok("truecolor" | "24bit") => Self::TrueColor,
+                _ => Self::Ansi256,
+            },
+            depth => depth,
+        }
+    }
+}
+
+impl Configurable<Self> for ColorDepth {
+    /// Get a potential `ColorDepth` variant from [Cli].
+    ///
+    /// If the "color-depth" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.color_depth.as_deref().map(|value| {
+            // Invalid value should be handled by `clap` when building an `Cli`
+            Self::from_arg_str(value)
+                .unwrap_or_else(|| unreachable!("Invalid value '{value}' for 'color-depth'"))
+        })
+    }
+
+    /// Get a potential `ColorDepth` variant from a [Config].
+    ///
+    /// If the `Config::color::depth` has value and is one of "auto", 24, 256 or 16,
+    /// this returns the corresponding variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.color.as_ref().and_then(|c| c.depth)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for ColorDepth {
+    fn deserialize<D>(deserializer: D) -> Result<ColorDepth, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ColorDepthVisitor;
+
+        impl<'de> Visitor<'de> for ColorDepthVisitor {
+            type Value = ColorDepth;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("`auto`, 24, 256 or 16")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<ColorDepth, E>
+            where
+                E: de::Error,
+            {
+                ColorDepth::from_arg_str(value)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<ColorDepth, E>
+            where
+                E: de::Error,
+            {
+                ColorDepth::from_arg_str(&value.to_string())
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(ColorDepthVisitor)
+    }
+}
+
 /// The flag showing when to use colors in the output.
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
@@ -217,6 +365,8 @@ mod test_color_option {
         c.color = Some(config_file::Color {
             when: Some(ColorOption::Always),
             theme: None,
+            source: None,
+            depth: None,
         });
 
         assert_eq!(Some(ColorOption::Always), ColorOption::from_config(&c));
@@ -228,6 +378,8 @@ mod test_color_option {
         c.color = Some(config_file::Color {
             when: Some(ColorOption::Auto),
             theme: None,
+            source: None,
+            depth: None,
         });
         assert_eq!(Some(ColorOption::Auto), ColorOption::from_config(&c));
     }
@@ -238,6 +390,8 @@ mod test_color_option {
         c.color = Some(config_file::Color {
             when: Some(ColorOption::Never),
             theme: None,
+            source: None,
+            depth: None,
         });
         assert_eq!(Some(ColorOption::Never), ColorOption::from_config(&c));
     }
@@ -248,6 +402,8 @@ mod test_color_option {
         c.color = Some(config_file::Color {
             when: Some(ColorOption::Always),
             theme: None,
+            source: None,
+            depth: None,
         });
         c.classic = Some(true);
         assert_eq!(Some(ColorOption::Never), ColorOption::from_config(&c));
@@ -273,6 +429,8 @@ mod test_theme_option {
         c.color = Some(config_file::Color {
             when: None,
             theme: Some(ThemeOption::Default),
+            source: None,
+            depth: None,
         });
 
         assert_eq!(ThemeOption::Default, ThemeOption::from_config(&c));
@@ -284,6 +442,8 @@ mod test_theme_option {
         c.color = Some(config_file::Color {
             when: None,
             theme: Some(ThemeOption::NoColor),
+            source: None,
+            depth: None,
         });
         assert_eq!(ThemeOption::NoColor, ThemeOption::from_config(&c));
     }
@@ -294,6 +454,8 @@ mod test_theme_option {
         c.color = Some(config_file::Color {
             when: None,
             theme: Some(ThemeOption::NoLscolors),
+            source: None,
+            depth: None,
         });
         assert_eq!(ThemeOption::NoLscolors, ThemeOption::from_config(&c));
     }
@@ -304,6 +466,8 @@ mod test_theme_option {
         c.color = Some(config_file::Color {
             when: None,
             theme: Some(ThemeOption::CustomLegacy("not-existed".to_string())),
+            source: None,
+            depth: None,
         });
         assert_eq!(
             ThemeOption::CustomLegacy("not-existed".to_string()),
@@ -317,8 +481,104 @@ mod test_theme_option {
         c.color = Some(config_file::Color {
             when: None,
             theme: Some(ThemeOption::Default),
+            source: None,
+            depth: None,
         });
         c.classic = Some(true);
         assert_eq!(ThemeOption::NoColor, ThemeOption::from_config(&c));
     }
 }
+
+#[cfg(test)]
+mod test_color_source {
+    use super::ColorSource;
+
+    use crate::app::Cli;
+    use crate::config_file::{self, Config};
+    use crate::flags::Configurable;
+
+    use clap::Parser;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, ColorSource::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_theme() {
+        let argv = ["lsd", "--color-source", "theme"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(ColorSource::Theme), ColorSource::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_ls_colors() {
+        let argv = ["lsd", "--color-source", "ls-colors"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(ColorSource::LsColors), ColorSource::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_theme() {
+        let mut c = Config::with_none();
+        c.color = Some(config_file::Color {
+            when: None,
+            theme: None,
+            source: Some(ColorSource::Theme),
+            depth: None,
+        });
+        assert_eq!(Some(ColorSource::Theme), ColorSource::from_config(&c));
+    }
+}
+
+#[cfg(test)]
+mod test_color_depth {
+    use super::ColorDepth;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use clap::Parser;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, ColorDepth::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_256() {
+        let argv = ["lsd", "--color-depth", "256"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(ColorDepth::Ansi256), ColorDepth::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--color-depth", "8"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ColorDepth::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_deserialize_number_and_string() {
+        assert_eq!(ColorDepth::Ansi16, serde_yaml::from_str("16").unwrap());
+        assert_eq!(ColorDepth::Ansi16, serde_yaml::from_str("'16'").unwrap());
+        assert_eq!(ColorDepth::Auto, serde_yaml::from_str("auto").unwrap());
+        assert!(serde_yaml::from_str::<ColorDepth>("8").is_err());
+    }
+
+    #[test]
+    fn test_resolve_explicit() {
+        assert_eq!(ColorDepth::Ansi16, ColorDepth::Ansi16.resolve());
+        assert_eq!(ColorDepth::TrueColor, ColorDepth::TrueColor.resolve());
+    }
+}
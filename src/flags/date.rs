@@ -14,18 +14,40 @@ pub enum DateFlag {
     Date,
     Locale,
     Relative,
+    /// The `ls` iso style, the date and time of the recent files and the date of the older ones
     Iso,
+    /// The RFC 3339 date and time, e.g. `2024-01-02T15:04:05Z`
+    Rfc3339,
+    /// The ISO 8601 date, e.g. `2024-01-02`
+    IsoShort,
     Formatted(String),
 }
 
 impl DateFlag {
     /// Get a value from a date format string
     fn from_format_string(value: &str) -> Option<Self> {
-        if app::validate_time_format(value).is_ok() {
-            Some(Self::Formatted(value[1..].to_string()))
-        } else {
-            print_error!("Not a valid date format: {}.", value);
-            None
+        match app::validate_time_format(value) {
+            Ok(_) => Some(Self::Formatted(value[1..].to_string())),
+            Err(err) => {
+                print_error!("Not a valid date format: {}, {}.", value, err);
+                None
+            }
+        }
+    }
+
+    /// Get a value from a GNU `ls` time style: "full-iso", "long-iso", "iso", "locale" or a
+    /// date format string.
+    fn from_time_style(value: &str) -> Option<Self> {
+        match value {
+            "full-iso" => Some(Self::Formatted("%F %T.%f %z".into())),
+            "long-iso" => Some(Self::Formatted("%F %R".into())),
+            "locale" => Some(Self::Locale),
+            "iso" => Some(Self::Iso),
+            _ if value.starts_with('+') => Self::from_format_string(value),
+            _ => {
+                print_error!("Not a valid date value: {}.", value);
+                None
+            }
         }
     }
 
@@ -36,6 +58,8 @@ impl DateFlag {
             "date" => Some(Self::Date),
             "locale" => Some(Self::Locale),
             "relative" => Some(Self::Relative),
+            "iso" => Some(Self::Rfc3339),
+            "iso-short" => Some(Self::IsoShort),
             _ if value.starts_with('+') => Self::from_format_string(value),
             _ => {
                 print_error!("Not a valid date value: {}.", value);
@@ -49,13 +73,16 @@ impl Configurable<Self> for DateFlag {
     /// Get a potential `DateFlag` variant from [Cli].
     ///
     /// If the "classic" argument is passed, then this returns the [DateFlag::Date] variant in a
-    /// [Some]. Otherwise if the argument is passed, this returns the variant corresponding to its
-    /// parameter in a [Some]. Otherwise this returns [None].
+    /// [Some]. Otherwise if the "date" or the "time-style" argument is passed, the last one
+    /// given, this returns the variant corresponding to its parameter in a [Some]. Otherwise
+    /// this returns [None].
     fn from_cli(cli: &Cli) -> Option<Self> {
         if cli.classic {
             Some(Self::Date)
+        } else if let Some(date) = &cli.date {
+            Self::from_str(date)
         } else {
-            cli.date.as_deref().and_then(Self::from_str)
+            cli.time_style.as_deref().and_then(Self::from_time_style)
         }
     }
 
@@ -75,21 +102,9 @@ impl Configurable<Self> for DateFlag {
 
     /// Get a potential `DateFlag` variant from the environment.
     fn from_environment() -> Option<Self> {
-        if let Ok(value) = std::env::var("TIME_STYLE") {
-            match value.as_str() {
-                "full-iso" => Some(Self::Formatted("%F %T.%f %z".into())),
-                "long-iso" => Some(Self::Formatted("%F %R".into())),
-                "locale" => Some(Self::Locale),
-                "iso" => Some(Self::Iso),
-                _ if value.starts_with('+') => Self::from_format_string(&value),
-                _ => {
-                    print_error!("Not a valid date value: {}.", value);
-                    None
-                }
-            }
-        } else {
-            None
-        }
+        std::env::var("TIME_STYLE")
+            .ok()
+            .and_then(|value| Self::from_time_style(&value))
     }
 }
 
@@ -131,6 +146,20 @@ mod test {
         assert_eq!(Some(DateFlag::Relative), DateFlag::from_cli(&cli));
     }
 
+    #[test]
+    fn test_from_cli_iso() {
+        let argv = ["lsd", "--date", "iso"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DateFlag::Rfc3339), DateFlag::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_iso_short() {
+        let argv = ["lsd", "--date", "iso-short"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DateFlag::IsoShort), DateFlag::from_cli(&cli));
+    }
+
     #[test]
     fn test_from_cli_format() {
         let argv = ["lsd", "--date", "+%F"];
@@ -149,6 +178,38 @@ mod test {
         DateFlag::from_cli(&cli);
     }
 
+    #[test]
+    fn test_from_cli_time_style() {
+        for (style, date) in [
+            ("full-iso", DateFlag::Formatted("%F %T.%f %z".into())),
+            ("long-iso", DateFlag::Formatted("%F %R".into())),
+            ("iso", DateFlag::Iso),
+            ("locale", DateFlag::Locale),
+            ("+%F", DateFlag::Formatted("%F".into())),
+        ] {
+            let argv = ["lsd", "--time-style", style];
+            let cli = Cli::try_parse_from(argv).unwrap();
+            assert_eq!(Some(date), DateFlag::from_cli(&cli));
+        }
+    }
+
+    #[test]
+    fn test_from_cli_time_style_invalid() {
+        let argv = ["lsd", "--time-style", "relative"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_cli_date_and_time_style_last_wins() {
+        let argv = ["lsd", "--time-style", "iso", "--date", "relative"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DateFlag::Relative), DateFlag::from_cli(&cli));
+
+        let argv = ["lsd", "--date", "relative", "--time-style", "iso"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DateFlag::Iso), DateFlag::from_cli(&cli));
+    }
+
     #[test]
     fn test_from_cli_classic_mode() {
         let argv = ["lsd", "--date", "date", "--classic"];
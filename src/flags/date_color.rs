@@ -0,0 +1,78 @@
+//! This module defines the [NoDateColor] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to display the dates without the colors of their age.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct NoDateColor(pub bool);
+
+impl Configurable<Self> for NoDateColor {
+    /// Get a potential `NoDateColor` value from [Cli].
+    ///
+    /// If the "no-date-color" argument is passed, this returns a `NoDateColor` with value `true` in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.no_date_color {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `NoDateColor` value from a [Config].
+    ///
+    /// If the `Config::no-date-color` has value,
+    /// this returns it as the value of the `NoDateColor`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.no_date_color.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::NoDateColor;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, NoDateColor::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--no-date-color"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(NoDateColor(true)), NoDateColor::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, NoDateColor::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.no_date_color = Some(true);
+        assert_eq!(Some(NoDateColor(true)), NoDateColor::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.no_date_color = Some(false);
+        assert_eq!(Some(NoDateColor(false)), NoDateColor::from_config(&c));
+    }
+}
@@ -0,0 +1,110 @@
+//! This module defines the [DateSource] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing which time stamp of a file to display and sort by.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum DateSource {
+    /// The last modification time
+    #[default]
+    Modified,
+    /// The last access time
+    Accessed,
+    /// The creation (birth) time, falling back to the modification time when not available
+    Created,
+}
+
+impl DateSource {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "modified" => Self::Modified,
+            "accessed" => Self::Accessed,
+            "created" => Self::Created,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'time'"),
+        }
+    }
+}
+
+impl Configurable<Self> for DateSource {
+    /// Get a potential `DateSource` variant from [Cli].
+    ///
+    /// If the "time" argument is passed, this returns the variant corresponding to its parameter
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.time.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `DateSource` variant from a [Config].
+    ///
+    /// If the `Config::time` has value and is one of "modified", "accessed" or "created",
+    /// this returns the corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.time
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::DateSource;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, DateSource::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_modified() {
+        let argv = ["lsd", "--time", "modified"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DateSource::Modified), DateSource::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_accessed() {
+        let argv = ["lsd", "--time", "accessed"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DateSource::Accessed), DateSource::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_created() {
+        let argv = ["lsd", "--time", "created"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DateSource::Created), DateSource::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--time", "changed"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DateSource::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_accessed() {
+        let mut c = Config::with_none();
+        c.time = Some(DateSource::Accessed);
+        assert_eq!(Some(DateSource::Accessed), DateSource::from_config(&c));
+    }
+}
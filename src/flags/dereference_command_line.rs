@@ -0,0 +1,90 @@
+//! This module defines the [DereferenceCommandLine] flag. To set it up from [Cli], a [Config] and
+//! its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to dereference the symbolic links given on the command line, but not
+/// the ones found in the listed directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DereferenceCommandLine(pub bool);
+
+impl Configurable<Self> for DereferenceCommandLine {
+    /// Get a potential `DereferenceCommandLine` value from [Cli].
+    ///
+    /// If the "dereference-command-line" argument is passed, this returns a
+    /// `DereferenceCommandLine` with value `true` in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.dereference_command_line {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DereferenceCommandLine` value from a [Config].
+    ///
+    /// If the `Config::dereference-command-line` has value, this returns its value as the value
+    /// of the `DereferenceCommandLine`, in a [Some], Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.dereference_command_line.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::DereferenceCommandLine;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, DereferenceCommandLine::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--dereference-command-line"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(DereferenceCommandLine(true)),
+            DereferenceCommandLine::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_cli_short() {
+        let argv = ["lsd", "-H"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(DereferenceCommandLine(true)),
+            DereferenceCommandLine::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(
+            None,
+            DereferenceCommandLine::from_config(&Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.dereference_command_line = Some(true);
+        assert_eq!(
+            Some(DereferenceCommandLine(true)),
+            DereferenceCommandLine::from_config(&c)
+        );
+    }
+}
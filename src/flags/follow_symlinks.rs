@@ -0,0 +1,78 @@
+//! This module defines the [FollowSymlinks] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to recurse into the symbolic links to directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct FollowSymlinks(pub bool);
+
+impl Configurable<Self> for FollowSymlinks {
+    /// Get a potential `FollowSymlinks` value from [Cli].
+    ///
+    /// If the "follow-symlinks" argument is passed, this returns a `FollowSymlinks` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.follow_symlinks {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `FollowSymlinks` value from a [Config].
+    ///
+    /// If the `Config::follow-symlinks` has value,
+    /// this returns it as the value of the `FollowSymlinks`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.follow_symlinks.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::FollowSymlinks;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, FollowSymlinks::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--follow-symlinks"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(FollowSymlinks(true)), FollowSymlinks::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FollowSymlinks::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.follow_symlinks = Some(true);
+        assert_eq!(Some(FollowSymlinks(true)), FollowSymlinks::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.follow_symlinks = Some(false);
+        assert_eq!(Some(FollowSymlinks(false)), FollowSymlinks::from_config(&c));
+    }
+}
@@ -0,0 +1,78 @@
+//! This module defines the [FullPath] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to name the entries by their path rather than by their file name.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct FullPath(pub bool);
+
+impl Configurable<Self> for FullPath {
+    /// Get a potential `FullPath` value from [Cli].
+    ///
+    /// If the "full-path" argument is passed, this returns a `FullPath` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.full_path {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `FullPath` value from a [Config].
+    ///
+    /// If the `Config::full_path` has value,
+    /// this returns it as the value of the `FullPath`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.full_path.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::FullPath;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, FullPath::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--full-path"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(FullPath(true)), FullPath::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FullPath::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.full_path = Some(true);
+        assert_eq!(Some(FullPath(true)), FullPath::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.full_path = Some(false);
+        assert_eq!(Some(FullPath(false)), FullPath::from_config(&c));
+    }
+}
@@ -0,0 +1,120 @@
+//! This module defines the [GridDirection] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+use term_grid::Direction;
+
+/// The flag showing in which order to fill the grid layout.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GridDirection {
+    /// Fill the columns one after the other, from top to bottom.
+    #[default]
+    Down,
+    /// Fill the rows one after the other, from left to right.
+    Across,
+}
+
+impl GridDirection {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "down" => Self::Down,
+            "across" => Self::Across,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'grid-direction'"),
+        }
+    }
+
+    /// The direction of the cells of the grid.
+    pub fn direction(&self) -> Direction {
+        match self {
+            Self::Down => Direction::TopToBottom,
+            Self::Across => Direction::LeftToRight,
+        }
+    }
+}
+
+impl Configurable<Self> for GridDirection {
+    /// Get a potential `GridDirection` variant from [Cli].
+    ///
+    /// If the "across" argument is passed, this returns [GridDirection::Across] in a [Some].
+    /// Otherwise if the "grid-direction" argument is passed, this returns the variant
+    /// corresponding to its parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.across {
+            Some(Self::Across)
+        } else {
+            cli.grid_direction.as_deref().map(Self::from_arg_str)
+        }
+    }
+
+    /// Get a potential `GridDirection` variant from a [Config].
+    ///
+    /// If the `Config::grid_direction` has value and is one of "down" or "across",
+    /// this returns the corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.grid_direction
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::GridDirection;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(GridDirection::Down, GridDirection::default());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, GridDirection::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_across() {
+        let argv = ["lsd", "--grid-direction", "across"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(GridDirection::Across), GridDirection::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_down() {
+        let argv = ["lsd", "--grid-direction", "down"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(GridDirection::Down), GridDirection::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_x() {
+        let argv = ["lsd", "-x"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(GridDirection::Across), GridDirection::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GridDirection::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_across() {
+        let mut c = Config::with_none();
+        c.grid_direction = Some(GridDirection::Across);
+        assert_eq!(Some(GridDirection::Across), GridDirection::from_config(&c));
+    }
+}
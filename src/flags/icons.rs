@@ -7,6 +7,7 @@ use crate::app::Cli;
 use crate::config_file::Config;
 
 use serde::Deserialize;
+use std::env;
 
 /// A collection of flags on how to use icons.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -44,6 +45,8 @@ pub enum IconOption {
     #[default]
     Auto,
     Never,
+    /// Print the icons of the directories only, as with [IconOption::Always].
+    DirectoriesOnly,
 }
 
 impl IconOption {
@@ -52,6 +55,7 @@ impl IconOption {
             "always" => Self::Always,
             "auto" => Self::Auto,
             "never" => Self::Never,
+            "directories-only" => Self::DirectoriesOnly,
             // Invalid value should be handled by `clap` when building an `Cli`
             other => unreachable!("Invalid value '{other}' for 'icon'"),
         }
@@ -75,7 +79,8 @@ impl Configurable<Self> for IconOption {
     /// Get a potential `IconOption` variant from a [Config].
     ///
     /// If the `Configs::classic` has value and is "true" then this returns Some(IconOption::Never).
-    /// Otherwise if the `Config::icon::when` has value and is one of "always", "auto" or "never",
+    /// Otherwise if the `Config::icon::when` has value and is one of "always", "auto", "never" or
+    /// "directories-only",
     /// this returns its corresponding variant in a [Some].
     /// Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
@@ -91,20 +96,34 @@ impl Configurable<Self> for IconOption {
 #[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum IconTheme {
-    Unicode,
     #[default]
+    Auto,
+    Unicode,
     Fancy,
 }
 
 impl IconTheme {
     fn from_arg_str(value: &str) -> Self {
         match value {
+            "auto" => Self::Auto,
             "fancy" => Self::Fancy,
             "unicode" => Self::Unicode,
             // Invalid value should be handled by `clap` when building an `Cli`
             other => unreachable!("Invalid value '{other}' for 'icon-theme'"),
         }
     }
+
+    /// Resolve [IconTheme::Auto] with the `NERD_FONT` environment variable, the fancy icons
+    /// needing a patched font, so this falls back to the unicode ones when it is unset.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => match env::var("NERD_FONT").as_deref() {
+                Ok("" | "0") | Err(_) => Self::Unicode,
+                Ok(_) => Self::Fancy,
+            },
+            theme => theme,
+        }
+    }
 }
 
 impl Configurable<Self> for IconTheme {
@@ -118,7 +137,7 @@ impl Configurable<Self> for IconTheme {
 
     /// Get a potential `IconTheme` variant from a [Config].
     ///
-    /// If the `Config::icons::theme` has value and is one of "fancy" or "unicode",
+    /// If the `Config::icons::theme` has value and is one of "auto", "fancy" or "unicode",
     /// this returns its corresponding variant in a [Some].
     /// Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
@@ -133,10 +152,10 @@ pub struct IconSeparator(pub String);
 impl Configurable<Self> for IconSeparator {
     /// Get a potential `IconSeparator` variant from [Cli].
     ///
-    /// If the argument is passed, this returns the variant corresponding to its parameter in a
-    /// [Some]. Otherwise this returns [None].
-    fn from_cli(_cli: &Cli) -> Option<Self> {
-        None
+    /// If the "icon-separator" argument is passed, this returns an `IconSeparator` with its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.icon_separator.clone().map(Self)
     }
 
     /// Get a potential `IconSeparator` variant from a [Config].
@@ -198,6 +217,16 @@ mod test_icon_option {
         assert_eq!(Some(IconOption::Never), IconOption::from_cli(&cli));
     }
 
+    #[test]
+    fn test_from_cli_directories_only() {
+        let argv = ["lsd", "--icon", "directories-only"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(IconOption::DirectoriesOnly),
+            IconOption::from_cli(&cli)
+        );
+    }
+
     #[test]
     fn test_from_cli_classic_mode() {
         let argv = ["lsd", "--icon", "always", "--classic"];
@@ -280,6 +309,19 @@ mod test_icon_theme {
         assert_eq!(None, IconTheme::from_cli(&cli));
     }
 
+    #[test]
+    fn test_from_cli_auto() {
+        let argv = ["lsd", "--icon-theme", "auto"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(IconTheme::Auto), IconTheme::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_resolve_explicit() {
+        assert_eq!(IconTheme::Fancy, IconTheme::Fancy.resolve());
+        assert_eq!(IconTheme::Unicode, IconTheme::Unicode.resolve());
+    }
+
     #[test]
     fn test_from_cli_fancy() {
         let argv = ["lsd", "--icon-theme", "fancy"];
@@ -331,11 +373,31 @@ mod test_icon_theme {
 
 #[cfg(test)]
 mod test_icon_separator {
+    use clap::Parser;
+
     use super::IconSeparator;
 
+    use crate::app::Cli;
     use crate::config_file::{Config, Icons};
     use crate::flags::Configurable;
 
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, IconSeparator::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_custom() {
+        let argv = ["lsd", "--icon-separator", "  "];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(IconSeparator("  ".to_string())),
+            IconSeparator::from_cli(&cli)
+        );
+    }
+
     #[test]
     fn test_from_config_default() {
         let mut c = Config::with_none();
@@ -6,11 +6,25 @@ use crate::config_file::Config;
 
 use clap::error::ErrorKind;
 use clap::Error;
-use globset::{Glob, GlobSet, GlobSetBuilder};
+use globset::{Candidate, Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use std::ffi::OsStr;
+use std::path::Path;
 
-/// The struct holding a [GlobSet] and methods to build it.
+/// The struct holding the [GlobSet]s of the entries to ignore and methods to build them.
+///
+/// A pattern ending with a `/` only matches the directories, and a pattern holding another `/`,
+/// like `src/**/*.rs`, is matched against the end of the paths rather than the names.
 #[derive(Clone, Debug)]
-pub struct IgnoreGlobs(pub GlobSet);
+pub struct IgnoreGlobs {
+    /// The globs matched against the names of all the entries.
+    names: GlobSet,
+    /// The globs matched against the names of the directories only.
+    dir_names: GlobSet,
+    /// The globs matched against the paths of all the entries.
+    paths: GlobSet,
+    /// The globs matched against the paths of the directories only.
+    dir_paths: GlobSet,
+}
 
 impl IgnoreGlobs {
     /// Returns a value from either [Cli], a [Config] or a [Default] value. The first value
@@ -19,15 +33,21 @@ impl IgnoreGlobs {
     /// - [from_config](IgnoreGlobs::from_config)
     /// - [Default::default]
     ///
+    /// The globs are case sensitive, unless the "ignore-glob-case-insensitive" argument is passed
+    /// or the `Config::ignore-globs-case-insensitive` is set.
+    ///
     /// # Errors
     ///
-    /// If either of the [Glob::new] or [GlobSetBuilder.build] methods return an [Err].
+    /// If either of the [GlobBuilder::build] or [GlobSetBuilder.build] methods return an [Err].
     pub fn configure_from(cli: &Cli, config: &Config) -> Result<Self, Error> {
-        if let Some(value) = Self::from_cli(cli) {
+        let case_insensitive = cli.ignore_glob_case_insensitive
+            || config.ignore_globs_case_insensitive.unwrap_or(false);
+
+        if let Some(value) = Self::from_cli(cli, case_insensitive) {
             return value;
         }
 
-        if let Some(value) = Self::from_config(config) {
+        if let Some(value) = Self::from_config(config, case_insensitive) {
             return value;
         }
 
@@ -39,53 +59,99 @@ impl IgnoreGlobs {
     /// If the "ignore-glob" argument has been passed, this returns a [Result] in a [Some] with
     /// either the built [IgnoreGlobs] or an [Error], if any error was encountered while creating the
     /// [IgnoreGlobs]. If the argument has not been passed, this returns [None].
-    fn from_cli(cli: &Cli) -> Option<Result<Self, Error>> {
+    fn from_cli(cli: &Cli, case_insensitive: bool) -> Option<Result<Self, Error>> {
         if cli.ignore_glob.is_empty() {
             return None;
         }
 
-        let mut glob_set_builder = GlobSetBuilder::new();
-
-        for value in &cli.ignore_glob {
-            match Self::create_glob(value) {
-                Ok(glob) => {
-                    glob_set_builder.add(glob);
-                }
-                Err(err) => return Some(Err(err)),
-            }
-        }
-
-        Some(Self::create_glob_set(&glob_set_builder).map(Self))
+        Some(Self::from_patterns(&cli.ignore_glob, case_insensitive))
     }
 
     /// Get a potential [IgnoreGlobs] from a [Config].
     ///
     /// If the `Config::ignore-globs` contains an Array of Strings,
-    /// each of its values is used to build the [GlobSet]. If the building
+    /// each of its values is used to build the [GlobSet]s. If the building
     /// succeeds, the [IgnoreGlobs] is returned in the [Result] in a [Some]. If any error is
     /// encountered while building, an [Error] is returned in the Result instead. If the Config does
     /// not contain such a key, this returns [None].
-    fn from_config(config: &Config) -> Option<Result<Self, Error>> {
+    fn from_config(config: &Config, case_insensitive: bool) -> Option<Result<Self, Error>> {
         let globs = config.ignore_globs.as_ref()?;
-        let mut glob_set_builder = GlobSetBuilder::new();
-
-        for glob in globs {
-            match Self::create_glob(glob) {
-                Ok(glob) => {
-                    glob_set_builder.add(glob);
-                }
-                Err(err) => return Some(Err(err)),
-            }
+
+        Some(Self::from_patterns(globs, case_insensitive))
+    }
+
+    /// Build the [GlobSet]s of the patterns, each one going to the set it is matched with.
+    fn from_patterns(patterns: &[String], case_insensitive: bool) -> Result<Self, Error> {
+        let mut names = GlobSetBuilder::new();
+        let mut dir_names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        let mut dir_paths = GlobSetBuilder::new();
+
+        for pattern in patterns {
+            let (pattern, dir_only) = match pattern.strip_suffix('/') {
+                Some(pattern) => (pattern, true),
+                None => (pattern.as_str(), false),
+            };
+
+            // the listed directories can be anywhere, so the paths are matched at any depth
+            let is_path = pattern.contains('/');
+            let glob = if !is_path {
+                Self::create_glob(pattern, false, case_insensitive)?
+            } else if pattern.starts_with("**/") {
+                Self::create_glob(pattern, true, case_insensitive)?
+            } else {
+                let pattern = format!("**/{}", pattern.trim_start_matches('/'));
+                Self::create_glob(&pattern, true, case_insensitive)?
+            };
+
+            match (is_path, dir_only) {
+                (false, false) => names.add(glob),
+                (false, true) => dir_names.add(glob),
+                (true, false) => paths.add(glob),
+                (true, true) => dir_paths.add(glob),
+            };
         }
 
-        Some(Self::create_glob_set(&glob_set_builder).map(Self))
+        Ok(Self {
+            names: Self::create_glob_set(&names)?,
+            dir_names: Self::create_glob_set(&dir_names)?,
+            paths: Self::create_glob_set(&paths)?,
+            dir_paths: Self::create_glob_set(&dir_paths)?,
+        })
+    }
+
+    /// Whether the entry at `path`, named `name`, matches one of the globs.
+    ///
+    /// As this is called for every entry, the name and the path are only parsed once, and the
+    /// path is not looked at when no glob is matched against it.
+    pub fn is_match(&self, name: &OsStr, path: &Path, is_dir: bool) -> bool {
+        let name = Candidate::new(name);
+        if self.names.is_match_candidate(&name)
+            || (is_dir && self.dir_names.is_match_candidate(&name))
+        {
+            return true;
+        }
+
+        if self.paths.is_empty() && (!is_dir || self.dir_paths.is_empty()) {
+            return false;
+        }
+        let path = Candidate::new(path);
+        self.paths.is_match_candidate(&path) || (is_dir && self.dir_paths.is_match_candidate(&path))
     }
 
     /// Create a [Glob] from a provided pattern.
     ///
     /// This method is mainly a helper to wrap the handling of potential errors.
-    fn create_glob(pattern: &str) -> Result<Glob, Error> {
-        Glob::new(pattern).map_err(|err| Error::raw(ErrorKind::ValueValidation, err))
+    fn create_glob(
+        pattern: &str,
+        literal_separator: bool,
+        case_insensitive: bool,
+    ) -> Result<Glob, Error> {
+        GlobBuilder::new(pattern)
+            .literal_separator(literal_separator)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|err| Error::raw(ErrorKind::ValueValidation, err))
     }
 
     /// Create a [GlobSet] from a provided [GlobSetBuilder].
@@ -98,10 +164,15 @@ impl IgnoreGlobs {
     }
 }
 
-/// The default value of `IgnoreGlobs` is the empty [GlobSet], returned by [GlobSet::empty()].
+/// The default value of `IgnoreGlobs` holds empty [GlobSet]s, returned by [GlobSet::empty()].
 impl Default for IgnoreGlobs {
     fn default() -> Self {
-        Self(GlobSet::empty())
+        Self {
+            names: GlobSet::empty(),
+            dir_names: GlobSet::empty(),
+            paths: GlobSet::empty(),
+            dir_paths: GlobSet::empty(),
+        }
     }
 }
 
@@ -113,6 +184,8 @@ mod test {
 
     use crate::app::Cli;
     use crate::config_file::Config;
+    use std::ffi::OsStr;
+    use std::path::Path;
 
     // The following tests are implemented using match expressions instead of the assert_eq macro,
     // because clap::Error does not implement PartialEq.
@@ -153,11 +226,75 @@ mod test {
     fn test_from_cli_none() {
         let argv = ["lsd"];
         let cli = Cli::try_parse_from(argv).unwrap();
-        assert!(IgnoreGlobs::from_cli(&cli).is_none());
+        assert!(IgnoreGlobs::from_cli(&cli, false).is_none());
     }
 
     #[test]
     fn test_from_config_none() {
-        assert!(IgnoreGlobs::from_config(&Config::with_none()).is_none());
+        assert!(IgnoreGlobs::from_config(&Config::with_none(), false).is_none());
+    }
+
+    fn globs(argv: &[&str]) -> IgnoreGlobs {
+        let cli = Cli::try_parse_from(argv).unwrap();
+        IgnoreGlobs::configure_from(&cli, &Config::with_none()).unwrap()
+    }
+
+    #[test]
+    fn test_is_match_name() {
+        let globs = globs(&["lsd", "--ignore-glob", "*.tmp", "--ignore-glob", ".git"]);
+        assert!(globs.is_match(OsStr::new("a.tmp"), Path::new("dir/a.tmp"), false));
+        assert!(globs.is_match(OsStr::new(".git"), Path::new(".git"), true));
+        assert!(!globs.is_match(OsStr::new("a.TMP"), Path::new("dir/a.TMP"), false));
+        assert!(!globs.is_match(OsStr::new("a.txt"), Path::new("a.tmp/a.txt"), false));
+    }
+
+    #[test]
+    fn test_is_match_directory_only() {
+        let globs = globs(&["lsd", "--ignore-glob", "build/"]);
+        assert!(globs.is_match(OsStr::new("build"), Path::new("./build"), true));
+        assert!(!globs.is_match(OsStr::new("build"), Path::new("./build"), false));
+    }
+
+    #[test]
+    fn test_is_match_path() {
+        let globs = globs(&[
+            "lsd",
+            "--ignore-glob",
+            "src/**/*.rs",
+            "-I",
+            "/target/debug/",
+        ]);
+        assert!(globs.is_match(OsStr::new("a.rs"), Path::new("./src/a.rs"), false));
+        assert!(globs.is_match(OsStr::new("b.rs"), Path::new("/repo/src/a/b.rs"), false));
+        assert!(!globs.is_match(OsStr::new("a.rs"), Path::new("./a.rs"), false));
+        assert!(globs.is_match(OsStr::new("debug"), Path::new("../target/debug"), true));
+        assert!(!globs.is_match(OsStr::new("debug"), Path::new("../target/debug"), false));
+    }
+
+    #[test]
+    fn test_is_match_case_insensitive() {
+        let globs = globs(&[
+            "lsd",
+            "--ignore-glob-case-insensitive",
+            "--ignore-glob",
+            "*.tmp",
+        ]);
+        assert!(globs.is_match(OsStr::new("a.TMP"), Path::new("a.TMP"), false));
+    }
+
+    #[test]
+    fn test_is_match_case_insensitive_from_config() {
+        let argv = ["lsd", "--ignore-glob", "*.tmp"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let mut c = Config::with_none();
+        c.ignore_globs_case_insensitive = Some(true);
+        let globs = IgnoreGlobs::configure_from(&cli, &c).unwrap();
+        assert!(globs.is_match(OsStr::new("a.TMP"), Path::new("a.TMP"), false));
+    }
+
+    #[test]
+    fn test_is_match_sort_ignore_case_keeps_case() {
+        let globs = globs(&["lsd", "--ignore-case", "--ignore-glob", "*.tmp"]);
+        assert!(!globs.is_match(OsStr::new("a.TMP"), Path::new("a.TMP"), false));
     }
 }
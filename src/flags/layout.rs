@@ -21,14 +21,21 @@ pub enum Layout {
 impl Configurable<Layout> for Layout {
     /// Get a potential `Layout` variant from [Cli].
     ///
-    /// If any of the "tree", "long" or "oneline" arguments is passed, this returns the
-    /// corresponding `Layout` variant in a [Some]. Otherwise if the number of passed "blocks"
-    /// arguments is greater than 1, this also returns the [OneLine](Layout::OneLine) variant.
+    /// If any of the "tree", "long", "numeric-uid-gid" or "oneline" arguments is passed, this
+    /// returns the corresponding `Layout` variant in a [Some]. Otherwise if the number of passed
+    /// "blocks" arguments is greater than 1, this also returns the [OneLine](Layout::OneLine)
+    /// variant.
     /// Finally if neither of them is passed, this returns [None].
     fn from_cli(cli: &Cli) -> Option<Self> {
         if cli.tree {
             Some(Self::Tree)
-        } else if cli.long || cli.oneline || cli.inode || cli.context || cli.blocks.len() > 1
+        } else if cli.long
+            || cli.numeric_uid_gid
+            || cli.oneline
+            || cli.inode
+            || cli.size_blocks
+            || cli.context
+            || cli.blocks.len() > 1
         // TODO: handle this differently
         {
             Some(Self::OneLine)
@@ -85,6 +92,13 @@ mod test {
         assert_eq!(Some(Layout::OneLine), Layout::from_cli(&cli));
     }
 
+    #[test]
+    fn test_from_cli_oneline_through_numeric_uid_gid() {
+        let argv = ["lsd", "--numeric-uid-gid"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Layout::OneLine), Layout::from_cli(&cli));
+    }
+
     #[test]
     fn test_from_cli_oneline_through_blocks() {
         let argv = ["lsd", "--blocks", "permission,name"];
@@ -92,6 +106,13 @@ mod test {
         assert_eq!(Some(Layout::OneLine), Layout::from_cli(&cli));
     }
 
+    #[test]
+    fn test_from_cli_oneline_through_size_blocks() {
+        let argv = ["lsd", "--size-blocks"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Layout::OneLine), Layout::from_cli(&cli));
+    }
+
     #[test]
     fn test_from_config_none() {
         assert_eq!(None, Layout::from_config(&Config::with_none()));
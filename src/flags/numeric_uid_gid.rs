@@ -0,0 +1,78 @@
+//! This module defines the [NumericUidGid] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to display the ids of the users and groups rather than their names.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct NumericUidGid(pub bool);
+
+impl Configurable<Self> for NumericUidGid {
+    /// Get a potential `NumericUidGid` value from [Cli].
+    ///
+    /// If the "numeric-uid-gid" argument is passed, this returns a `NumericUidGid` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.numeric_uid_gid {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `NumericUidGid` value from a [Config].
+    ///
+    /// If the `Config::numeric-uid-gid` has value,
+    /// this returns it as the value of the `NumericUidGid`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.numeric_uid_gid.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::NumericUidGid;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, NumericUidGid::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--numeric-uid-gid"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(NumericUidGid(true)), NumericUidGid::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, NumericUidGid::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.numeric_uid_gid = Some(true);
+        assert_eq!(Some(NumericUidGid(true)), NumericUidGid::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.numeric_uid_gid = Some(false);
+        assert_eq!(Some(NumericUidGid(false)), NumericUidGid::from_config(&c));
+    }
+}
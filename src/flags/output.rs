@@ -0,0 +1,152 @@
+//! This module defines the [OutputFormat] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing how to print the entries.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// The grid, lines or tree of the entries.
+    #[default]
+    Listing,
+    /// A JSON array, the content of the directories being nested in their entry.
+    Json,
+    /// A header row then one row per entry, with comma separated values.
+    Csv,
+    /// A header row then one row per entry, with tab separated values.
+    Tsv,
+    /// The names of the entries, each followed by a NUL byte.
+    Zero,
+}
+
+impl OutputFormat {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "listing" => Self::Listing,
+            "json" => Self::Json,
+            "csv" => Self::Csv,
+            "tsv" => Self::Tsv,
+            "zero" => Self::Zero,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'output'"),
+        }
+    }
+}
+
+impl Configurable<Self> for OutputFormat {
+    /// Get a potential `OutputFormat` variant from [Cli].
+    ///
+    /// If the "json" argument is passed, this returns [OutputFormat::Json] in a [Some], and if
+    /// the "zero" argument is passed, [OutputFormat::Zero]. Otherwise if the "output" argument
+    /// is passed, this returns the variant corresponding to its parameter in a [Some].
+    /// Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.json {
+            Some(Self::Json)
+        } else if cli.zero {
+            Some(Self::Zero)
+        } else {
+            cli.output.as_deref().map(Self::from_arg_str)
+        }
+    }
+
+    /// Get a potential `OutputFormat` variant from a [Config].
+    ///
+    /// If the `Config::output` has value and is one of "listing", "json", "csv", "tsv" or "zero",
+    /// this returns the corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::OutputFormat;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, OutputFormat::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_json() {
+        let argv = ["lsd", "--json"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(OutputFormat::Json), OutputFormat::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_json_conflicts_with_output() {
+        let argv = ["lsd", "--json", "--output", "csv"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_cli_output_csv() {
+        let argv = ["lsd", "--output", "csv"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(OutputFormat::Csv), OutputFormat::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_output_tsv() {
+        let argv = ["lsd", "--output", "tsv"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(OutputFormat::Tsv), OutputFormat::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_zero() {
+        let argv = ["lsd", "-z"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(OutputFormat::Zero), OutputFormat::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_zero_conflicts_with_json_and_output() {
+        let argv = ["lsd", "-z", "--json"];
+        assert!(Cli::try_parse_from(argv).is_err());
+        let argv = ["lsd", "-z", "--output", "csv"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_cli_output_zero() {
+        let argv = ["lsd", "--output", "zero"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(OutputFormat::Zero), OutputFormat::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_output_invalid() {
+        let argv = ["lsd", "--output", "xml"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, OutputFormat::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_csv() {
+        let mut c = Config::with_none();
+        c.output = Some(OutputFormat::Csv);
+        assert_eq!(Some(OutputFormat::Csv), OutputFormat::from_config(&c));
+    }
+}
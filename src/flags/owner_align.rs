@@ -0,0 +1,96 @@
+//! This module defines the [OwnerAlign] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing how to align the user and group names in their columns.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OwnerAlign {
+    #[default]
+    Left,
+    Right,
+}
+
+impl OwnerAlign {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "left" => Self::Left,
+            "right" => Self::Right,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'owner-align'"),
+        }
+    }
+}
+
+impl Configurable<Self> for OwnerAlign {
+    /// Get a potential `OwnerAlign` variant from [Cli].
+    ///
+    /// If the "owner-align" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.owner_align.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `OwnerAlign` variant from a [Config].
+    ///
+    /// If the `Config::owner_align` has value and is one of "left" or "right",
+    /// this returns the corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.owner_align
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::OwnerAlign;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(OwnerAlign::Left, OwnerAlign::default());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, OwnerAlign::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_right() {
+        let argv = ["lsd", "--owner-align", "right"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(OwnerAlign::Right), OwnerAlign::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--owner-align", "center"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, OwnerAlign::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_right() {
+        let mut c = Config::with_none();
+        c.owner_align = Some(OwnerAlign::Right);
+        assert_eq!(Some(OwnerAlign::Right), OwnerAlign::from_config(&c));
+    }
+}
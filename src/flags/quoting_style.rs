@@ -0,0 +1,112 @@
+//! This module defines the [QuotingStyle] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing how to quote the file names, like the coreutils `--quoting-style`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuotingStyle {
+    /// Shell escape on a terminal, literal otherwise.
+    #[default]
+    #[serde(skip)]
+    Auto,
+    /// The names as they are, only the control characters being escaped.
+    Literal,
+    /// Quote the names for a shell when needed, showing the control characters as `?`.
+    Shell,
+    /// Quote the names for a shell when needed, with `$'\n'` for the control characters.
+    ShellEscape,
+    /// Quote the names like a C string, with `\n` for the control characters.
+    C,
+}
+
+impl QuotingStyle {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "literal" => Self::Literal,
+            "shell" => Self::Shell,
+            "shell-escape" => Self::ShellEscape,
+            "c" => Self::C,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'quoting-style'"),
+        }
+    }
+}
+
+impl Configurable<Self> for QuotingStyle {
+    /// Get a potential `QuotingStyle` variant from [Cli].
+    ///
+    /// If the "quoting-style" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.quoting_style.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `QuotingStyle` variant from a [Config].
+    ///
+    /// If the `Config::quoting_style` has value and is one of "literal", "shell", "shell-escape"
+    /// or "c", this returns the corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.quoting_style
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::QuotingStyle;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, QuotingStyle::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_shell_escape() {
+        let argv = ["lsd", "--quoting-style", "shell-escape"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(QuotingStyle::ShellEscape),
+            QuotingStyle::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_cli_c() {
+        let argv = ["lsd", "--quoting-style", "c"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(QuotingStyle::C), QuotingStyle::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_auto_is_invalid() {
+        let argv = ["lsd", "--quoting-style", "auto"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, QuotingStyle::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_shell() {
+        let mut c = Config::with_none();
+        c.quoting_style = Some(QuotingStyle::Shell);
+        assert_eq!(Some(QuotingStyle::Shell), QuotingStyle::from_config(&c));
+    }
+}
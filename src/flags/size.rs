@@ -27,6 +27,8 @@ impl SizeFlag {
             "default" => Self::Default,
             "short" => Self::Short,
             "bytes" => Self::Bytes,
+            // The unit is set by the `SizeUnit` flag, the sizes are displayed as usual
+            "binary" | "si" => Self::Default,
             // Invalid value should be handled by `clap` when building an `Cli`
             other => unreachable!("Invalid value '{other}' for 'size'"),
         }
@@ -0,0 +1,135 @@
+//! This module defines the [SizeSeparator] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing how to group the digits of the sizes displayed in bytes.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SizeSeparator {
+    /// No grouping, e.g. `1048576`
+    #[default]
+    None,
+    /// Thousands separated by commas, e.g. `1,048,576`
+    Comma,
+    /// Thousands separated by underscores, e.g. `1_048_576`
+    Underscore,
+}
+
+impl SizeSeparator {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "none" => Self::None,
+            "comma" => Self::Comma,
+            "underscore" => Self::Underscore,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'size-separator'"),
+        }
+    }
+
+    /// Group the digits of a number of bytes by thousands.
+    pub fn format(&self, bytes: u64) -> String {
+        let separator = match self {
+            Self::None => return bytes.to_string(),
+            Self::Comma => ',',
+            Self::Underscore => '_',
+        };
+
+        let digits = bytes.to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+}
+
+impl Configurable<Self> for SizeSeparator {
+    /// Get a potential `SizeSeparator` variant from [Cli].
+    ///
+    /// If the "size-separator" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.size_separator.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `SizeSeparator` variant from a [Config].
+    ///
+    /// If the `Config::size_separator` has value and is one of "none", "comma" or "underscore",
+    /// this returns the corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.size_separator
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::SizeSeparator;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, SizeSeparator::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_comma() {
+        let argv = ["lsd", "--size-separator", "comma"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SizeSeparator::Comma), SizeSeparator::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_underscore() {
+        let argv = ["lsd", "--size-separator", "underscore"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SizeSeparator::Underscore),
+            SizeSeparator::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--size-separator", "dot"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SizeSeparator::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_comma() {
+        let mut c = Config::with_none();
+        c.size_separator = Some(SizeSeparator::Comma);
+        assert_eq!(Some(SizeSeparator::Comma), SizeSeparator::from_config(&c));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("0", SizeSeparator::Comma.format(0));
+        assert_eq!("999", SizeSeparator::Comma.format(999));
+        assert_eq!("1,000", SizeSeparator::Comma.format(1_000));
+        assert_eq!("1,048,576", SizeSeparator::Comma.format(1_048_576));
+        assert_eq!("123_456", SizeSeparator::Underscore.format(123_456));
+        assert_eq!("1048576", SizeSeparator::None.format(1_048_576));
+    }
+}
@@ -0,0 +1,78 @@
+//! This module defines the [SizeSummary] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to display the total size of the listed entries before the listing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct SizeSummary(pub bool);
+
+impl Configurable<Self> for SizeSummary {
+    /// Get a potential `SizeSummary` value from [Cli].
+    ///
+    /// If the "size-summary" argument is passed, this returns a `SizeSummary` with value `true` in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.size_summary {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `SizeSummary` value from a [Config].
+    ///
+    /// If the `Config::size_summary` has value,
+    /// this returns it as the value of the `SizeSummary`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.size_summary.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::SizeSummary;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, SizeSummary::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--size-summary"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SizeSummary(true)), SizeSummary::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SizeSummary::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.size_summary = Some(true);
+        assert_eq!(Some(SizeSummary(true)), SizeSummary::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.size_summary = Some(false);
+        assert_eq!(Some(SizeSummary(false)), SizeSummary::from_config(&c));
+    }
+}
@@ -0,0 +1,142 @@
+//! This module defines the [SizeUnit] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing which multiple of bytes to display the file sizes in.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SizeUnit {
+    /// Powers of 1024, with the KiB, MiB, GiB and TiB units.
+    #[default]
+    Binary,
+    /// Powers of 1000, with the kB, MB, GB and TB units.
+    Si,
+    /// The raw number of bytes.
+    Bytes,
+}
+
+impl SizeUnit {
+    /// The number of bytes in a kilo of this unit.
+    pub fn base(&self) -> u64 {
+        match self {
+            Self::Binary => 1024,
+            Self::Si => 1000,
+            Self::Bytes => 1,
+        }
+    }
+}
+
+impl Configurable<Self> for SizeUnit {
+    /// Get a potential `SizeUnit` variant from [Cli].
+    ///
+    /// If the "size" argument is passed with "binary", "si" or "bytes", this returns the
+    /// corresponding `SizeUnit` variant in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.classic {
+            return Some(Self::Bytes);
+        }
+        match cli.size.as_deref() {
+            Some("binary") => Some(Self::Binary),
+            Some("si") => Some(Self::Si),
+            Some("bytes") => Some(Self::Bytes),
+            _ => None,
+        }
+    }
+
+    /// Get a potential `SizeUnit` variant from a [Config].
+    ///
+    /// If the `Config::size_unit` has value and is one of "binary", "si" or "bytes",
+    /// this returns the corresponding `SizeUnit` variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if config.classic == Some(true) {
+            Some(Self::Bytes)
+        } else {
+            config.size_unit
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::SizeUnit;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(SizeUnit::Binary, SizeUnit::default());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, SizeUnit::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_short() {
+        let argv = ["lsd", "--size", "short"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, SizeUnit::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_binary() {
+        let argv = ["lsd", "--size", "binary"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SizeUnit::Binary), SizeUnit::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_si() {
+        let argv = ["lsd", "--size", "si"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SizeUnit::Si), SizeUnit::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_bytes() {
+        let argv = ["lsd", "--size", "bytes"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SizeUnit::Bytes), SizeUnit::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_classic() {
+        let argv = ["lsd", "--size", "si", "--classic"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SizeUnit::Bytes), SizeUnit::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SizeUnit::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_si() {
+        let mut c = Config::with_none();
+        c.size_unit = Some(SizeUnit::Si);
+        assert_eq!(Some(SizeUnit::Si), SizeUnit::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let mut c = Config::with_none();
+        c.size_unit = Some(SizeUnit::Si);
+        c.classic = Some(true);
+        assert_eq!(Some(SizeUnit::Bytes), SizeUnit::from_config(&c));
+    }
+}
@@ -3,38 +3,69 @@
 
 use super::Configurable;
 
-use crate::app::Cli;
+use crate::app::{Cli, SORT_KEYS};
 use crate::config_file::Config;
+use crate::print_error;
 
 use serde::Deserialize;
 
 /// A collection of flags on how to sort the output.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Sorting {
     pub column: SortColumn,
     pub order: SortOrder,
+    pub reverse_all: ReverseAll,
+    pub keys: SortKeys,
     pub dir_grouping: DirGrouping,
+    pub dirs_among_files: DirsAmongFiles,
+    pub hidden_grouping: HiddenGrouping,
+    pub symlink_grouping: SymlinkGrouping,
+    pub case_sensitivity: CaseSensitivity,
+    pub collation: Collation,
 }
 
 impl Sorting {
     /// Get a `Sorting` struct from [Cli], a [Config] or the [Default] values.
     ///
-    /// The [SortColumn], [SortOrder] and [DirGrouping] are configured with their respective
-    /// [Configurable] implementation.
+    /// The [SortColumn], [SortOrder], [ReverseAll], [SortKeys], [DirGrouping], [DirsAmongFiles],
+    /// [HiddenGrouping], [SymlinkGrouping], [CaseSensitivity] and [Collation] are configured with
+    /// their respective [Configurable] implementation.
     pub fn configure_from(cli: &Cli, config: &Config) -> Self {
         let column = SortColumn::configure_from(cli, config);
         let order = SortOrder::configure_from(cli, config);
+        let reverse_all = ReverseAll::configure_from(cli, config);
+        let keys = SortKeys::configure_from(cli, config);
         let dir_grouping = DirGrouping::configure_from(cli, config);
+        let dirs_among_files = DirsAmongFiles::configure_from(cli, config);
+        let hidden_grouping = HiddenGrouping::configure_from(cli, config);
+        let symlink_grouping = SymlinkGrouping::configure_from(cli, config);
+        let case_sensitivity = CaseSensitivity::configure_from(cli, config);
+        let collation = Collation::configure_from(cli, config);
         Self {
             column,
             order,
+            reverse_all,
+            keys,
             dir_grouping,
+            dirs_among_files,
+            hidden_grouping,
+            symlink_grouping,
+            case_sensitivity,
+            collation,
+        }
+    }
+    /// Whether the output is sorted by the given column, either alone or as one of the keys.
+    pub fn sorts_by(&self, column: SortColumn) -> bool {
+        if self.keys.0.is_empty() {
+            self.column == column
+        } else {
+            self.keys.0.iter().any(|(key, _)| *key == column)
         }
     }
 }
 
 /// The flag showing which column to use for sorting.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum SortColumn {
     None,
@@ -45,30 +76,38 @@ pub enum SortColumn {
     Size,
     Version,
     GitStatus,
+    Entries,
+    Inode,
+    Target,
+    Permission,
+    Owner,
+    /// A comparator registered with [register_sorter](crate::sort::register_sorter)
+    Custom(String),
 }
 
 impl Configurable<Self> for SortColumn {
     /// Get a potential `SortColumn` variant from [Cli].
     ///
-    /// If either the "timesort" or "sizesort" arguments are passed, this returns the corresponding
-    /// `SortColumn` variant in a [Some]. Otherwise this returns [None].
+    /// If one of the sort arguments, like "timesort", "sizesort" or "sort", is passed, this returns
+    /// the corresponding `SortColumn` variant in a [Some], the last one given overriding the
+    /// others. Otherwise this returns [None].
     fn from_cli(cli: &Cli) -> Option<Self> {
         let sort = cli.sort.as_deref();
 
-        if cli.timesort || sort == Some("time") {
+        if cli.timesort {
             Some(Self::Time)
-        } else if cli.sizesort || sort == Some("size") {
+        } else if cli.sizesort {
             Some(Self::Size)
-        } else if cli.extensionsort || sort == Some("extension") {
+        } else if cli.extensionsort {
             Some(Self::Extension)
-        } else if cli.versionsort || sort == Some("version") {
+        } else if cli.versionsort {
             Some(Self::Version)
-        } else if cli.gitsort || sort == Some("git") {
+        } else if cli.gitsort {
             Some(Self::GitStatus)
         } else if cli.no_sort || sort == Some("none") {
             Some(Self::None)
         } else {
-            None
+            sort.and_then(Self::from_key)
         }
     }
 
@@ -78,7 +117,88 @@ impl Configurable<Self> for SortColumn {
     /// this returns the corresponding variant in a [Some].
     /// Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
-        config.sorting.as_ref().and_then(|s| s.column)
+        config.sorting.as_ref().and_then(|s| s.column.clone())
+    }
+}
+
+impl SortColumn {
+    /// The columns of the [SORT_KEYS], in the same order.
+    const KEYED: [Self; SORT_KEYS.len()] = [
+        Self::Name,
+        Self::Size,
+        Self::Time,
+        Self::Version,
+        Self::Extension,
+        Self::GitStatus,
+        Self::Entries,
+        Self::Inode,
+        Self::Target,
+        Self::Permission,
+        Self::Owner,
+    ];
+
+    /// Get the column of a key of a sort specification, [None] if the key is unknown.
+    fn from_key(key: &str) -> Option<Self> {
+        match key.strip_prefix("custom:") {
+            Some(name) => (!name.is_empty()).then(|| Self::Custom(name.to_owned())),
+            None => SORT_KEYS
+                .iter()
+                .position(|known| *known == key)
+                .map(|i| Self::KEYED[i].clone()),
+        }
+    }
+}
+
+/// The flag showing the columns to sort by one after the other, as given by a specification like
+/// `name,-size,time` where a leading `-` reverses the order of its key.
+///
+/// When it is not empty, it replaces the single [SortColumn].
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SortKeys(pub Vec<(SortColumn, SortOrder)>);
+
+impl SortKeys {
+    /// Parse a sort specification, returning the first unknown key as an error.
+    fn from_spec(spec: &str) -> Result<Self, String> {
+        spec.split(',')
+            .map(|key| {
+                let (order, name) = match key.strip_prefix('-') {
+                    Some(name) => (SortOrder::Reverse, name),
+                    None => (SortOrder::Default, key),
+                };
+                SortColumn::from_key(name)
+                    .map(|column| (column, order))
+                    .ok_or_else(|| format!("unknown sort key '{name}'"))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl Configurable<Self> for SortKeys {
+    /// Get a potential `SortKeys` value from [Cli].
+    ///
+    /// If the "sort-by" argument is passed, this returns its parsed specification in a [Some].
+    /// Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        // Invalid keys should be handled by `clap` when building an `Cli`
+        cli.sort_by
+            .as_deref()
+            .map(|spec| Self::from_spec(spec).expect("invalid sort specification"))
+    }
+
+    /// Get a potential `SortKeys` value from a [Config].
+    ///
+    /// If the `Config::sorting::sort-by` has a valid specification, this returns it parsed in a
+    /// [Some]. Otherwise this returns [None], printing an error for an invalid specification.
+    fn from_config(config: &Config) -> Option<Self> {
+        let spec = config.sorting.as_ref()?.sort_by.as_deref()?;
+        match Self::from_spec(spec) {
+            Ok(keys) => Some(keys),
+            Err(err) => {
+                print_error!("Not a valid sort specification: {}.", err);
+                None
+            }
+        }
     }
 }
 
@@ -118,6 +238,76 @@ impl Configurable<Self> for SortOrder {
     }
 }
 
+/// The flag showing whether reversing the sort order also reverses the order of the entries
+/// which are equal on the sorted column, listed by name. This is the default, like `ls -r`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct ReverseAll(pub bool);
+
+impl Default for ReverseAll {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl Configurable<Self> for ReverseAll {
+    /// Get a potential `ReverseAll` value from [Cli].
+    ///
+    /// If the "stable-ties" argument is passed, this returns a `ReverseAll` with value `false` in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.stable_ties {
+            Some(Self(false))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ReverseAll` value from a [Config].
+    ///
+    /// If the `Config::sorting::reverse-all` has value,
+    /// this returns it as the value of the `ReverseAll`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .sorting
+            .as_ref()
+            .and_then(|s| s.reverse_all)
+            .map(Self)
+    }
+}
+
+/// The flag showing whether the directories are sorted among the files, without being grouped
+/// whatever the [DirGrouping], even in a tree.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DirsAmongFiles(pub bool);
+
+impl Configurable<Self> for DirsAmongFiles {
+    /// Get a potential `DirsAmongFiles` value from [Cli].
+    ///
+    /// If the "sort-dirs-among-files" argument is passed, this returns a `DirsAmongFiles` with
+    /// value `true` in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.sort_dirs_among_files {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DirsAmongFiles` value from a [Config].
+    ///
+    /// If the `Config::sorting::dirs-among-files` has value,
+    /// this returns it as the value of the `DirsAmongFiles`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .sorting
+            .as_ref()
+            .and_then(|s| s.dirs_among_files)
+            .map(Self)
+    }
+}
+
 /// The flag showing where to place directories.
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
@@ -177,6 +367,196 @@ impl Configurable<Self> for DirGrouping {
     }
 }
 
+/// The flag showing where to place hidden files, i.e. the ones whose name starts with a dot.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum HiddenGrouping {
+    #[default]
+    None,
+    First,
+    Last,
+}
+
+impl HiddenGrouping {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "first" => Self::First,
+            "last" => Self::Last,
+            "none" => Self::None,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'group-hidden'"),
+        }
+    }
+}
+
+impl Configurable<Self> for HiddenGrouping {
+    /// Get a potential `HiddenGrouping` variant from [Cli].
+    ///
+    /// If the "classic" argument is passed, then this returns the [HiddenGrouping::None] variant
+    /// in a [Some]. Otherwise if the "group-hidden" argument is passed, this returns the variant
+    /// corresponding to its parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.classic {
+            return Some(Self::None);
+        }
+
+        cli.group_hidden.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `HiddenGrouping` variant from a [Config].
+    ///
+    /// If the `Config::classic` has value and is `true`,
+    /// then this returns the the [HiddenGrouping::None] variant in a [Some].
+    /// Otherwise if `Config::sorting::hidden-grouping` has value and
+    /// is one of "first", "last" or "none", this returns its corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if config.classic == Some(true) {
+            Some(Self::None)
+        } else {
+            config.sorting.as_ref().and_then(|s| s.hidden_grouping)
+        }
+    }
+}
+
+/// The flag showing where to place symlinks, whatever they point to.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SymlinkGrouping {
+    #[default]
+    None,
+    First,
+    Last,
+}
+
+impl SymlinkGrouping {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "first" => Self::First,
+            "last" => Self::Last,
+            "none" => Self::None,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'group-symlinks'"),
+        }
+    }
+}
+
+impl Configurable<Self> for SymlinkGrouping {
+    /// Get a potential `SymlinkGrouping` variant from [Cli].
+    ///
+    /// If the "classic" argument is passed, then this returns the [SymlinkGrouping::None] variant
+    /// in a [Some]. Otherwise if the "group-symlinks" argument is passed, this returns the variant
+    /// corresponding to its parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.classic {
+            return Some(Self::None);
+        }
+
+        cli.group_symlinks.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `SymlinkGrouping` variant from a [Config].
+    ///
+    /// If the `Config::classic` has value and is `true`,
+    /// then this returns the the [SymlinkGrouping::None] variant in a [Some].
+    /// Otherwise if `Config::sorting::symlink-grouping` has value and
+    /// is one of "first", "last" or "none", this returns its corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if config.classic == Some(true) {
+            Some(Self::None)
+        } else {
+            config.sorting.as_ref().and_then(|s| s.symlink_grouping)
+        }
+    }
+}
+
+/// The flag showing whether names are compared case sensitively.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum CaseSensitivity {
+    #[default]
+    Insensitive,
+    Sensitive,
+}
+
+impl Configurable<Self> for CaseSensitivity {
+    /// Get a potential `CaseSensitivity` variant from [Cli].
+    ///
+    /// If the "ignore-case" argument is passed, this returns [CaseSensitivity::Insensitive] in a
+    /// [Some]. If the "case-sensitive" argument is passed, this returns
+    /// [CaseSensitivity::Sensitive] in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.ignore_case {
+            Some(Self::Insensitive)
+        } else if cli.case_sensitive {
+            Some(Self::Sensitive)
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `CaseSensitivity` variant from a [Config].
+    ///
+    /// If the `Config::sorting::case-sensitive` has value,
+    /// this returns a mapped variant in a [Some].
+    /// Otherwise [None] is returned.
+    /// A `true` maps to [CaseSensitivity::Sensitive] while `false` maps to
+    /// [CaseSensitivity::Insensitive].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .sorting
+            .as_ref()
+            .and_then(|s| match s.case_sensitive {
+                Some(true) => Some(Self::Sensitive),
+                Some(false) => Some(Self::Insensitive),
+                None => None,
+            })
+    }
+}
+
+/// The flag showing how names are collated when sorting by name.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Collation {
+    /// Compare the code points of the names, which is fast but puts accented letters after
+    /// every unaccented one.
+    #[default]
+    Bytewise,
+    /// Compare the names with their accents and ligatures folded first, so `café` sorts next to
+    /// `cafe`. This builds a collation key on every comparison and is therefore slower.
+    Unicode,
+}
+
+impl Collation {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "bytewise" => Self::Bytewise,
+            "unicode" => Self::Unicode,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'collation'"),
+        }
+    }
+}
+
+impl Configurable<Self> for Collation {
+    /// Get a potential `Collation` variant from [Cli].
+    ///
+    /// If the "collation" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.collation.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `Collation` variant from a [Config].
+    ///
+    /// If the `Config::sorting::collation` has value and is one of "bytewise" or "unicode",
+    /// this returns the corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.sorting.as_ref().and_then(|s| s.collation)
+    }
+}
+
 #[cfg(test)]
 mod test_sort_column {
     use clap::Parser;
@@ -257,6 +637,40 @@ mod test_sort_column {
         let argv = ["lsd", "--sort", "none"];
         let cli = Cli::try_parse_from(argv).unwrap();
         assert_eq!(Some(SortColumn::None), SortColumn::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "name"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SortColumn::Name), SortColumn::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "entries"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SortColumn::Entries), SortColumn::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "inode"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SortColumn::Inode), SortColumn::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "target"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SortColumn::Target), SortColumn::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "permission"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SortColumn::Permission), SortColumn::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "owner"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SortColumn::Owner), SortColumn::from_cli(&cli));
+
+        let argv = ["lsd", "--sort", "custom:mine"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::Custom("mine".into())),
+            SortColumn::from_cli(&cli)
+        );
+
+        let argv = ["lsd", "--sort", "custom:"];
+        assert!(Cli::try_parse_from(argv).is_err());
     }
 
     #[cfg(not(feature = "no-git"))]
@@ -281,6 +695,21 @@ mod test_sort_column {
         assert_eq!(Some(SortColumn::Time), SortColumn::from_cli(&cli));
     }
 
+    #[test]
+    fn test_multi_sort_shortcuts_use_last() {
+        for (argv, column) in [
+            (["lsd", "-t", "-S"], SortColumn::Size),
+            (["lsd", "-S", "-t"], SortColumn::Time),
+            (["lsd", "-t", "-X"], SortColumn::Extension),
+            (["lsd", "-X", "-v"], SortColumn::Version),
+            (["lsd", "-v", "-U"], SortColumn::None),
+            (["lsd", "-U", "-S"], SortColumn::Size),
+        ] {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            assert_eq!(Some(column), SortColumn::from_cli(&cli), "{argv:?}");
+        }
+    }
+
     #[test]
     fn test_from_config_empty() {
         assert_eq!(None, SortColumn::from_config(&Config::with_none()));
@@ -292,7 +721,14 @@ mod test_sort_column {
         c.sorting = Some(Sorting {
             column: None,
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
 
         assert_eq!(None, SortColumn::from_config(&c));
@@ -304,7 +740,14 @@ mod test_sort_column {
         c.sorting = Some(Sorting {
             column: Some(SortColumn::Extension),
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(SortColumn::Extension), SortColumn::from_config(&c));
     }
@@ -315,7 +758,14 @@ mod test_sort_column {
         c.sorting = Some(Sorting {
             column: Some(SortColumn::Name),
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(SortColumn::Name), SortColumn::from_config(&c));
     }
@@ -326,7 +776,14 @@ mod test_sort_column {
         c.sorting = Some(Sorting {
             column: Some(SortColumn::Time),
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(SortColumn::Time), SortColumn::from_config(&c));
     }
@@ -337,7 +794,14 @@ mod test_sort_column {
         c.sorting = Some(Sorting {
             column: Some(SortColumn::Size),
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(SortColumn::Size), SortColumn::from_config(&c));
     }
@@ -348,7 +812,14 @@ mod test_sort_column {
         c.sorting = Some(Sorting {
             column: Some(SortColumn::Version),
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(SortColumn::Version), SortColumn::from_config(&c));
     }
@@ -359,7 +830,14 @@ mod test_sort_column {
         c.sorting = Some(Sorting {
             column: Some(SortColumn::GitStatus),
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(SortColumn::GitStatus), SortColumn::from_config(&c));
     }
@@ -408,7 +886,14 @@ mod test_sort_order {
         c.sorting = Some(Sorting {
             column: None,
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(None, SortOrder::from_config(&c));
     }
@@ -419,7 +904,14 @@ mod test_sort_order {
         c.sorting = Some(Sorting {
             column: None,
             reverse: Some(true),
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(SortOrder::Reverse), SortOrder::from_config(&c));
     }
@@ -430,12 +922,161 @@ mod test_sort_order {
         c.sorting = Some(Sorting {
             column: None,
             reverse: Some(false),
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(SortOrder::Default), SortOrder::from_config(&c));
     }
 }
 
+#[cfg(test)]
+mod test_reverse_all {
+    use clap::Parser;
+
+    use super::ReverseAll;
+
+    use crate::app::Cli;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            ReverseAll(true),
+            ReverseAll::configure_from(&cli, &Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_cli_stable_ties() {
+        let argv = ["lsd", "--stable-ties"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(ReverseAll(false)), ReverseAll::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: Some(false),
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
+        });
+        assert_eq!(Some(ReverseAll(false)), ReverseAll::from_config(&c));
+    }
+}
+
+#[cfg(test)]
+mod test_sort_keys {
+    use clap::Parser;
+
+    use super::{SortColumn, SortKeys, SortOrder};
+
+    use crate::app::{Cli, SORT_KEYS};
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, SortKeys::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_spec() {
+        let argv = ["lsd", "--sort-by", "name,-size,time"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SortKeys(vec![
+                (SortColumn::Name, SortOrder::Default),
+                (SortColumn::Size, SortOrder::Reverse),
+                (SortColumn::Time, SortOrder::Default),
+            ])),
+            SortKeys::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_every_key_has_its_own_column() {
+        let keys = SortKeys::from_spec(&SORT_KEYS.join(",")).unwrap().0;
+        assert_eq!(SORT_KEYS.len(), keys.len());
+        for (i, (column, _)) in keys.iter().enumerate() {
+            assert!(keys[i + 1..].iter().all(|(other, _)| other != column));
+        }
+    }
+
+    #[test]
+    fn test_from_cli_unknown_key() {
+        let argv = ["lsd", "--sort-by", "name,-colour"];
+        let err = Cli::try_parse_from(argv).unwrap_err();
+        assert!(err.to_string().contains("unknown sort key 'colour'"));
+    }
+
+    #[test]
+    fn test_from_cli_empty_key() {
+        let argv = ["lsd", "--sort-by", "name,,size"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_spec() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: Some("-git,name".into()),
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
+        });
+        assert_eq!(
+            Some(SortKeys(vec![
+                (SortColumn::GitStatus, SortOrder::Reverse),
+                (SortColumn::Name, SortOrder::Default),
+            ])),
+            SortKeys::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: Some("name,colour".into()),
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
+        });
+        assert_eq!(None, SortKeys::from_config(&c));
+    }
+}
+
 #[cfg(test)]
 mod test_dir_grouping {
     use clap::Parser;
@@ -512,7 +1153,14 @@ mod test_dir_grouping {
         c.sorting = Some(Sorting {
             column: None,
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: Some(DirGrouping::First),
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(DirGrouping::First), DirGrouping::from_config(&c));
     }
@@ -523,7 +1171,14 @@ mod test_dir_grouping {
         c.sorting = Some(Sorting {
             column: None,
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: Some(DirGrouping::Last),
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(Some(DirGrouping::Last), DirGrouping::from_config(&c));
     }
@@ -534,7 +1189,14 @@ mod test_dir_grouping {
         c.sorting = Some(Sorting {
             column: None,
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         assert_eq!(None, DirGrouping::from_config(&c));
     }
@@ -545,9 +1207,383 @@ mod test_dir_grouping {
         c.sorting = Some(Sorting {
             column: None,
             reverse: None,
+            reverse_all: None,
+            sort_by: None,
             dir_grouping: Some(DirGrouping::Last),
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
         });
         c.classic = Some(true);
         assert_eq!(Some(DirGrouping::None), DirGrouping::from_config(&c));
     }
 }
+
+#[cfg(test)]
+mod test_dirs_among_files {
+    use clap::Parser;
+
+    use super::DirsAmongFiles;
+
+    use crate::app::Cli;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            DirsAmongFiles(false),
+            DirsAmongFiles::configure_from(&cli, &Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_cli() {
+        let argv = ["lsd", "--sort-dirs-among-files"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DirsAmongFiles(true)), DirsAmongFiles::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: Some(true),
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
+        });
+        assert_eq!(Some(DirsAmongFiles(true)), DirsAmongFiles::from_config(&c));
+    }
+}
+
+#[cfg(test)]
+mod test_hidden_grouping {
+    use clap::Parser;
+
+    use super::HiddenGrouping;
+
+    use crate::app::Cli;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_empty() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, HiddenGrouping::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_first() {
+        let argv = ["lsd", "--group-hidden", "first"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(HiddenGrouping::First), HiddenGrouping::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_last() {
+        let argv = ["lsd", "--group-hidden", "last"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(HiddenGrouping::Last), HiddenGrouping::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_classic_mode() {
+        let argv = ["lsd", "--group-hidden", "first", "--classic"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(HiddenGrouping::None), HiddenGrouping::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        assert_eq!(None, HiddenGrouping::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_last() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: Some(HiddenGrouping::Last),
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
+        });
+        assert_eq!(Some(HiddenGrouping::Last), HiddenGrouping::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: Some(HiddenGrouping::First),
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: None,
+        });
+        c.classic = Some(true);
+        assert_eq!(Some(HiddenGrouping::None), HiddenGrouping::from_config(&c));
+    }
+}
+
+#[cfg(test)]
+mod test_symlink_grouping {
+    use clap::Parser;
+
+    use super::SymlinkGrouping;
+
+    use crate::app::Cli;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_empty() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, SymlinkGrouping::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_first() {
+        let argv = ["lsd", "--group-symlinks", "first"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkGrouping::First),
+            SymlinkGrouping::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_cli_last() {
+        let argv = ["lsd", "--group-symlinks", "last"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SymlinkGrouping::Last), SymlinkGrouping::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_classic_mode() {
+        let argv = ["lsd", "--group-symlinks", "first", "--classic"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SymlinkGrouping::None), SymlinkGrouping::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        assert_eq!(None, SymlinkGrouping::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_last() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: Some(SymlinkGrouping::Last),
+            case_sensitive: None,
+            collation: None,
+        });
+        assert_eq!(
+            Some(SymlinkGrouping::Last),
+            SymlinkGrouping::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: Some(SymlinkGrouping::First),
+            case_sensitive: None,
+            collation: None,
+        });
+        c.classic = Some(true);
+        assert_eq!(
+            Some(SymlinkGrouping::None),
+            SymlinkGrouping::from_config(&c)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_case_sensitivity {
+    use clap::Parser;
+
+    use super::CaseSensitivity;
+
+    use crate::app::Cli;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, CaseSensitivity::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_ignore_case() {
+        let argv = ["lsd", "--ignore-case"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(CaseSensitivity::Insensitive),
+            CaseSensitivity::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_cli_case_sensitive() {
+        let argv = ["lsd", "--case-sensitive"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(CaseSensitivity::Sensitive),
+            CaseSensitivity::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_cli_case_multi() {
+        let argv = ["lsd", "--case-sensitive", "--ignore-case"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(CaseSensitivity::Insensitive),
+            CaseSensitivity::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        assert_eq!(None, CaseSensitivity::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_case_sensitive() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: Some(true),
+            collation: None,
+        });
+        assert_eq!(
+            Some(CaseSensitivity::Sensitive),
+            CaseSensitivity::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_case_insensitive() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: Some(false),
+            collation: None,
+        });
+        assert_eq!(
+            Some(CaseSensitivity::Insensitive),
+            CaseSensitivity::from_config(&c)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_collation {
+    use clap::Parser;
+
+    use super::Collation;
+
+    use crate::app::Cli;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Collation::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_bytewise() {
+        let argv = ["lsd", "--collation", "bytewise"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Collation::Bytewise), Collation::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_unicode() {
+        let argv = ["lsd", "--collation", "unicode"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Collation::Unicode), Collation::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        assert_eq!(None, Collation::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_unicode() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            reverse_all: None,
+            sort_by: None,
+            dir_grouping: None,
+            dirs_among_files: None,
+            hidden_grouping: None,
+            symlink_grouping: None,
+            case_sensitive: None,
+            collation: Some(Collation::Unicode),
+        });
+        assert_eq!(Some(Collation::Unicode), Collation::from_config(&c));
+    }
+}
@@ -2,28 +2,42 @@ use super::Configurable;
 
 use crate::app::Cli;
 use crate::config_file::Config;
+use crate::print_error;
 
 /// The flag showing how to display symbolic arrow.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SymlinkArrow(String);
 
 impl Configurable<Self> for SymlinkArrow {
-    /// `SymlinkArrow` can not be configured by [Cli]
+    /// Get a potential `SymlinkArrow` value from [Cli].
     ///
-    /// Return `None`
-    fn from_cli(_: &Cli) -> Option<Self> {
-        None
+    /// If the "symlink-arrow" argument is passed, this returns its value as the value of the
+    /// `SymlinkArrow` in a [Some]. Otherwise if the "classic" argument is passed, this returns
+    /// the `->` arrow of `ls`. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        match &cli.symlink_arrow {
+            Some(arrow) => Some(SymlinkArrow(arrow.to_string())),
+            None if cli.classic => Some(SymlinkArrow(String::from("->"))),
+            None => None,
+        }
     }
+
     /// Get a potential `SymlinkArrow` value from a [Config].
     ///
-    /// If the `Config::symlink-arrow` has value,
-    /// returns its value as the value of the `SymlinkArrow`, in a [Some].
-    /// Otherwise this returns [None].
+    /// If the `Config::classic` is `true`, this returns the `->` arrow of `ls`. Otherwise if the
+    /// `Config::symlink-arrow` has a value which is not empty, returns it as the value of the
+    /// `SymlinkArrow`, in a [Some]. Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
-        config
-            .symlink_arrow
-            .as_ref()
-            .map(|arrow| SymlinkArrow(arrow.to_string()))
+        if config.classic == Some(true) {
+            return Some(SymlinkArrow(String::from("->")));
+        }
+        match config.symlink_arrow.as_deref() {
+            Some("") => {
+                print_error!("The symlink arrow can not be empty.");
+                None
+            }
+            arrow => arrow.map(|arrow| SymlinkArrow(arrow.to_string())),
+        }
     }
 }
 
@@ -68,6 +82,57 @@ mod test {
         assert_eq!(None, SymlinkArrow::from_cli(&cli));
     }
 
+    #[test]
+    fn test_symlink_arrow_from_args() {
+        let argv = ["lsd", "--symlink-arrow", "->"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkArrow(String::from("->"))),
+            SymlinkArrow::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_symlink_arrow_from_args_empty() {
+        let argv = ["lsd", "--symlink-arrow", ""];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_symlink_arrow_from_config_empty() {
+        let mut c = Config::with_none();
+        c.symlink_arrow = Some("".into());
+        assert_eq!(None, SymlinkArrow::from_config(&c));
+    }
+
+    #[test]
+    fn test_symlink_arrow_from_args_classic() {
+        let argv = ["lsd", "--classic"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkArrow(String::from("->"))),
+            SymlinkArrow::from_cli(&cli)
+        );
+
+        let argv = ["lsd", "--classic", "--symlink-arrow", "~>"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkArrow(String::from("~>"))),
+            SymlinkArrow::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_symlink_arrow_from_config_classic() {
+        let mut c = Config::with_none();
+        c.symlink_arrow = Some("↹".into());
+        c.classic = Some(true);
+        assert_eq!(
+            Some(SymlinkArrow(String::from("->"))),
+            SymlinkArrow::from_config(&c)
+        );
+    }
+
     #[test]
     fn test_symlink_arrow_default() {
         assert_eq!(
@@ -0,0 +1,78 @@
+//! This module defines the [NoSymlinkColor] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to display the symlinks, their arrow and their target without colors.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct NoSymlinkColor(pub bool);
+
+impl Configurable<Self> for NoSymlinkColor {
+    /// Get a potential `NoSymlinkColor` value from [Cli].
+    ///
+    /// If the "no-symlink-color" argument is passed, this returns a `NoSymlinkColor` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.no_symlink_color {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `NoSymlinkColor` value from a [Config].
+    ///
+    /// If the `Config::no-symlink-color` has value,
+    /// this returns it as the value of the `NoSymlinkColor`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.no_symlink_color.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::NoSymlinkColor;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, NoSymlinkColor::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--no-symlink-color"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(NoSymlinkColor(true)), NoSymlinkColor::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, NoSymlinkColor::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.no_symlink_color = Some(true);
+        assert_eq!(Some(NoSymlinkColor(true)), NoSymlinkColor::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.no_symlink_color = Some(false);
+        assert_eq!(Some(NoSymlinkColor(false)), NoSymlinkColor::from_config(&c));
+    }
+}
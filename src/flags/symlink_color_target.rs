@@ -0,0 +1,87 @@
+//! This module defines the [SymlinkColorTarget] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to color the symlink names like their target rather than as links.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct SymlinkColorTarget(pub bool);
+
+impl Configurable<Self> for SymlinkColorTarget {
+    /// Get a potential `SymlinkColorTarget` value from [Cli].
+    ///
+    /// If the "symlink-color-target" argument is passed, this returns a `SymlinkColorTarget` with
+    /// value `true` in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.symlink_color_target {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `SymlinkColorTarget` value from a [Config].
+    ///
+    /// If the `Config::symlink-color-target` has value,
+    /// this returns it as the value of the `SymlinkColorTarget`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.symlink_color_target.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::SymlinkColorTarget;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, SymlinkColorTarget::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--symlink-color-target"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkColorTarget(true)),
+            SymlinkColorTarget::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SymlinkColorTarget::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.symlink_color_target = Some(true);
+        assert_eq!(
+            Some(SymlinkColorTarget(true)),
+            SymlinkColorTarget::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.symlink_color_target = Some(false);
+        assert_eq!(
+            Some(SymlinkColorTarget(false)),
+            SymlinkColorTarget::from_config(&c)
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! This module defines the [TreeCharset] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing which characters to draw the edges of the tree layout with.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TreeCharset {
+    /// The box-drawing characters, like `├──`.
+    #[default]
+    Unicode,
+    /// The ASCII characters, like `|--`, for the terminals without Unicode.
+    Ascii,
+}
+
+impl TreeCharset {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "unicode" => Self::Unicode,
+            "ascii" => Self::Ascii,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'tree-charset'"),
+        }
+    }
+}
+
+impl Configurable<Self> for TreeCharset {
+    /// Get a potential `TreeCharset` variant from [Cli].
+    ///
+    /// If the "tree-charset" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.tree_charset.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `TreeCharset` variant from a [Config].
+    ///
+    /// If the `Config::tree_charset` has value and is one of "unicode" or "ascii",
+    /// this returns the corresponding variant in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.tree_charset
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::TreeCharset;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(TreeCharset::Unicode, TreeCharset::default());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, TreeCharset::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_ascii() {
+        let argv = ["lsd", "--tree-charset", "ascii"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(TreeCharset::Ascii), TreeCharset::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--tree-charset", "utf8"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TreeCharset::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_ascii() {
+        let mut c = Config::with_none();
+        c.tree_charset = Some(TreeCharset::Ascii);
+        assert_eq!(Some(TreeCharset::Ascii), TreeCharset::from_config(&c));
+    }
+}
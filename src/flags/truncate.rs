@@ -0,0 +1,86 @@
+//! This module defines the [Truncate] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+use crate::print_error;
+
+/// The flag setting the width, in columns, the names are truncated to with an ellipsis.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Truncate(pub Option<usize>);
+
+impl Configurable<Self> for Truncate {
+    /// Get a potential `Truncate` value from [Cli].
+    ///
+    /// If the "truncate" argument is passed, this returns a `Truncate` with its parameter in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.truncate.map(|width| Self(Some(width)))
+    }
+
+    /// Get a potential `Truncate` value from a [Config].
+    ///
+    /// If the `Config::truncate` has a value which is not 0, this returns it as the value of the
+    /// `Truncate`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        match config.truncate {
+            Some(0) => {
+                print_error!("Not a valid truncate width: 0.");
+                None
+            }
+            width => width.map(|width| Self(Some(width))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Truncate;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Truncate::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli() {
+        let argv = ["lsd", "--truncate", "20"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Truncate(Some(20))), Truncate::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_zero() {
+        let argv = ["lsd", "--truncate", "0"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Truncate::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut c = Config::with_none();
+        c.truncate = Some(20);
+        assert_eq!(Some(Truncate(Some(20))), Truncate::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_zero() {
+        let mut c = Config::with_none();
+        c.truncate = Some(0);
+        assert_eq!(None, Truncate::from_config(&c));
+    }
+}
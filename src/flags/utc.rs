@@ -0,0 +1,78 @@
+//! This module defines the [Utc] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to display the dates in UTC rather than in the local timezone.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Utc(pub bool);
+
+impl Configurable<Self> for Utc {
+    /// Get a potential `Utc` value from [Cli].
+    ///
+    /// If the "utc" argument is passed, this returns a `Utc` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.utc {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Utc` value from a [Config].
+    ///
+    /// If the `Config::utc` has value,
+    /// this returns it as the value of the `Utc`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.utc.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Utc;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Utc::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--utc"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Utc(true)), Utc::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Utc::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.utc = Some(true);
+        assert_eq!(Some(Utc(true)), Utc::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.utc = Some(false);
+        assert_eq!(Some(Utc(false)), Utc::from_config(&c));
+    }
+}
@@ -0,0 +1,80 @@
+//! This module defines the [Width] flag. To set it up from [Cli], a [Config] and its [Default]
+//! value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag setting the width to lay the grid layout out in, rather than the width of the
+/// terminal. A width of 0 lists one entry per line.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Width(pub Option<usize>);
+
+impl Configurable<Self> for Width {
+    /// Get a potential `Width` value from [Cli].
+    ///
+    /// If the "width" argument is passed, this returns a `Width` with its parameter in a [Some].
+    /// Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.width.map(|width| Self(Some(width)))
+    }
+
+    /// Get a potential `Width` value from a [Config].
+    ///
+    /// If the `Config::width` has value, this returns it as the value of the `Width`, in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.width.map(|width| Self(Some(width)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Width;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Width::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli() {
+        let argv = ["lsd", "--width", "80"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Width(Some(80))), Width::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_zero() {
+        let argv = ["lsd", "--width", "0"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Width(Some(0))), Width::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--width", "-1"];
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Width::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut c = Config::with_none();
+        c.width = Some(40);
+        assert_eq!(Some(Width(Some(40))), Width::from_config(&c));
+    }
+}
@@ -0,0 +1,78 @@
+//! This module defines the [Xattr] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to list the extended attributes of the files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Xattr(pub bool);
+
+impl Configurable<Self> for Xattr {
+    /// Get a potential `Xattr` value from [Cli].
+    ///
+    /// If the "xattr" argument is passed, this returns a `Xattr` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.xattr {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Xattr` value from a [Config].
+    ///
+    /// If the `Config::xattr` has value,
+    /// this returns it as the value of the `Xattr`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.xattr.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Xattr;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Xattr::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--xattr"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Xattr(true)), Xattr::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Xattr::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.xattr = Some(true);
+        assert_eq!(Some(Xattr(true)), Xattr::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.xattr = Some(false);
+        assert_eq!(Some(Xattr(false)), Xattr::from_config(&c));
+    }
+}
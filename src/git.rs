@@ -1,4 +1,8 @@
 use crate::meta::git_file_status::GitFileStatus;
+#[cfg(not(feature = "no-git"))]
+use std::collections::BTreeMap;
+#[cfg(not(feature = "no-git"))]
+use std::ops::Bound;
 use std::path::{Path, PathBuf};
 
 #[allow(dead_code)]
@@ -28,13 +32,26 @@ pub enum GitStatus {
 }
 
 pub struct GitCache {
+    /// The statuses of the entries of the working directory, sorted by path so the entries under
+    /// a directory are next to each other.
+    #[cfg(not(feature = "no-git"))]
+    statuses: BTreeMap<PathBuf, git2::Status>,
+}
+
+/// The [GitCache]s of the repositories of the listed paths, so the status of each repository is
+/// only read once, however many of its paths are listed.
+#[derive(Default)]
+pub struct GitCaches {
+    /// The caches of the repositories met so far, with their canonical working directory.
     #[cfg(not(feature = "no-git"))]
-    statuses: Vec<(PathBuf, git2::Status)>,
+    caches: Vec<(PathBuf, GitCache)>,
+    /// The cache of the paths outside of a repository.
+    empty: GitCache,
 }
 
 #[cfg(feature = "no-git")]
 impl GitCache {
-    pub fn new(_: &Path) -> Self {
+    pub fn empty() -> Self {
         Self {}
     }
 
@@ -43,51 +60,75 @@ impl GitCache {
     }
 }
 
+#[cfg(feature = "no-git")]
+impl GitCaches {
+    pub fn get(&mut self, _: &Path) -> &GitCache {
+        &self.empty
+    }
+}
+
+impl Default for GitCache {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 #[cfg(not(feature = "no-git"))]
-impl GitCache {
-    pub fn new(path: &Path) -> GitCache {
-        let repo = match git2::Repository::discover(path) {
-            Ok(r) => r,
-            Err(_e) => {
-                // Unable to retrieve Git info; it doesn't seem to be a git directory
-                return Self::empty();
-            }
+impl GitCaches {
+    /// Get the cache of the repository holding `path`, reading its status the first time.
+    pub fn get(&mut self, path: &Path) -> &GitCache {
+        let Some((repo, workdir)) = GitCache::discover(path) else {
+            return &self.empty;
         };
 
-        if let Some(workdir) = repo.workdir().and_then(|x| std::fs::canonicalize(x).ok()) {
-            let mut statuses = Vec::new();
-            // Retrieving Git statuses for workdir
-            match repo.statuses(None) {
-                Ok(status_list) => {
-                    for status_entry in status_list.iter() {
-                        // git2-rs provides / separated path even on Windows. We have to rebuild it
-                        let str_path = status_entry.path().unwrap();
-                        let path: PathBuf =
-                            str_path.split('/').collect::<Vec<_>>().iter().collect();
-                        let path = workdir.join(path);
-                        let elem = (path, status_entry.status());
-                        statuses.push(elem);
-                    }
-                }
-                Err(err) => {
-                    crate::print_error!(
-                        "Cannot retrieve Git statuses for directory {:?}: {}",
-                        workdir,
-                        err
-                    );
-                }
+        match self.caches.iter().position(|(dir, _)| dir == &workdir) {
+            Some(idx) => &self.caches[idx].1,
+            None => {
+                let cache = GitCache::from_repo(&repo, &workdir);
+                self.caches.push((workdir, cache));
+                &self.caches[self.caches.len() - 1].1
             }
+        }
+    }
+}
 
-            GitCache { statuses }
-        } else {
-            // No workdir
-            Self::empty()
+#[cfg(not(feature = "no-git"))]
+impl GitCache {
+    /// Open the repository holding `path`, with its canonical working directory.
+    fn discover(path: &Path) -> Option<(git2::Repository, PathBuf)> {
+        // Unable to retrieve Git info when it doesn't seem to be a git directory, or has no workdir
+        let repo = git2::Repository::discover(path).ok()?;
+        let workdir = repo.workdir().and_then(|x| std::fs::canonicalize(x).ok())?;
+        Some((repo, workdir))
+    }
+
+    fn from_repo(repo: &git2::Repository, workdir: &Path) -> GitCache {
+        let mut statuses = BTreeMap::new();
+        // Retrieving Git statuses for workdir
+        match repo.statuses(None) {
+            Ok(status_list) => {
+                for status_entry in status_list.iter() {
+                    // git2-rs provides / separated path even on Windows. We have to rebuild it
+                    let str_path = status_entry.path().unwrap();
+                    let path: PathBuf = str_path.split('/').collect::<Vec<_>>().iter().collect();
+                    statuses.insert(workdir.join(path), status_entry.status());
+                }
+            }
+            Err(err) => {
+                crate::print_error!(
+                    "Cannot retrieve Git statuses for directory {:?}: {}",
+                    workdir,
+                    err
+                );
+            }
         }
+
+        GitCache { statuses }
     }
 
     pub fn empty() -> Self {
         GitCache {
-            statuses: Vec::new(),
+            statuses: BTreeMap::new(),
         }
     }
 
@@ -106,18 +147,17 @@ impl GitCache {
     fn inner_get(&self, filepath: &PathBuf, is_directory: bool) -> GitFileStatus {
         if is_directory {
             self.statuses
-                .iter()
-                .filter(|&x| x.0.starts_with(filepath))
-                .map(|x| GitFileStatus::new(x.1))
+                .range::<Path, _>((Bound::Included(filepath.as_path()), Bound::Unbounded))
+                .take_while(|&(path, _)| path.starts_with(filepath))
+                .map(|(_, status)| GitFileStatus::new(*status))
                 .fold(GitFileStatus::default(), |acc, x| GitFileStatus {
                     index: std::cmp::max(acc.index, x.index),
                     workdir: std::cmp::max(acc.workdir, x.workdir),
                 })
         } else {
             self.statuses
-                .iter()
-                .find(|&x| filepath == &x.0)
-                .map(|e| GitFileStatus::new(e.1))
+                .get(filepath)
+                .map(|status| GitFileStatus::new(*status))
                 .unwrap_or_default()
         }
     }
@@ -178,8 +218,23 @@ mod tests {
         (commit, tree_id)
     }
 
+    #[test]
+    fn test_caches_read_a_repository_once() {
+        let (root, _repo) = repo_init();
+        let dir = root.child("d");
+        dir.create_dir_all().unwrap();
+        let outside = t!(TempDir::new());
+
+        let mut caches = GitCaches::default();
+        let root_cache: *const GitCache = caches.get(root.path());
+        assert!(std::ptr::eq(root_cache, caches.get(dir.path())));
+        assert!(std::ptr::eq(&caches.empty, caches.get(outside.path())));
+        assert_eq!(1, caches.caches.len());
+    }
+
     fn check_cache(root: &Path, statuses: &HashMap<&PathBuf, GitFileStatus>, msg: &str) {
-        let cache = GitCache::new(root);
+        let mut caches = GitCaches::default();
+        let cache = caches.get(root);
         for (&path, status) in statuses.iter() {
             if let Ok(filename) = std::fs::canonicalize(&root.join(path)) {
                 let is_directory = filename.is_dir();
@@ -1,10 +1,15 @@
 use crate::flags::{IconOption, IconTheme as FlagTheme};
 use crate::meta::{FileType, Name};
-use crate::theme::{icon::IconTheme, Theme};
+use crate::theme::icon::{CustomIcons, IconTheme};
+use crate::theme::Theme;
+use unicode_width::UnicodeWidthStr;
 
 pub struct Icons {
     icon_separator: String,
     theme: Option<IconTheme>,
+    /// Whether only the directories have an icon, the other entries being padded to the width
+    /// of the directory icon.
+    directories_only: bool,
 }
 
 // In order to add a new icon, write the unicode value like "\ue5fb" then
@@ -13,21 +18,25 @@ pub struct Icons {
 // s#\\u[0-9a-f]*#\=eval('"'.submatch(0).'"')#
 impl Icons {
     pub fn new(tty: bool, when: IconOption, theme: FlagTheme, icon_separator: String) -> Self {
-        let icon_theme = match (tty, when, theme) {
+        let icon_theme = match (tty, when, theme.resolve()) {
             (_, IconOption::Never, _) | (false, IconOption::Auto, _) => None,
-            (_, _, FlagTheme::Fancy) => {
-                if let Ok(t) = Theme::from_path::<IconTheme>("icons") {
-                    Some(t)
-                } else {
-                    Some(IconTheme::default())
+            (_, _, theme) => {
+                let icon_theme = match theme {
+                    FlagTheme::Fancy => IconTheme::default(),
+                    FlagTheme::Unicode | FlagTheme::Auto => IconTheme::unicode(),
+                };
+                // the icons of the theme file apply over the ones of either theme
+                match Theme::from_path::<CustomIcons>("icons") {
+                    Ok(custom) => Some(icon_theme.with_custom(custom)),
+                    Err(_) => Some(icon_theme),
                 }
             }
-            (_, _, FlagTheme::Unicode) => Some(IconTheme::unicode()),
         };
 
         Self {
             icon_separator,
             theme: icon_theme,
+            directories_only: when == IconOption::DirectoriesOnly,
         }
     }
 
@@ -37,9 +46,13 @@ impl Icons {
             Some(t) => {
                 // Check file types
                 let file_type: FileType = name.file_type();
+                if self.directories_only && !matches!(file_type, FileType::Directory { .. }) {
+                    let width = t.filetype.dir.width();
+                    return format!("{}{}", " ".repeat(width), self.icon_separator);
+                }
                 let icon = match file_type {
-                    FileType::SymLink { is_dir: true } => &t.filetype.symlink_dir,
-                    FileType::SymLink { is_dir: false } => &t.filetype.symlink_file,
+                    FileType::SymLink { is_dir: true, .. } => &t.filetype.symlink_dir,
+                    FileType::SymLink { is_dir: false, .. } => &t.filetype.symlink_file,
                     FileType::Socket => &t.filetype.socket,
                     FileType::Pipe => &t.filetype.pipe,
                     FileType::CharDevice => &t.filetype.device_char,
@@ -75,7 +88,7 @@ impl Icons {
 #[cfg(test)]
 mod test {
     use super::{IconTheme, Icons};
-    use crate::flags::{IconOption, IconTheme as FlagTheme, PermissionFlag};
+    use crate::flags::{DateSource, IconOption, IconTheme as FlagTheme, PermissionFlag};
     use crate::meta::Meta;
     use std::fs::File;
     use tempfile::tempdir;
@@ -85,7 +98,8 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icons = Icons::new(true, IconOption::Never, FlagTheme::Fancy, " ".to_string());
         let icon = icons.get(&meta.name);
@@ -97,7 +111,8 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icons = Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string());
         let icon = icons.get(&meta.name);
@@ -110,7 +125,8 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icons = Icons::new(false, IconOption::Auto, FlagTheme::Fancy, " ".to_string());
         let icon = icons.get(&meta.name);
@@ -122,7 +138,8 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icons = Icons::new(true, IconOption::Auto, FlagTheme::Fancy, " ".to_string());
         let icon = icons.get(&meta.name);
@@ -135,7 +152,8 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icon = Icons::new(true, IconOption::Always, FlagTheme::Fancy, " ".to_string());
         let icon_str = icon.get(&meta.name);
@@ -148,7 +166,8 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icon = Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
         let icon_str = icon.get(&meta.name);
@@ -161,7 +180,8 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icon = Icons::new(
             false,
@@ -178,7 +198,8 @@ mod test {
     fn get_icon_default_directory() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path();
-        let meta = Meta::from_path(file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icon = Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
         let icon_str = icon.get(&meta.name);
@@ -190,7 +211,8 @@ mod test {
     fn get_icon_default_directory_unicode() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path();
-        let meta = Meta::from_path(file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let icon = Icons::new(
             false,
@@ -203,6 +225,40 @@ mod test {
         assert_eq!(icon_str, format!("{}{}", "\u{1f4c2}", icon.icon_separator));
     }
 
+    #[test]
+    fn get_icon_directories_only() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+        let dir = Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        let file =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
+
+        let icon = Icons::new(
+            false,
+            IconOption::DirectoriesOnly,
+            FlagTheme::Fancy,
+            " ".to_string(),
+        );
+        assert_eq!(icon.get(&dir.name), "\u{f115} "); //
+        assert_eq!(icon.get(&file.name), "  ");
+
+        let icon = Icons::new(
+            false,
+            IconOption::DirectoriesOnly,
+            FlagTheme::Unicode,
+            " ".to_string(),
+        );
+        assert_eq!(icon.get(&dir.name), "\u{1f4c2} ");
+        assert_eq!(icon.get(&file.name), "   ");
+    }
+
     #[test]
     fn get_icon_by_name() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -210,7 +266,9 @@ mod test {
         for (file_name, file_icon) in &IconTheme::get_default_icons_by_name() {
             let file_path = tmp_dir.path().join(file_name);
             File::create(&file_path).expect("failed to create file");
-            let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+            let meta =
+                Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .unwrap();
 
             let icon = Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
             let icon_str = icon.get(&meta.name);
@@ -226,7 +284,9 @@ mod test {
         for (ext, file_icon) in &IconTheme::get_default_icons_by_extension() {
             let file_path = tmp_dir.path().join(format!("file.{ext}"));
             File::create(&file_path).expect("failed to create file");
-            let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+            let meta =
+                Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .unwrap();
 
             let icon = Icons::new(false, IconOption::Always, FlagTheme::Fancy, " ".to_string());
             let icon_str = icon.get(&meta.name);
@@ -0,0 +1,107 @@
+//! The listing of `lsd`, to embed it: set the [Flags](flags::Flags) up from a [Cli](app::Cli)
+//! and a [Config](config_file::Config), then [render](core::Core::render) the listing of some
+//! paths with a [Core](core::Core).
+//!
+//! A comparator of [Meta](meta::Meta)s registered with [register_sorter](sort::register_sorter)
+//! sorts the listing with `--sort custom:<name>`.
+
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::match_same_arms,
+    clippy::cast_possible_wrap
+)]
+
+extern crate chrono;
+extern crate clap;
+extern crate dirs;
+extern crate libc;
+extern crate lscolors;
+#[cfg(test)]
+extern crate tempfile;
+extern crate term_grid;
+extern crate terminal_size;
+extern crate unicode_width;
+extern crate url;
+extern crate wild;
+extern crate yaml_rust;
+
+#[cfg(unix)]
+extern crate users;
+
+#[cfg(windows)]
+extern crate windows;
+
+pub mod app;
+mod color;
+pub mod config_file;
+pub mod core;
+mod display;
+pub mod flags;
+mod git;
+mod git_theme;
+mod icon;
+pub mod meta;
+pub mod sort;
+mod theme;
+
+#[derive(PartialEq, Eq, PartialOrd, Copy, Clone)]
+pub enum ExitCode {
+    OK,
+    MinorIssue,
+    MajorIssue,
+}
+impl ExitCode {
+    pub fn set_if_greater(&mut self, code: ExitCode) {
+        let self_i32 = *self as i32;
+        let code_i32 = code as i32;
+        if self_i32 < code_i32 {
+            *self = code;
+        }
+    }
+}
+/// Macro used to avoid panicking when the lsd method is used with a pipe and
+/// stderr close before our program.
+#[macro_export]
+macro_rules! print_error {
+    ($($arg:tt)*) => {
+        {
+            use std::io::Write;
+
+            let stderr = std::io::stderr();
+
+            {
+                let mut handle = stderr.lock();
+                // We can write on stderr, so we simply ignore the error and don't print
+                // and stop with success.
+                let res = handle.write_all(std::format!("lsd: {}\n\n",
+                                                        std::format!($($arg)*)).as_bytes());
+                if res.is_err() {
+                    std::process::exit(0);
+                }
+            }
+        }
+    };
+}
+
+/// Macro used to avoid panicking when the lsd method is used with a pipe and
+/// stdout close before our program.
+#[macro_export]
+macro_rules! print_output {
+    ($($arg:tt)*) => {
+        use std::io::Write;
+
+        let stderr = std::io::stdout();
+
+
+        {
+            let mut handle = stderr.lock();
+            // We can write on stdout, so we simply ignore the error and don't print
+            // and stop with success.
+            let res = handle.write_all(std::format!($($arg)*).as_bytes());
+            if res.is_err() {
+                std::process::exit(0);
+            }
+        }
+    };
+}
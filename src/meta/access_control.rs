@@ -6,6 +6,7 @@ pub struct AccessControl {
     has_acl: bool,
     selinux_context: String,
     smack_context: String,
+    xattrs: Vec<String>,
 }
 
 impl AccessControl {
@@ -14,6 +15,12 @@ impl AccessControl {
         Self::from_data(false, &[], &[])
     }
 
+    /// The names of the extended attributes of the file, other than the ones of the ACL and of
+    /// the security contexts.
+    pub fn xattrs(&self) -> &[String] {
+        &self.xattrs
+    }
+
     #[cfg(unix)]
     pub fn for_path(path: &Path) -> Self {
         let has_acl = !xattr::get(path, Method::Acl.name())
@@ -27,7 +34,18 @@ impl AccessControl {
             .unwrap_or_default()
             .unwrap_or_default();
 
-        Self::from_data(has_acl, &selinux_context, &smack_context)
+        let mut access_control = Self::from_data(has_acl, &selinux_context, &smack_context);
+        if !cfg!(feature = "no-xattr") {
+            access_control.xattrs = xattr::list(path)
+                .map(|names| {
+                    names
+                        .filter(|name| !Method::ALL.iter().any(|method| *name == method.name()))
+                        .map(|name| name.to_string_lossy().to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+        }
+        access_control
     }
 
     fn from_data(has_acl: bool, selinux_context: &[u8], smack_context: &[u8]) -> Self {
@@ -37,11 +55,14 @@ impl AccessControl {
             has_acl,
             selinux_context,
             smack_context,
+            xattrs: Vec::new(),
         }
     }
 
     pub fn render_method(&self, colors: &Colors) -> ColoredString {
-        if self.has_acl {
+        if !self.xattrs.is_empty() {
+            colors.colorize('@', &Elem::Acl)
+        } else if self.has_acl {
             colors.colorize('+', &Elem::Acl)
         } else if !self.selinux_context.is_empty() || !self.smack_context.is_empty() {
             colors.colorize('.', &Elem::Context)
@@ -74,6 +95,8 @@ enum Method {
 
 #[cfg(unix)]
 impl Method {
+    const ALL: [Method; 3] = [Method::Acl, Method::Selinux, Method::Smack];
+
     fn name(&self) -> &'static str {
         match self {
             Method::Acl => "system.posix_acl_access",
@@ -120,6 +143,36 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_xattr_indicator() {
+        let mut access_control = AccessControl::from_data(true, b"a", &[]);
+        access_control.xattrs = vec![String::from("user.comment")];
+
+        assert_eq!(
+            String::from("@").with(Color::DarkCyan),
+            access_control.render_method(&Colors::new(ThemeOption::Default))
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_xattrs_for_path() {
+        use super::Method;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("file.txt");
+        std::fs::File::create(&path).expect("failed to create file");
+
+        // Not all filesystems support the user attributes
+        if xattr::set(&path, "user.comment", b"lsd").is_err() {
+            return;
+        }
+        let _ = xattr::set(&path, Method::Selinux.name(), b"a");
+
+        let access_control = AccessControl::for_path(&path);
+        assert_eq!(&[String::from("user.comment")], access_control.xattrs());
+    }
+
     #[test]
     fn test_selinux_context() {
         let access_control = AccessControl::from_data(false, &[b'a'], &[]);
@@ -0,0 +1,79 @@
+use crate::color::{ColoredString, Colors};
+#[cfg(unix)]
+use std::fs::Metadata;
+
+/// The number of 512-byte blocks allocated to a file, which is less than its size for a sparse
+/// file.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct BlockCount {
+    count: Option<u64>,
+}
+
+#[cfg(unix)]
+impl From<&Metadata> for BlockCount {
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        Self {
+            count: Some(meta.blocks()),
+        }
+    }
+}
+
+impl BlockCount {
+    /// Approximate the blocks of a path from the size it takes on the disk, which is less than
+    /// its size when it is compressed or sparse.
+    #[cfg(windows)]
+    pub fn from_path(path: &std::path::Path) -> Self {
+        Self {
+            count: super::windows_utils::get_compressed_size(path).map(|size| size.div_ceil(512)),
+        }
+    }
+
+    pub fn count(&self) -> Option<u64> {
+        self.count
+    }
+
+    pub fn value_string(&self) -> String {
+        match self.count {
+            Some(count) => count.to_string(),
+            None => String::from('-'),
+        }
+    }
+
+    /// Render the count, right-aligned with `alignment` if given.
+    pub fn render(&self, alignment: Option<usize>) -> ColoredString {
+        let content = self.value_string();
+        let content = match alignment {
+            Some(align) => format!("{content:>align$}"),
+            None => content,
+        };
+        ColoredString::new(Colors::default_style(), content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockCount;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sparse_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sparse");
+        let file = std::fs::File::create(&path).unwrap();
+        file.set_len(1024 * 1024).unwrap();
+
+        let count = BlockCount::from(&path.metadata().unwrap()).count().unwrap();
+        assert!(count < 1024 * 1024 / 512);
+    }
+
+    #[test]
+    fn test_render_right_aligned() {
+        let count = BlockCount { count: Some(8) };
+
+        assert_eq!("8", count.render(None).content());
+        assert_eq!("   8", count.render(Some(4)).content());
+        assert_eq!("-", BlockCount { count: None }.render(None).content());
+    }
+}
@@ -1,11 +1,15 @@
 use super::locale::current_locale;
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{DateFlag, Flags};
-use chrono::{DateTime, Duration, Local};
-use chrono_humanize::HumanTime;
+use crate::flags::{DateFlag, DateSource, Flags};
+use crate::print_error;
+use chrono::{DateTime, Duration, Local, Offset, TimeZone, Utc};
 use std::fs::Metadata;
 use std::panic;
+use std::sync::Once;
 use std::time::SystemTime;
+use unicode_width::UnicodeWidthStr;
+
+static UNAVAILABLE_DATE_WARNING: Once = Once::new();
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Date {
@@ -32,7 +36,59 @@ impl From<&Metadata> for Date {
 }
 
 impl Date {
-    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+    /// Get the date of the given time stamp from a [Metadata].
+    ///
+    /// If the time stamp is not available on this platform or filesystem, the modification
+    /// date is used instead and a warning is printed, only once.
+    pub fn from_metadata(meta: &Metadata, source: DateSource) -> Self {
+        let date = match source {
+            DateSource::Modified => return meta.into(),
+            DateSource::Accessed => meta.accessed(),
+            DateSource::Created => meta.created(),
+        };
+
+        match date {
+            Ok(date) => date.into(),
+            Err(err) => {
+                UNAVAILABLE_DATE_WARNING.call_once(|| {
+                    print_error!(
+                        "{:?} time not available, using modified time: {}.",
+                        source,
+                        err
+                    )
+                });
+                meta.into()
+            }
+        }
+    }
+
+    /// The number of seconds since the unix epoch, [None] for an invalid date.
+    pub fn timestamp(&self) -> Option<i64> {
+        match self {
+            Date::Date(val) => Some(val.timestamp()),
+            Date::Invalid => None,
+        }
+    }
+
+    /// Render the date, right-aligned to `alignment` columns when given.
+    pub fn render(
+        &self,
+        colors: &Colors,
+        flags: &Flags,
+        alignment: Option<usize>,
+    ) -> ColoredString {
+        let content = self.date_string(flags);
+        let content = match alignment {
+            Some(align) => format!(
+                "{}{content}",
+                " ".repeat(align.saturating_sub(content.width()))
+            ),
+            None => content,
+        };
+        if flags.no_date_color.0 {
+            return ColoredString::new(Colors::default_style(), content);
+        }
+
         let now = Local::now();
         #[allow(deprecated)]
         let elem = match self {
@@ -40,28 +96,44 @@ impl Date {
             &Date::Date(modified) if modified > now - Duration::days(1) => Elem::DayOld,
             &Date::Date(_) | Date::Invalid => Elem::Older,
         };
-        colors.colorize(self.date_string(flags), &elem)
+        colors.colorize(content, &elem)
     }
 
-    fn date_string(&self, flags: &Flags) -> String {
+    pub fn date_string(&self, flags: &Flags) -> String {
         let locale = current_locale();
 
         if let Date::Date(val) = self {
+            let offset = if flags.utc.0 {
+                Utc.fix()
+            } else {
+                val.offset().fix()
+            };
+            let val = &val.with_timezone(&offset);
+
             #[allow(deprecated)]
             match &flags.date {
                 DateFlag::Date => val.format("%c").to_string(),
                 DateFlag::Locale => val.format_localized("%c", locale).to_string(),
-                DateFlag::Relative => HumanTime::from(*val - Local::now()).to_string(),
+                DateFlag::Relative => relative_date(val.signed_duration_since(Local::now())),
+                DateFlag::Rfc3339 if flags.utc.0 => val.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                DateFlag::Rfc3339 => val.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                DateFlag::IsoShort => val.format("%F").to_string(),
                 DateFlag::Iso => {
-                    // 365.2425 * 24 * 60 * 60 = 31556952 seconds per year
-                    // 15778476 seconds are 6 months
-                    if *val > Local::now() - Duration::seconds(15_778_476) {
+                    if is_recent(val) {
                         val.format("%m-%d %R").to_string()
                     } else {
                         val.format("%F").to_string()
                     }
                 }
-                DateFlag::Formatted(format) => val.format_localized(format, locale).to_string(),
+                DateFlag::Formatted(format) => {
+                    // Like coreutils, a newline separates the formats of the older and recent dates
+                    let format = match format.split_once('\n') {
+                        Some((_, recent)) if is_recent(val) => recent,
+                        Some((older, _)) => older,
+                        None => format,
+                    };
+                    val.format_localized(format, locale).to_string()
+                }
             }
         } else {
             String::from('-')
@@ -69,11 +141,53 @@ impl Date {
     }
 }
 
+/// Whether a date is less than 6 months old.
+fn is_recent<Tz: TimeZone>(date: &DateTime<Tz>) -> bool {
+    // 365.2425 * 24 * 60 * 60 = 31556952 seconds per year
+    // 15778476 seconds are 6 months
+    #[allow(deprecated)]
+    let six_months = Duration::seconds(15_778_476);
+    *date > Local::now() - six_months
+}
+
+/// Format the delta between a date and now, like "3 hours ago", "yesterday" or "in 5 minutes".
+///
+/// The delta is truncated to the largest unit fitting in it, so that a date stays in the same
+/// bucket until it reaches the next one: anything under a minute is "now", the day before and
+/// after are "yesterday" and "tomorrow", a month is 30 days and a year 365 days.
+fn relative_date(delta: Duration) -> String {
+    let future = delta > Duration::zero();
+    let seconds = delta.num_seconds().unsigned_abs();
+    let days = seconds / (24 * 60 * 60);
+
+    let (count, unit) = match seconds {
+        s if s < 60 => return String::from("now"),
+        s if s < 60 * 60 => (s / 60, "minute"),
+        s if s < 24 * 60 * 60 => (s / (60 * 60), "hour"),
+        _ if days < 2 => return String::from(if future { "tomorrow" } else { "yesterday" }),
+        _ if days < 7 => (days, "day"),
+        _ if days < 30 => (days / 7, "week"),
+        _ if days < 365 => (days / 30, "month"),
+        _ => (days / 365, "year"),
+    };
+
+    let amount = match count {
+        1 if unit == "hour" => format!("an {unit}"),
+        1 => format!("a {unit}"),
+        n => format!("{n} {unit}s"),
+    };
+    if future {
+        format!("in {amount}")
+    } else {
+        format!("{amount} ago")
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Date;
+    use super::{relative_date, Date};
     use crate::color::{Colors, ThemeOption};
-    use crate::flags::{DateFlag, Flags};
+    use crate::flags::{DateFlag, DateSource, Flags, NoDateColor};
     use crate::meta::locale::current_locale;
     use chrono::{DateTime, Duration, Local};
     use crossterm::style::{Color, Stylize};
@@ -116,6 +230,37 @@ mod test {
             .status()
     }
 
+    #[test]
+    fn test_from_metadata() {
+        let mut file_path = env::temp_dir();
+        file_path.push("test_from_metadata.tmp");
+
+        #[allow(deprecated)]
+        let modification_date = Local::now() - chrono::Duration::days(3);
+
+        let success = cross_platform_touch(&file_path, &modification_date)
+            .unwrap()
+            .success();
+        assert!(success, "failed to exec touch");
+
+        let metadata = file_path.metadata().unwrap();
+        assert_eq!(
+            Date::from(&metadata),
+            Date::from_metadata(&metadata, DateSource::Modified)
+        );
+        assert_eq!(
+            Date::from(metadata.accessed().unwrap()),
+            Date::from_metadata(&metadata, DateSource::Accessed)
+        );
+        let created = match metadata.created() {
+            Ok(created) => Date::from(created),
+            Err(_) => Date::from(&metadata),
+        };
+        assert_eq!(created, Date::from_metadata(&metadata, DateSource::Created));
+
+        fs::remove_file(file_path).unwrap();
+    }
+
     #[test]
     fn test_an_hour_old_file_color() {
         let mut file_path = env::temp_dir();
@@ -138,7 +283,7 @@ mod test {
                 .format("%c")
                 .to_string()
                 .with(Color::AnsiValue(40)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
 
         fs::remove_file(file_path).unwrap();
@@ -166,7 +311,7 @@ mod test {
                 .format("%c")
                 .to_string()
                 .with(Color::AnsiValue(42)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
 
         fs::remove_file(file_path).unwrap();
@@ -194,12 +339,34 @@ mod test {
                 .format("%c")
                 .to_string()
                 .with(Color::AnsiValue(36)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
 
         fs::remove_file(file_path).unwrap();
     }
 
+    #[test]
+    fn test_no_date_color() {
+        let colors = Colors::new(ThemeOption::Default);
+        let date = Date::Date(Local::now());
+        let mut flags = Flags::default();
+
+        assert_eq!(
+            Some(Color::AnsiValue(40)),
+            date.render(&colors, &flags, None).style().foreground_color
+        );
+
+        flags.no_date_color = NoDateColor(true);
+        assert_eq!(
+            None,
+            date.render(&colors, &flags, None).style().foreground_color
+        );
+        assert_eq!(
+            date.date_string(&flags),
+            date.render(&colors, &flags, None).to_string()
+        );
+    }
+
     #[test]
     fn test_with_relative_date() {
         let mut file_path = env::temp_dir();
@@ -223,7 +390,7 @@ mod test {
 
         assert_eq!(
             "2 days ago".to_string().with(Color::AnsiValue(36)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
 
         fs::remove_file(file_path).unwrap();
@@ -250,12 +417,39 @@ mod test {
 
         assert_eq!(
             "now".to_string().with(Color::AnsiValue(40)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
 
         fs::remove_file(file_path).unwrap();
     }
 
+    #[test]
+    fn test_relative_date_buckets() {
+        let past = |seconds: i64| relative_date(Duration::seconds(-seconds));
+        let future = |seconds: i64| relative_date(Duration::seconds(seconds));
+        let (minute, hour, day) = (60, 60 * 60, 24 * 60 * 60);
+
+        assert_eq!("now", past(59));
+        assert_eq!("now", future(59));
+        assert_eq!("a minute ago", past(minute));
+        assert_eq!("in 5 minutes", future(5 * minute + 30));
+        assert_eq!("59 minutes ago", past(hour - 1));
+        assert_eq!("an hour ago", past(hour));
+        assert_eq!("2 hours ago", past(2 * hour + 59 * minute));
+        assert_eq!("yesterday", past(day));
+        assert_eq!("yesterday", past(2 * day - 1));
+        assert_eq!("tomorrow", future(day + hour));
+        assert_eq!("2 days ago", past(2 * day));
+        assert_eq!("6 days ago", past(7 * day - 1));
+        assert_eq!("a week ago", past(7 * day));
+        assert_eq!("4 weeks ago", past(29 * day));
+        assert_eq!("a month ago", past(30 * day));
+        assert_eq!("3 months ago", past(100 * day));
+        assert_eq!("12 months ago", past(364 * day));
+        assert_eq!("a year ago", past(365 * day));
+        assert_eq!("in 2 years", future(800 * day));
+    }
+
     #[test]
     fn test_iso_format_now() {
         let mut file_path = env::temp_dir();
@@ -280,7 +474,7 @@ mod test {
                 .format("%m-%d %R")
                 .to_string()
                 .with(Color::AnsiValue(40)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
 
         fs::remove_file(file_path).unwrap();
@@ -311,7 +505,7 @@ mod test {
                 .format("%F")
                 .to_string()
                 .with(Color::AnsiValue(36)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
 
         fs::remove_file(file_path).unwrap();
@@ -341,12 +535,64 @@ mod test {
                 .format_localized("%c", current_locale())
                 .to_string()
                 .with(Color::AnsiValue(40)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
 
         fs::remove_file(file_path).unwrap();
     }
 
+    #[test]
+    fn test_iso_and_iso_short() {
+        use crate::flags::Utc;
+        use chrono::TimeZone;
+
+        let date = Date::Date(
+            chrono::Utc
+                .with_ymd_and_hms(2024, 1, 2, 15, 4, 5)
+                .unwrap()
+                .with_timezone(&Local),
+        );
+        let mut flags = Flags {
+            date: DateFlag::Rfc3339,
+            utc: Utc(true),
+            ..Default::default()
+        };
+        assert_eq!("2024-01-02T15:04:05Z", date.date_string(&flags));
+
+        flags.date = DateFlag::IsoShort;
+        assert_eq!("2024-01-02", date.date_string(&flags));
+
+        // The local time has a fixed width offset
+        flags.date = DateFlag::Rfc3339;
+        flags.utc = Utc(false);
+        let local = date.date_string(&flags);
+        assert_eq!(25, local.len());
+        assert_eq!(
+            chrono::DateTime::parse_from_rfc3339(&local).unwrap(),
+            chrono::Utc.with_ymd_and_hms(2024, 1, 2, 15, 4, 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_formatted_older_and_recent() {
+        let flags = Flags {
+            date: DateFlag::Formatted("older %Y\nrecent %Y".into()),
+            ..Default::default()
+        };
+
+        #[allow(deprecated)]
+        let older = Local::now() - Duration::days(365);
+        let recent = Local::now();
+        assert_eq!(
+            format!("older {}", older.format("%Y")),
+            Date::Date(older).date_string(&flags)
+        );
+        assert_eq!(
+            format!("recent {}", recent.format("%Y")),
+            Date::Date(recent).date_string(&flags)
+        );
+    }
+
     #[test]
     #[cfg(all(not(windows), target_arch = "x86_64"))]
     fn test_bad_date() {
@@ -365,7 +611,7 @@ mod test {
 
         assert_eq!(
             "-".to_string().with(Color::AnsiValue(36)),
-            date.render(&colors, &flags)
+            date.render(&colors, &flags, None)
         );
     }
 }
@@ -0,0 +1,151 @@
+use crate::color::{ColoredString, Colors};
+use crate::meta::FileType;
+use std::path::Path;
+
+/// The flags set on a file with `chattr` on Linux or `chflags` on BSD and macOS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileFlags {
+    names: Vec<&'static str>,
+}
+
+/// The `chattr` flags of the Linux `FS_IOC_GETFLAGS` ioctl, with their `lsattr` letters.
+#[cfg(target_os = "linux")]
+const FLAGS: &[(u64, &str)] = &[
+    (0x0000_0001, "s"), // secure deletion
+    (0x0000_0002, "u"), // undeletable
+    (0x0000_0004, "c"), // compressed
+    (0x0000_0008, "S"), // synchronous updates
+    (0x0000_0010, "i"), // immutable
+    (0x0000_0020, "a"), // append only
+    (0x0000_0040, "d"), // no dump
+    (0x0000_0080, "A"), // no atime updates
+    (0x0000_4000, "j"), // data journaling
+    (0x0000_8000, "t"), // no tail-merging
+    (0x0001_0000, "D"), // synchronous directory updates
+    (0x0002_0000, "T"), // top of directory hierarchies
+    (0x0080_0000, "C"), // no copy on write
+];
+
+/// The `chflags` flags of `st_flags`, with their `ls -o` names.
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+const FLAGS: &[(u64, &str)] = &[
+    (0x0000_0001, "nodump"),
+    (0x0000_0002, "uchg"),
+    (0x0000_0004, "uappnd"),
+    (0x0000_0008, "opaque"),
+    (0x0000_8000, "hidden"),
+    (0x0001_0000, "arch"),
+    (0x0002_0000, "schg"),
+    (0x0004_0000, "sappnd"),
+];
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+#[allow(dead_code)]
+const FLAGS: &[(u64, &str)] = &[];
+
+impl FileFlags {
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")),
+        allow(dead_code)
+    )]
+    fn from_bits(bits: u64) -> Self {
+        Self {
+            names: FLAGS
+                .iter()
+                .filter(|(flag, _)| bits & flag != 0)
+                .map(|(_, name)| *name)
+                .collect(),
+        }
+    }
+
+    /// Query the flags of a regular file or a directory, [None] if they are not available.
+    #[cfg(target_os = "linux")]
+    pub fn for_path(path: &Path, file_type: FileType) -> Option<Self> {
+        use std::os::unix::fs::OpenOptionsExt;
+        use std::os::unix::io::AsRawFd;
+
+        // Like lsattr, do not open the devices, pipes or sockets
+        if !matches!(
+            file_type,
+            FileType::File { .. } | FileType::Directory { .. }
+        ) {
+            return None;
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK | libc::O_NOFOLLOW)
+            .open(path)
+            .ok()?;
+
+        let mut bits: libc::c_int = 0;
+        // SAFETY: FS_IOC_GETFLAGS writes an int to the pointer, on a file open for this call.
+        let res = unsafe { libc::ioctl(file.as_raw_fd(), libc::FS_IOC_GETFLAGS, &mut bits) };
+        (res == 0).then(|| Self::from_bits(bits as u32 as u64))
+    }
+
+    /// Query the flags of a file, [None] if they are not available.
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    pub fn for_path(path: &Path, _: FileType) -> Option<Self> {
+        #[cfg(target_os = "freebsd")]
+        use std::os::freebsd::fs::MetadataExt;
+        #[cfg(target_os = "macos")]
+        use std::os::macos::fs::MetadataExt;
+
+        let metadata = path.symlink_metadata().ok()?;
+        Some(Self::from_bits(u64::from(metadata.st_flags())))
+    }
+
+    /// The flags are not available on this platform.
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+    pub fn for_path(_: &Path, _: FileType) -> Option<Self> {
+        None
+    }
+
+    pub fn render(&self) -> ColoredString {
+        let content = if self.names.is_empty() {
+            String::from('-')
+        } else {
+            self.names.join(",")
+        };
+        ColoredString::new(Colors::default_style(), content)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FileFlags, FLAGS};
+
+    #[test]
+    fn test_render_no_flags() {
+        assert_eq!("-", FileFlags::from_bits(0).render().content());
+    }
+
+    #[test]
+    fn test_render_flags() {
+        let bits = FLAGS.iter().take(2).fold(0, |bits, (flag, _)| bits | flag);
+        let expected = FLAGS
+            .iter()
+            .take(2)
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        assert_eq!(&expected, FileFlags::from_bits(bits).render().content());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_render_immutable_and_append() {
+        assert_eq!("i,a", FileFlags::from_bits(0x10 | 0x20).render().content());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_for_path_pipe() {
+        use crate::meta::FileType;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        assert_eq!(None, FileFlags::for_path(tmp_dir.path(), FileType::Pipe));
+    }
+}
@@ -0,0 +1,214 @@
+use crate::color::{ColoredString, Colors};
+#[cfg(target_os = "linux")]
+use once_cell::sync::Lazy;
+use std::path::Path;
+
+/// The filesystem an entry lives on: where it is mounted and its type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filesystem {
+    mount_point: String,
+    fs_type: String,
+}
+
+/// A line of `/proc/self/mountinfo`.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+struct Mount {
+    /// The major and minor numbers of the device of the filesystem.
+    dev: (u64, u64),
+    filesystem: Filesystem,
+}
+
+/// The mount table, read once per run.
+#[cfg(target_os = "linux")]
+static MOUNTS: Lazy<Vec<Mount>> = Lazy::new(|| {
+    std::fs::read_to_string("/proc/self/mountinfo")
+        .map(|mountinfo| parse_mountinfo(&mountinfo))
+        .unwrap_or_default()
+});
+
+/// Parse the mount table, skipping the malformed lines.
+///
+/// Each line reads `id parent major:minor root mount-point options [optional...] - type source
+/// super-options`.
+#[cfg(target_os = "linux")]
+fn parse_mountinfo(mountinfo: &str) -> Vec<Mount> {
+    mountinfo
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(' ');
+            let (major, minor) = fields.nth(2)?.split_once(':')?;
+            let mount_point = fields.nth(1)?;
+            let fs_type = fields.skip_while(|field| *field != "-").nth(1)?;
+
+            Some(Mount {
+                dev: (major.parse().ok()?, minor.parse().ok()?),
+                filesystem: Filesystem {
+                    mount_point: unescape(mount_point),
+                    fs_type: unescape(fs_type),
+                },
+            })
+        })
+        .collect()
+}
+
+/// Decode the `\ooo` octal escapes of the spaces, tabs, newlines and backslashes in the mount
+/// table.
+#[cfg(target_os = "linux")]
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let code = bytes
+            .get(i + 1..i + 4)
+            .filter(|_| bytes[i] == b'\\')
+            .and_then(|code| std::str::from_utf8(code).ok())
+            .and_then(|code| u8::from_str_radix(code, 8).ok());
+        match code {
+            Some(code) => {
+                output.push(code);
+                i += 4;
+            }
+            None => {
+                output.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// The major and minor numbers of a device, split like `major(3)` and `minor(3)` do.
+#[cfg(target_os = "linux")]
+fn split_dev(dev: u64) -> (u64, u64) {
+    let major = ((dev >> 32) & 0xffff_f000) | ((dev >> 8) & 0x0000_0fff);
+    let minor = ((dev >> 12) & 0xffff_ff00) | (dev & 0x0000_00ff);
+    (major, minor)
+}
+
+impl Filesystem {
+    /// Find the filesystem of the entry in the mount table, [None] if it is not there.
+    ///
+    /// When a filesystem is mounted more than once, the mount point the entry is under is
+    /// preferred.
+    #[cfg(target_os = "linux")]
+    pub fn for_path(path: &Path, follow_symlink: bool) -> Option<Self> {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = if follow_symlink {
+            path.metadata()
+        } else {
+            path.symlink_metadata()
+        }
+        .ok()?;
+        let dev = split_dev(metadata.dev());
+
+        let mut mounts = MOUNTS.iter().filter(|mount| mount.dev == dev).peekable();
+        let first = mounts.peek()?.filesystem.clone();
+        // a symlink lives in its directory, wherever it points to
+        let canonical = if metadata.is_symlink() {
+            path.parent().and_then(|parent| parent.canonicalize().ok())
+        } else {
+            path.canonicalize().ok()
+        };
+        let under = canonical.and_then(|canonical| {
+            mounts
+                .filter(|mount| canonical.starts_with(&mount.filesystem.mount_point))
+                .max_by_key(|mount| mount.filesystem.mount_point.len())
+        });
+        Some(under.map_or(first, |mount| mount.filesystem.clone()))
+    }
+
+    /// Ask the filesystem of the entry with `statfs`, [None] if it fails.
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    pub fn for_path(path: &Path, _: bool) -> Option<Self> {
+        use std::ffi::{CStr, CString};
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        // SAFETY: statfs fills the zeroed struct, which is only read when the call succeeds.
+        let stat = unsafe {
+            let mut stat: libc::statfs = std::mem::zeroed();
+            if libc::statfs(path.as_ptr(), &mut stat) != 0 {
+                return None;
+            }
+            stat
+        };
+        // SAFETY: the names are nul-terminated strings within their arrays.
+        let (mount_point, fs_type) = unsafe {
+            (
+                CStr::from_ptr(stat.f_mntonname.as_ptr()),
+                CStr::from_ptr(stat.f_fstypename.as_ptr()),
+            )
+        };
+
+        Some(Self {
+            mount_point: mount_point.to_string_lossy().into_owned(),
+            fs_type: fs_type.to_string_lossy().into_owned(),
+        })
+    }
+
+    /// The filesystems are not available on this platform.
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+    pub fn for_path(_: &Path, _: bool) -> Option<Self> {
+        None
+    }
+
+    pub fn render(&self) -> ColoredString {
+        ColoredString::new(
+            Colors::default_style(),
+            format!("{} ({})", self.mount_point, self.fs_type),
+        )
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::{parse_mountinfo, split_dev, unescape, Filesystem};
+
+    const MOUNTINFO: &str = "\
+22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+23 22 0:21 / /proc rw,nosuid - proc proc rw
+24 22 8:2 / /mnt/my\\040disk rw master:2 shared:3 - xfs /dev/sda2 rw
+malformed line
+";
+
+    #[test]
+    fn test_parse_mountinfo() {
+        let mounts = parse_mountinfo(MOUNTINFO);
+
+        assert_eq!(3, mounts.len());
+        assert_eq!((8, 1), mounts[0].dev);
+        assert_eq!("/ (ext4)", mounts[0].filesystem.render().content());
+        assert_eq!((0, 21), mounts[1].dev);
+        assert_eq!("/proc (proc)", mounts[1].filesystem.render().content());
+        assert_eq!(
+            "/mnt/my disk (xfs)",
+            mounts[2].filesystem.render().content()
+        );
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!("a b\\c", unescape("a\\040b\\134c"));
+        assert_eq!("a\\9", unescape("a\\9"));
+        assert_eq!("end\\", unescape("end\\"));
+    }
+
+    #[test]
+    fn test_split_dev() {
+        assert_eq!((8, 1), split_dev(0x801));
+        assert_eq!((259, 0x12345), split_dev(0x1231_0345));
+    }
+
+    #[test]
+    fn test_for_path() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        assert!(Filesystem::for_path(tmp_dir.path(), false).is_some());
+        assert_eq!(
+            None,
+            Filesystem::for_path(&tmp_dir.path().join("none"), false)
+        );
+    }
+}
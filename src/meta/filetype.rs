@@ -8,7 +8,7 @@ pub enum FileType {
     CharDevice,
     Directory { uid: bool },
     File { uid: bool, exec: bool },
-    SymLink { is_dir: bool },
+    SymLink { is_dir: bool, broken: bool },
     Pipe,
     Socket,
     Special,
@@ -43,6 +43,7 @@ impl FileType {
             FileType::SymLink {
                 // if broken, defaults to false
                 is_dir: symlink_meta.map(|m| m.is_dir()).unwrap_or_default(),
+                broken: symlink_meta.is_none(),
             }
         } else if file_type.is_char_device() {
             FileType::CharDevice
@@ -76,6 +77,7 @@ impl FileType {
             FileType::SymLink {
                 // if broken, defaults to false
                 is_dir: symlink_meta.map(|m| m.is_dir()).unwrap_or_default(),
+                broken: symlink_meta.is_none(),
             }
         } else {
             FileType::Special
@@ -85,9 +87,23 @@ impl FileType {
     pub fn is_dirlike(self) -> bool {
         matches!(
             self,
-            FileType::Directory { .. } | FileType::SymLink { is_dir: true }
+            FileType::Directory { .. } | FileType::SymLink { is_dir: true, .. }
         )
     }
+
+    /// The name of the type, as printed in the JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            FileType::File { .. } => "file",
+            FileType::Directory { .. } => "directory",
+            FileType::Pipe => "pipe",
+            FileType::SymLink { .. } => "symlink",
+            FileType::BlockDevice => "block-device",
+            FileType::CharDevice => "char-device",
+            FileType::Socket => "socket",
+            FileType::Special => "special",
+        }
+    }
 }
 
 impl FileType {
@@ -95,7 +111,7 @@ impl FileType {
         match self {
             FileType::File { exec, .. } => colors.colorize('.', &Elem::File { exec, uid: false }),
             FileType::Directory { .. } => colors.colorize('d', &Elem::Dir { uid: false }),
-            FileType::Pipe => colors.colorize('|', &Elem::Pipe),
+            FileType::Pipe => colors.colorize('p', &Elem::Pipe),
             FileType::SymLink { .. } => colors.colorize('l', &Elem::SymLink),
             FileType::BlockDevice => colors.colorize('b', &Elem::BlockDevice),
             FileType::CharDevice => colors.colorize('c', &Elem::CharDevice),
@@ -110,7 +126,7 @@ mod test {
     use super::FileType;
     use crate::color::{Colors, ThemeOption};
     #[cfg(unix)]
-    use crate::flags::PermissionFlag;
+    use crate::flags::{DateSource, PermissionFlag};
     #[cfg(unix)]
     use crate::meta::permissions_or_attributes::PermissionsOrAttributes;
     #[cfg(unix)]
@@ -148,8 +164,13 @@ mod test {
     fn test_dir_type() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         #[cfg(not(windows))]
-        let meta = crate::meta::Meta::from_path(tmp_dir.path(), false, PermissionFlag::Rwx)
-            .expect("failed to get tempdir path");
+        let meta = crate::meta::Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .expect("failed to get tempdir path");
         let metadata = tmp_dir.path().metadata().expect("failed to get metas");
 
         let colors = Colors::new(ThemeOption::NoLscolors);
@@ -239,7 +260,7 @@ mod test {
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
 
         assert_eq!(
-            "|".to_string().with(Color::AnsiValue(44)),
+            "p".to_string().with(Color::AnsiValue(44)),
             file_type.render(&colors)
         );
     }
@@ -272,6 +293,23 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_char_device_type_null() {
+        let meta = std::path::Path::new("/dev/null")
+            .metadata()
+            .expect("failed to get metas");
+
+        let colors = Colors::new(ThemeOption::NoLscolors);
+        let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
+
+        assert_eq!(FileType::CharDevice, file_type);
+        assert_eq!(
+            "c".to_string().with(Color::AnsiValue(172)),
+            file_type.render(&colors)
+        );
+    }
+
     #[test]
     #[cfg(unix)] // Sockets don't work the same way on Windows
     fn test_socket_type() {
@@ -1,4 +1,4 @@
-use crate::color::{self, ColoredString, Colors};
+use crate::color::{self, ColoredString, Colors, Elem};
 use crate::git::GitStatus;
 use crate::git_theme::GitTheme;
 
@@ -43,6 +43,32 @@ impl GitFileStatus {
         }
     }
 
+    /// The color of the name of the entry, for its most pressing change: a conflict, then a
+    /// change not staged, then being untracked, then a staged change. This is [None] when the
+    /// entry is unchanged.
+    pub fn name_elem(&self) -> Option<Elem> {
+        match (self.index, self.workdir) {
+            (_, GitStatus::Conflicted) => Some(Elem::GitConflicted),
+            (
+                _,
+                GitStatus::Modified
+                | GitStatus::Deleted
+                | GitStatus::Renamed
+                | GitStatus::Typechange,
+            ) => Some(Elem::GitModified),
+            (_, GitStatus::NewInWorkdir) => Some(Elem::GitUntracked),
+            (
+                GitStatus::NewInIndex
+                | GitStatus::Modified
+                | GitStatus::Deleted
+                | GitStatus::Renamed
+                | GitStatus::Typechange,
+                _,
+            ) => Some(Elem::GitStaged),
+            _ => None,
+        }
+    }
+
     pub fn render(&self, colors: &Colors, git_theme: &GitTheme) -> ColoredString {
         let res = [
             colors.colorize(
@@ -65,3 +91,43 @@ impl GitFileStatus {
         ColoredString::new(Colors::default_style(), res)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::GitFileStatus;
+    use crate::color::Elem;
+    use crate::git::GitStatus;
+
+    fn status(index: GitStatus, workdir: GitStatus) -> GitFileStatus {
+        GitFileStatus { index, workdir }
+    }
+
+    #[test]
+    fn test_name_elem() {
+        use GitStatus::*;
+
+        assert_eq!(None, status(Unmodified, Unmodified).name_elem());
+        assert_eq!(None, status(Unmodified, Ignored).name_elem());
+        assert_eq!(None, GitFileStatus::default().name_elem());
+        assert_eq!(
+            Some(Elem::GitModified),
+            status(Unmodified, Modified).name_elem()
+        );
+        assert_eq!(
+            Some(Elem::GitUntracked),
+            status(Unmodified, NewInWorkdir).name_elem()
+        );
+        assert_eq!(
+            Some(Elem::GitStaged),
+            status(NewInIndex, Unmodified).name_elem()
+        );
+        assert_eq!(
+            Some(Elem::GitModified),
+            status(Modified, Modified).name_elem()
+        );
+        assert_eq!(
+            Some(Elem::GitConflicted),
+            status(Modified, Conflicted).name_elem()
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::print_error;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The rules of the directories already read by their canonical path, [None] for the ones out
+/// of a git repository, so that the `.gitignore` files are compiled once for the whole walk.
+static CACHE: Lazy<Mutex<HashMap<PathBuf, Option<Arc<IgnoreRules>>>>> = Lazy::new(Default::default);
+
+/// The git ignore rules applying to the entries of a directory.
+pub struct IgnoreRules {
+    /// The canonical path of the directory.
+    dir: PathBuf,
+    /// The rules of the repository excludes then of the `.gitignore` files, from the root of the
+    /// repository down to the directory, so the last ones take precedence.
+    matchers: Vec<Arc<Gitignore>>,
+}
+
+impl IgnoreRules {
+    /// Get the rules applying to the entries of `dir`, or return [None] when it is not in a git
+    /// repository.
+    pub fn for_dir(dir: &Path) -> Option<Arc<Self>> {
+        Self::for_canonical_dir(&dir.canonicalize().ok()?)
+    }
+
+    fn for_canonical_dir(dir: &Path) -> Option<Arc<Self>> {
+        let cached = CACHE
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(dir)
+            .cloned();
+        if let Some(rules) = cached {
+            return rules;
+        }
+
+        // the lock is not held while reading, the rules of the parent being read first
+        let rules = Self::read(dir).map(Arc::new);
+        CACHE
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(dir.to_path_buf(), rules.clone());
+        rules
+    }
+
+    /// Read the `.gitignore` of `dir` over the rules of its parent, or over the excludes of the
+    /// repository at its root.
+    fn read(dir: &Path) -> Option<Self> {
+        let mut matchers = if dir.join(".git").exists() {
+            let mut matchers = Vec::new();
+            let exclude = dir.join(".git").join("info").join("exclude");
+            if exclude.is_file() {
+                let mut builder = GitignoreBuilder::new(dir);
+                if let Some(err) = builder.add(&exclude) {
+                    print_error!("{}: {}.", exclude.display(), err);
+                }
+                matchers.extend(builder.build().ok().map(Arc::new));
+            }
+            matchers
+        } else {
+            Self::for_canonical_dir(dir.parent()?)?.matchers.clone()
+        };
+
+        let gitignore = dir.join(".gitignore");
+        if gitignore.is_file() {
+            let (matcher, err) = Gitignore::new(&gitignore);
+            if let Some(err) = err {
+                print_error!("{}: {}.", gitignore.display(), err);
+            }
+            matchers.push(Arc::new(matcher));
+        }
+
+        Some(Self {
+            dir: dir.to_path_buf(),
+            matchers,
+        })
+    }
+
+    /// Whether the entry of the directory with this name is ignored, the deepest rule matching
+    /// it or one of its parents deciding, so a `!pattern` can show again what a parent directory
+    /// ignores.
+    pub fn is_ignored(&self, name: &OsStr, is_dir: bool) -> bool {
+        let path = self.dir.join(name);
+        for matcher in self.matchers.iter().rev() {
+            match matcher.matched_path_or_any_parents(&path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IgnoreRules;
+    use std::ffi::OsStr;
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_for_dir_outside_repository() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        assert!(IgnoreRules::for_dir(tmp_dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_is_ignored_nested_and_negated() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let nested = tmp_dir.path().join("nested");
+        fs::create_dir_all(tmp_dir.path().join(".git")).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(tmp_dir.path().join(".gitignore"), "*.log\ntarget/\n").unwrap();
+        fs::write(nested.join(".gitignore"), "!keep.log\nlocal\n").unwrap();
+
+        let root = IgnoreRules::for_dir(tmp_dir.path()).unwrap();
+        assert!(root.is_ignored(OsStr::new("debug.log"), false));
+        assert!(root.is_ignored(OsStr::new("target"), true));
+        assert!(!root.is_ignored(OsStr::new("target"), false));
+        assert!(!root.is_ignored(OsStr::new("local"), false));
+
+        let nested = IgnoreRules::for_dir(&nested).unwrap();
+        assert!(nested.is_ignored(OsStr::new("debug.log"), false));
+        assert!(!nested.is_ignored(OsStr::new("keep.log"), false));
+        assert!(nested.is_ignored(OsStr::new("local"), false));
+    }
+
+    #[test]
+    fn test_is_ignored_in_ignored_dir() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let target = tmp_dir.path().join("target");
+        fs::create_dir_all(tmp_dir.path().join(".git")).unwrap();
+        fs::create_dir_all(&target).unwrap();
+        fs::write(
+            tmp_dir.path().join(".gitignore"),
+            "target/
+",
+        )
+        .unwrap();
+
+        let rules = IgnoreRules::for_dir(&target).unwrap();
+        assert!(rules.is_ignored(OsStr::new("debug"), false));
+    }
+
+    #[test]
+    fn test_for_dir_reads_each_gitignore_once() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let nested = tmp_dir.path().join("nested");
+        fs::create_dir_all(tmp_dir.path().join(".git")).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            tmp_dir.path().join(".gitignore"),
+            "*.log
+",
+        )
+        .unwrap();
+
+        let root = IgnoreRules::for_dir(tmp_dir.path()).unwrap();
+        let nested_rules = IgnoreRules::for_dir(&nested).unwrap();
+        assert!(Arc::ptr_eq(&root.matchers[0], &nested_rules.matchers[0]));
+        assert!(Arc::ptr_eq(
+            &nested_rules,
+            &IgnoreRules::for_dir(&nested).unwrap()
+        ));
+    }
+}
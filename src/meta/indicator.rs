@@ -21,6 +21,14 @@ impl From<FileType> for Indicator {
 }
 
 impl Indicator {
+    /// The indicator of an entry, a `~` marking a sparse file unless it is executable.
+    pub fn new(file_type: FileType, sparse: bool) -> Self {
+        match file_type {
+            FileType::File { exec: false, .. } if sparse => Indicator("~"),
+            _ => Self::from(file_type),
+        }
+    }
+
     pub fn render(&self, flags: &Flags) -> ColoredString {
         if flags.display_indicators.0 {
             ColoredString::new(Colors::default_style(), self.0.to_string())
@@ -82,10 +90,16 @@ mod test {
             ..Default::default()
         };
 
-        let file_type = Indicator::from(FileType::SymLink { is_dir: false });
+        let file_type = Indicator::from(FileType::SymLink {
+            is_dir: false,
+            broken: false,
+        });
         assert_eq!("@", file_type.render(&flags).to_string());
 
-        let file_type = Indicator::from(FileType::SymLink { is_dir: true });
+        let file_type = Indicator::from(FileType::SymLink {
+            is_dir: true,
+            broken: false,
+        });
         assert_eq!("@", file_type.render(&flags).to_string());
     }
 
@@ -104,4 +118,31 @@ mod test {
 
         assert_eq!("", file_type.render(&flags).to_string());
     }
+
+    #[test]
+    fn test_sparse_file_indicator() {
+        let flags = Flags {
+            display_indicators: Indicators(true),
+            ..Default::default()
+        };
+
+        let file_type = Indicator::new(
+            FileType::File {
+                exec: false,
+                uid: false,
+            },
+            true,
+        );
+        assert_eq!("~", file_type.render(&flags).to_string());
+
+        // the executable files keep their indicator
+        let file_type = Indicator::new(
+            FileType::File {
+                exec: true,
+                uid: false,
+            },
+            true,
+        );
+        assert_eq!("*", file_type.render(&flags).to_string());
+    }
 }
@@ -1,4 +1,5 @@
 use crate::color::{ColoredString, Colors, Elem};
+#[cfg(unix)]
 use std::fs::Metadata;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -6,8 +7,8 @@ pub struct INode {
     index: Option<u64>,
 }
 
+#[cfg(unix)]
 impl From<&Metadata> for INode {
-    #[cfg(unix)]
     fn from(meta: &Metadata) -> Self {
         use std::os::unix::fs::MetadataExt;
 
@@ -15,26 +16,49 @@ impl From<&Metadata> for INode {
 
         Self { index: Some(index) }
     }
+}
 
+impl INode {
+    /// Get the file index of a path, the Windows counterpart of the inode number.
     #[cfg(windows)]
-    fn from(_: &Metadata) -> Self {
-        Self { index: None }
+    pub fn from_path(path: &std::path::Path) -> Self {
+        Self {
+            index: super::windows_utils::get_file_index(path),
+        }
     }
-}
 
-impl INode {
-    pub fn render(&self, colors: &Colors) -> ColoredString {
+    pub fn index(&self) -> Option<u64> {
+        self.index
+    }
+
+    pub fn value_string(&self) -> String {
         match self.index {
-            Some(i) => colors.colorize(i.to_string(), &Elem::INode { valid: true }),
-            None => colors.colorize('-', &Elem::INode { valid: false }),
+            Some(i) => i.to_string(),
+            None => String::from('-'),
         }
     }
+
+    /// Render the index, right-aligned with `alignment` if given.
+    pub fn render(&self, colors: &Colors, alignment: Option<usize>) -> ColoredString {
+        let content = self.value_string();
+        let content = match alignment {
+            Some(align) => format!("{content:>align$}"),
+            None => content,
+        };
+        colors.colorize(
+            content,
+            &Elem::INode {
+                valid: self.index.is_some(),
+            },
+        )
+    }
 }
 
 #[cfg(test)]
 #[cfg(unix)]
 mod tests {
     use super::INode;
+    use crate::color::{Colors, ThemeOption};
     use std::env;
     use std::io;
     use std::path::Path;
@@ -59,4 +83,13 @@ mod tests {
         #[cfg(windows)]
         assert!(inode.index.is_none());
     }
+
+    #[test]
+    fn test_render_right_aligned() {
+        let colors = Colors::new(ThemeOption::NoColor);
+        let inode = INode { index: Some(42) };
+
+        assert_eq!("42", inode.render(&colors, None).content());
+        assert_eq!("   42", inode.render(&colors, Some(5)).content());
+    }
 }
@@ -23,18 +23,34 @@ impl From<&Metadata> for Links {
 }
 
 impl Links {
-    pub fn render(&self, colors: &Colors) -> ColoredString {
+    pub fn value_string(&self) -> String {
         match self.nlink {
-            Some(i) => colors.colorize(i.to_string(), &Elem::Links { valid: true }),
-            None => colors.colorize('-', &Elem::Links { valid: false }),
+            Some(i) => i.to_string(),
+            None => String::from('-'),
         }
     }
+
+    /// Render the number of links, right-aligned with `alignment` if given.
+    pub fn render(&self, colors: &Colors, alignment: Option<usize>) -> ColoredString {
+        let content = self.value_string();
+        let content = match alignment {
+            Some(align) => format!("{content:>align$}"),
+            None => content,
+        };
+        colors.colorize(
+            content,
+            &Elem::Links {
+                valid: self.nlink.is_some(),
+            },
+        )
+    }
 }
 
 #[cfg(test)]
 #[cfg(unix)]
 mod tests {
     use super::Links;
+    use crate::color::{Colors, ThemeOption};
     use std::env;
     use std::io;
     use std::path::Path;
@@ -59,4 +75,13 @@ mod tests {
         #[cfg(windows)]
         assert!(links.nlink.is_none());
     }
+
+    #[test]
+    fn test_render_right_aligned() {
+        let colors = Colors::new(ThemeOption::NoColor);
+        let links = Links { nlink: Some(2) };
+
+        assert_eq!("2", links.render(&colors, None).content());
+        assert_eq!("  2", links.render(&colors, Some(3)).content());
+    }
 }
@@ -1,7 +1,11 @@
 mod access_control;
+mod block_count;
 mod date;
+mod file_flags;
+mod filesystem;
 mod filetype;
 pub mod git_file_status;
+mod gitignore;
 mod indicator;
 mod inode;
 mod links;
@@ -12,6 +16,7 @@ mod permissions;
 mod permissions_or_attributes;
 mod size;
 mod symlink;
+mod total_size;
 
 #[cfg(windows)]
 mod windows_attributes;
@@ -19,9 +24,13 @@ mod windows_attributes;
 mod windows_utils;
 
 pub use self::access_control::AccessControl;
+pub use self::block_count::BlockCount;
 pub use self::date::Date;
+pub use self::file_flags::FileFlags;
+pub use self::filesystem::Filesystem;
 pub use self::filetype::FileType;
 pub use self::git_file_status::GitFileStatus;
+use self::gitignore::IgnoreRules;
 pub use self::indicator::Indicator;
 pub use self::inode::INode;
 pub use self::links::Links;
@@ -32,12 +41,16 @@ use self::permissions_or_attributes::PermissionsOrAttributes;
 pub use self::size::Size;
 pub use self::symlink::SymLink;
 
-use crate::flags::{Display, Flags, Layout, PermissionFlag};
+use crate::flags::blocks::Block;
+use crate::flags::{DateSource, Display, Flags, Layout, OutputFormat, PermissionFlag, SortColumn};
 use crate::{print_error, ExitCode};
 
 use crate::git::GitCache;
+use std::fs::{Metadata, ReadDir};
 use std::io::{self, Error, ErrorKind};
+use std::num::NonZeroUsize;
 use std::path::{Component, Path, PathBuf};
+use std::thread;
 
 #[cfg(windows)]
 use self::windows_attributes::get_attributes;
@@ -53,10 +66,107 @@ pub struct Meta {
     pub symlink: SymLink,
     pub indicator: Indicator,
     pub inode: Option<INode>,
+    pub block_count: Option<BlockCount>,
     pub links: Option<Links>,
     pub content: Option<Vec<Meta>>,
     pub access_control: Option<AccessControl>,
     pub git_status: Option<GitFileStatus>,
+    pub entry_count: Option<u64>,
+    pub file_flags: Option<FileFlags>,
+    pub filesystem: Option<Filesystem>,
+    /// Whether the content of this directory was not read because of the depth limit.
+    pub truncated: bool,
+    /// Whether this symlink was not followed because it leads back to a directory being listed.
+    pub recursion: bool,
+    /// Whether this is the `.` or `..` entry listed with `--all`.
+    pub dot_entry: bool,
+    /// Whether this is a sparse file, which takes less space on the disk than its size.
+    pub sparse: bool,
+    /// The collation key of the name, cached by [crate::sort::cache_sort_keys].
+    pub name_key: Option<String>,
+}
+
+/// What identifies a directory to detect the symlink loops: its device and inode numbers, or its
+/// canonical path on Windows.
+#[cfg(unix)]
+type DirId = (u64, u64);
+#[cfg(windows)]
+type DirId = PathBuf;
+
+/// The maximum number of threads reading the metadata of the entries of a directory.
+const MAX_THREADS: usize = 8;
+/// The minimum number of entries read by each thread, below which they are read serially.
+const MIN_PATHS_PER_THREAD: usize = 256;
+/// The number of entries of a directory whose metadata is read at once.
+const BATCH_SIZE: usize = 8192;
+
+/// What to read of the entries, on top of their metadata: the parts which need more system calls
+/// are only read when they are displayed or sorted by.
+#[derive(Clone, Copy, Debug)]
+pub struct MetaRequest {
+    pub dereference: bool,
+    pub permission: PermissionFlag,
+    pub date_source: DateSource,
+    /// Whether the owner is read, which queries the security of each file on Windows.
+    pub owner: bool,
+    /// Whether the permissions are read, which queries the security of each file on Windows.
+    pub permissions: bool,
+    /// Whether the ACL, the security contexts and the extended attributes are read.
+    pub access_control: bool,
+    /// Whether the inode is read, which opens each file on Windows.
+    pub inode: bool,
+    /// Whether the allocated blocks are read, which queries each file on Windows.
+    pub block_count: bool,
+}
+
+impl MetaRequest {
+    /// A request reading everything.
+    pub fn all(dereference: bool, permission: PermissionFlag, date_source: DateSource) -> Self {
+        Self {
+            dereference,
+            permission,
+            date_source,
+            owner: true,
+            permissions: true,
+            access_control: true,
+            inode: true,
+            block_count: true,
+        }
+    }
+}
+
+impl From<&Flags> for MetaRequest {
+    fn from(flags: &Flags) -> Self {
+        let blocks = &flags.blocks.0;
+        // the JSON output has all the fields, and a custom sorter may use any of them
+        let all = flags.output == OutputFormat::Json
+            || matches!(flags.sorting.column, SortColumn::Custom(_))
+            || flags
+                .sorting
+                .keys
+                .0
+                .iter()
+                .any(|(column, _)| matches!(column, SortColumn::Custom(_)));
+
+        Self {
+            owner: all
+                || blocks.contains(&Block::User)
+                || blocks.contains(&Block::Group)
+                || flags.sorting.sorts_by(SortColumn::Owner),
+            permissions: all
+                || blocks.contains(&Block::Permission)
+                || flags.sorting.sorts_by(SortColumn::Permission),
+            access_control: all
+                || blocks.contains(&Block::Permission)
+                || blocks.contains(&Block::Context)
+                || flags.xattr.0,
+            inode: all
+                || blocks.contains(&Block::INode)
+                || flags.sorting.sorts_by(SortColumn::Inode),
+            block_count: all || blocks.contains(&Block::BlockCount),
+            ..Self::all(flags.dereference.0, flags.permission, flags.date_source)
+        }
+    }
 }
 
 impl Meta {
@@ -66,56 +176,149 @@ impl Meta {
         flags: &Flags,
         cache: Option<&GitCache>,
     ) -> io::Result<(Option<Vec<Meta>>, ExitCode)> {
+        let mut ancestors = Vec::from_iter(Self::dir_id(&self.path));
+        // like `ls`, failing to open a directory given by the user is a serious trouble
+        match self.content_entries(depth, flags, ExitCode::MajorIssue) {
+            (Some(entries), _) => {
+                self.collect_entries(entries, depth, flags, cache, &mut ancestors)
+            }
+            (None, exit_code) => Ok((None, exit_code)),
+        }
+    }
+
+    /// Read the content of this directory like [recurse_into](Meta::recurse_into) with a depth
+    /// of 1, passing each entry to `f` once read rather than collecting them.
+    ///
+    /// The entries are read a batch after another, so that a huge directory is not held in
+    /// memory. The errors met reading the entries are returned like those of `f`.
+    pub fn stream_content<E: From<io::Error>>(
+        &self,
+        flags: &Flags,
+        cache: Option<&GitCache>,
+        mut f: impl FnMut(Self) -> Result<(), E>,
+    ) -> Result<ExitCode, E> {
+        match self.content_entries(1, flags, ExitCode::MajorIssue) {
+            (Some(entries), _) => {
+                let mut ancestors = Vec::from_iter(Self::dir_id(&self.path));
+                self.for_each_entry(entries, 1, flags, cache, &mut ancestors, &mut f)
+            }
+            (None, exit_code) => Ok(exit_code),
+        }
+    }
+
+    /// Read the content of this directory, `ancestors` holding the directories being listed.
+    fn recurse_into_dir(
+        &self,
+        depth: usize,
+        flags: &Flags,
+        cache: Option<&GitCache>,
+        ancestors: &mut Vec<DirId>,
+    ) -> io::Result<(Option<Vec<Meta>>, ExitCode)> {
+        match self.content_entries(depth, flags, ExitCode::MinorIssue) {
+            (Some(entries), _) => self.collect_entries(entries, depth, flags, cache, ancestors),
+            (None, exit_code) => Ok((None, exit_code)),
+        }
+    }
+
+    /// Read the listed `entries` of this directory with their content.
+    fn collect_entries(
+        &self,
+        entries: ReadDir,
+        depth: usize,
+        flags: &Flags,
+        cache: Option<&GitCache>,
+        ancestors: &mut Vec<DirId>,
+    ) -> io::Result<(Option<Vec<Meta>>, ExitCode)> {
+        let mut content: Vec<Meta> = Vec::new();
+        let exit_code =
+            self.for_each_entry(entries, depth, flags, cache, ancestors, &mut |meta| {
+                content.push(meta);
+                Ok::<_, io::Error>(())
+            })?;
+
+        Ok((Some(content), exit_code))
+    }
+
+    /// The entries of this directory if its content is listed, with the exit code of opening it:
+    /// `failure` when it can not be opened.
+    fn content_entries(
+        &self,
+        depth: usize,
+        flags: &Flags,
+        failure: ExitCode,
+    ) -> (Option<ReadDir>, ExitCode) {
         if depth == 0 {
-            return Ok((None, ExitCode::OK));
+            return (None, ExitCode::OK);
         }
 
         if flags.display == Display::DirectoryOnly && flags.layout != Layout::Tree {
-            return Ok((None, ExitCode::OK));
+            return (None, ExitCode::OK);
         }
 
         match self.file_type {
             FileType::Directory { .. } => (),
-            FileType::SymLink { is_dir: true } => {
-                if flags.layout == Layout::OneLine {
-                    return Ok((None, ExitCode::OK));
+            FileType::SymLink { is_dir: true, .. } => {
+                if flags.layout == Layout::OneLine && !flags.follow_symlinks.0 {
+                    return (None, ExitCode::OK);
                 }
             }
-            _ => return Ok((None, ExitCode::OK)),
+            _ => return (None, ExitCode::OK),
         }
 
-        let entries = match self.path.read_dir() {
-            Ok(entries) => entries,
+        match self.path.read_dir() {
+            Ok(entries) => (Some(entries), ExitCode::OK),
+            // like `ls`, the error is reported and the other entries are still listed
             Err(err) => {
-                print_error!("{}: {}.", self.path.display(), err);
-                return Ok((None, ExitCode::MinorIssue));
+                print_error!(
+                    "cannot open directory '{}': {}",
+                    self.path.display(),
+                    error_message(&err)
+                );
+                (None, failure)
             }
-        };
-
-        let mut content: Vec<Meta> = Vec::new();
+        }
+    }
 
+    /// Pass the listed `entries` of this directory to `f`, read with their content.
+    fn for_each_entry<E: From<io::Error>>(
+        &self,
+        entries: ReadDir,
+        depth: usize,
+        flags: &Flags,
+        cache: Option<&GitCache>,
+        ancestors: &mut Vec<DirId>,
+        f: &mut impl FnMut(Self) -> Result<(), E>,
+    ) -> Result<ExitCode, E> {
         if matches!(flags.display, Display::All | Display::SystemProtected)
             && flags.layout != Layout::Tree
         {
             let mut current_meta = self.clone();
             current_meta.name.name = ".".to_owned();
+            current_meta.dot_entry = true;
 
-            let mut parent_meta = Self::from_path(
+            let mut parent_meta = Self::from_path_for(
                 &self.path.join(Component::ParentDir),
-                flags.dereference.0,
-                flags.permission,
+                &MetaRequest::from(flags),
             )?;
             parent_meta.name.name = "..".to_owned();
+            parent_meta.dot_entry = true;
 
             current_meta.git_status = cache.and_then(|cache| cache.get(&current_meta.path, true));
             parent_meta.git_status = cache.and_then(|cache| cache.get(&parent_meta.path, true));
 
-            content.push(current_meta);
-            content.push(parent_meta);
+            f(current_meta)?;
+            f(parent_meta)?;
         }
 
         let mut exit_code = ExitCode::OK;
+        let ignore_rules = if flags.gitignore.0 {
+            IgnoreRules::for_dir(&self.path)
+        } else {
+            None
+        };
 
+        // the entries are filtered first, so that only the listed ones are read in parallel
+        let mut listed = Vec::new();
         for entry in entries {
             let entry = entry?;
             let path = entry.path();
@@ -124,9 +327,16 @@ impl Meta {
                 .file_name()
                 .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
 
-            if flags.ignore_globs.0.is_match(name) {
+            let file_type = entry.file_type()?;
+            let is_dir = file_type.is_dir();
+            if flags.ignore_globs.is_match(name, &path, is_dir) {
                 continue;
             }
+            if let Some(rules) = &ignore_rules {
+                if rules.is_ignored(name, is_dir) {
+                    continue;
+                }
+            }
 
             #[cfg(windows)]
             let is_hidden =
@@ -147,8 +357,33 @@ impl Meta {
                 _ => {}
             }
 
-            let mut entry_meta = match Self::from_path(&path, flags.dereference.0, flags.permission)
-            {
+            listed.push((path, is_dir, file_type.is_symlink()));
+            if listed.len() == BATCH_SIZE {
+                let batch = std::mem::take(&mut listed);
+                exit_code
+                    .set_if_greater(self.read_entries(batch, depth, flags, cache, ancestors, f)?);
+            }
+        }
+        exit_code.set_if_greater(self.read_entries(listed, depth, flags, cache, ancestors, f)?);
+
+        Ok(exit_code)
+    }
+
+    /// Read the `listed` entries of this directory with their content, passing them to `f`.
+    fn read_entries<E: From<io::Error>>(
+        &self,
+        listed: Vec<(PathBuf, bool, bool)>,
+        depth: usize,
+        flags: &Flags,
+        cache: Option<&GitCache>,
+        ancestors: &mut Vec<DirId>,
+        f: &mut impl FnMut(Self) -> Result<(), E>,
+    ) -> Result<ExitCode, E> {
+        let mut exit_code = ExitCode::OK;
+        let paths: Vec<&Path> = listed.iter().map(|(path, _, _)| path.as_path()).collect();
+        let metas = Self::from_paths(&paths, flags);
+        for ((path, is_dir, is_symlink), entry_meta) in listed.into_iter().zip(metas) {
+            let mut entry_meta = match entry_meta {
                 Ok(res) => res,
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
@@ -158,18 +393,38 @@ impl Meta {
             };
 
             // skip files for --tree -d
-            if flags.layout == Layout::Tree
-                && flags.display == Display::DirectoryOnly
-                && !entry.file_type()?.is_dir()
-            {
+            if flags.layout == Layout::Tree && flags.display == Display::DirectoryOnly && !is_dir {
                 continue;
             }
 
-            // check dereferencing
-            if flags.dereference.0 || !matches!(entry_meta.file_type, FileType::SymLink { .. }) {
-                match entry_meta.recurse_into(depth - 1, flags, cache) {
+            let id = if is_dir || is_symlink {
+                Self::dir_id(&path)
+            } else {
+                None
+            };
+            let follow = flags.dereference.0 || flags.follow_symlinks.0 || !is_symlink;
+            // one of the directories being listed, reached again through a followed symlink, would
+            // be listed forever
+            if follow && id.as_ref().is_some_and(|id| ancestors.contains(id)) {
+                print_error!("{}: not listing already-listed directory.", path.display());
+                exit_code.set_if_greater(ExitCode::MinorIssue);
+                entry_meta.recursion = true;
+            } else if follow {
+                let pushed = id.is_some();
+                ancestors.extend(id);
+                let result = entry_meta.recurse_into_dir(depth - 1, flags, cache, ancestors);
+                if pushed {
+                    ancestors.pop();
+                }
+                match result {
                     Ok((content, rec_exit_code)) => {
                         entry_meta.content = content;
+                        // only a directory with entries left unread is truncated
+                        entry_meta.truncated = depth == 1
+                            && matches!(entry_meta.file_type, FileType::Directory { .. })
+                            && path
+                                .read_dir()
+                                .is_ok_and(|mut entries| entries.next().is_some());
                         exit_code.set_if_greater(rec_exit_code);
                     }
                     Err(err) => {
@@ -180,13 +435,62 @@ impl Meta {
                 };
             }
 
-            let is_directory = entry.file_type()?.is_dir();
-            entry_meta.git_status =
-                cache.and_then(|cache| cache.get(&entry_meta.path, is_directory));
-            content.push(entry_meta);
+            entry_meta.git_status = cache.and_then(|cache| cache.get(&entry_meta.path, is_dir));
+            f(entry_meta)?;
         }
 
-        Ok((Some(content), exit_code))
+        Ok(exit_code)
+    }
+
+    /// Read the metadata of the `paths`, in parallel when they are many, in the order of the
+    /// `paths`.
+    fn from_paths(paths: &[&Path], flags: &Flags) -> Vec<io::Result<Self>> {
+        let threads = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(MAX_THREADS)
+            .min(paths.len() / MIN_PATHS_PER_THREAD);
+        Self::from_paths_in_threads(paths, flags, threads)
+    }
+
+    /// Read the metadata of the `paths` split between `threads` threads, in the order of the
+    /// `paths`.
+    fn from_paths_in_threads(
+        paths: &[&Path],
+        flags: &Flags,
+        threads: usize,
+    ) -> Vec<io::Result<Self>> {
+        let request = MetaRequest::from(flags);
+        let from_path = |path: &&Path| Self::from_path_for(path, &request);
+
+        if threads <= 1 {
+            return paths.iter().map(from_path).collect();
+        }
+
+        let chunk_size = paths.len().div_ceil(threads);
+        thread::scope(|scope| {
+            let handles: Vec<_> = paths
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(from_path).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+
+    /// The identifier of the directory at `path`, the symlinks being followed.
+    fn dir_id(path: &Path) -> Option<DirId> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            path.metadata()
+                .ok()
+                .filter(|metadata| metadata.is_dir())
+                .map(|metadata| (metadata.dev(), metadata.ino()))
+        }
+        #[cfg(windows)]
+        path.canonicalize().ok().filter(|path| path.is_dir())
     }
 
     pub fn calculate_total_size(&mut self) {
@@ -210,57 +514,71 @@ impl Meta {
                 self.size = Some(Size::new(size_accumulated));
             } else {
                 // possibility that 'depth' limited the recursion in 'recurse_into'
-                self.size = Some(Size::new(Meta::calculate_total_file_size(&self.path)));
+                self.size = Some(Size::new(total_size::total_size(&self.path)));
             }
         }
     }
 
-    fn calculate_total_file_size(path: &Path) -> u64 {
-        let metadata = path.symlink_metadata();
-        let metadata = match metadata {
-            Ok(meta) => meta,
-            Err(err) => {
-                print_error!("{}: {}.", path.display(), err);
-                return 0;
+    pub fn calculate_entry_count(&mut self) {
+        if self.file_type.is_dirlike() {
+            // an unreadable directory is counted as empty
+            self.entry_count = Some(
+                self.path
+                    .read_dir()
+                    .map(|entries| entries.count() as u64)
+                    .unwrap_or(0),
+            );
+        }
+
+        if let Some(metas) = &mut self.content {
+            for x in &mut metas.iter_mut() {
+                x.calculate_entry_count();
             }
-        };
-        let file_type = metadata.file_type();
-        if file_type.is_file() {
-            metadata.len()
-        } else if file_type.is_dir() {
-            let mut size = metadata.len();
-
-            let entries = match path.read_dir() {
-                Ok(entries) => entries,
-                Err(err) => {
-                    print_error!("{}: {}.", path.display(), err);
-                    return size;
-                }
-            };
-            for entry in entries {
-                let path = match entry {
-                    Ok(entry) => entry.path(),
-                    Err(err) => {
-                        print_error!("{}: {}.", path.display(), err);
-                        continue;
-                    }
-                };
-                size += Meta::calculate_total_file_size(&path);
+        }
+    }
+
+    pub fn calculate_file_flags(&mut self) {
+        self.file_flags = FileFlags::for_path(&self.path, self.file_type);
+
+        if let Some(metas) = &mut self.content {
+            for x in &mut metas.iter_mut() {
+                x.calculate_file_flags();
             }
-            size
-        } else {
-            0
         }
     }
 
+    pub fn calculate_filesystem(&mut self) {
+        // a dereferenced symlink has the file type of its target
+        let follow_symlink = !matches!(self.file_type, FileType::SymLink { .. });
+        self.filesystem = Filesystem::for_path(&self.path, follow_symlink);
+
+        if let Some(metas) = &mut self.content {
+            for x in &mut metas.iter_mut() {
+                x.calculate_filesystem();
+            }
+        }
+    }
+
+    /// Read everything of the entry at `path`.
+    #[cfg(test)]
     pub fn from_path(
         path: &Path,
         dereference: bool,
         permission_flag: PermissionFlag,
+        date_source: DateSource,
     ) -> io::Result<Self> {
+        Self::from_path_for(
+            path,
+            &MetaRequest::all(dereference, permission_flag, date_source),
+        )
+    }
+
+    /// Read the entry at `path`, leaving out what the `request` does not ask for.
+    pub fn from_path_for(path: &Path, request: &MetaRequest) -> io::Result<Self> {
+        let dereference = request.dereference;
+        let permission_flag = request.permission;
         let mut metadata = path.symlink_metadata()?;
         let mut symlink_meta = None;
-        let mut broken_link = false;
         if metadata.file_type().is_symlink() {
             match path.metadata() {
                 Ok(m) => {
@@ -272,25 +590,32 @@ impl Meta {
                 }
                 Err(e) => {
                     // This case, it is definitely a symlink or
-                    // path.symlink_metadata would have errored out
+                    // path.symlink_metadata would have errored out,
+                    // so the link itself is displayed
                     if dereference {
-                        broken_link = true;
-                        eprintln!("lsd: {}: {}", path.to_str().unwrap_or(""), e);
+                        print_error!(
+                            "{}: dangling symbolic link, displaying the link itself: {}.",
+                            path.display(),
+                            e
+                        );
                     }
                 }
             }
         }
 
+        // the file type, and its executable indicator, needs the permissions in any case
         #[cfg(unix)]
-        let (owner, permissions) = match permission_flag {
+        let permissions = Permissions::from(&metadata);
+        #[cfg(unix)]
+        let (owner, permissions_or_attributes) = match permission_flag {
             PermissionFlag::Disable => (None, None),
             _ => (
-                Some(Owner::from(&metadata)),
-                Some(Permissions::from(&metadata)),
+                request.owner.then(|| Owner::from(&metadata)),
+                request
+                    .permissions
+                    .then_some(PermissionsOrAttributes::Permissions(permissions)),
             ),
         };
-        #[cfg(unix)]
-        let permissions_or_attributes = permissions.map(PermissionsOrAttributes::Permissions);
 
         #[cfg(windows)]
         let (owner, permissions_or_attributes) = match permission_flag {
@@ -301,6 +626,7 @@ impl Meta {
                     &metadata,
                 ))),
             ),
+            _ if !request.owner && !request.permissions => (None, None),
             _ => match windows_utils::get_file_data(path) {
                 Ok((owner, permissions)) => (
                     Some(owner),
@@ -318,70 +644,216 @@ impl Meta {
         };
 
         #[cfg(not(windows))]
-        let file_type = FileType::new(
-            &metadata,
-            symlink_meta.as_ref(),
-            &permissions.unwrap_or_default(),
-        );
+        let file_type = FileType::new(&metadata, symlink_meta.as_ref(), &permissions);
 
         #[cfg(windows)]
         let file_type = FileType::new(&metadata, symlink_meta.as_ref(), path);
 
-        let name = Name::new(path, file_type);
+        let symlink = SymLink::from(path);
+        let mut name = Name::new(path, file_type);
+        if let (Some(target_meta), Some(destination)) = (&symlink_meta, symlink.destination()) {
+            let destination = Path::new(destination);
+            #[cfg(not(windows))]
+            let target_type = FileType::new(target_meta, None, &Permissions::from(target_meta));
+            #[cfg(windows)]
+            let target_type = FileType::new(target_meta, None, destination);
+            name = name.with_target(destination, target_type);
+        }
+        let sparse = matches!(file_type, FileType::File { .. }) && is_sparse(&metadata);
 
-        let (inode, links, size, date, owner, permissions_or_attributes, access_control) =
-            match broken_link {
-                true => (None, None, None, None, None, None, None),
-                false => (
-                    Some(INode::from(&metadata)),
-                    Some(Links::from(&metadata)),
-                    Some(Size::from(&metadata)),
-                    Some(Date::from(&metadata)),
-                    Some(owner),
-                    Some(permissions_or_attributes),
-                    Some(AccessControl::for_path(path)),
-                ),
-            };
+        #[cfg(unix)]
+        let inode = request.inode.then(|| INode::from(&metadata));
+        #[cfg(windows)]
+        let inode = request.inode.then(|| INode::from_path(path));
+
+        #[cfg(unix)]
+        let block_count = request.block_count.then(|| BlockCount::from(&metadata));
+        #[cfg(windows)]
+        let block_count = request.block_count.then(|| BlockCount::from_path(path));
 
         Ok(Self {
             inode,
-            links,
+            block_count,
+            links: Some(Links::from(&metadata)),
             path: path.to_path_buf(),
-            symlink: SymLink::from(path),
-            size,
-            date,
-            indicator: Indicator::from(file_type),
-            owner: owner.unwrap_or_default(),
-            permissions_or_attributes: permissions_or_attributes.unwrap_or_default(),
+            symlink,
+            size: Some(Size::from(&metadata)),
+            date: Some(Date::from_metadata(&metadata, request.date_source)),
+            indicator: Indicator::new(file_type, sparse),
+            owner,
+            permissions_or_attributes,
             name,
             file_type,
             content: None,
-            access_control,
+            access_control: request
+                .access_control
+                .then(|| AccessControl::for_path(path)),
             git_status: None,
+            entry_count: None,
+            file_flags: None,
+            filesystem: None,
+            name_key: None,
+            truncated: false,
+            recursion: false,
+            dot_entry: false,
+            sparse,
         })
     }
 }
 
+/// Whether the file of `metadata` has less blocks allocated than its size needs, like the VM
+/// images and the truncated logs, or is marked as sparse on Windows.
+fn is_sparse(metadata: &Metadata) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.blocks().saturating_mul(512) < metadata.size()
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        use windows::Win32::Storage::FileSystem::FILE_ATTRIBUTE_SPARSE_FILE;
+        metadata.file_attributes() & FILE_ATTRIBUTE_SPARSE_FILE.0 != 0
+    }
+}
+
+/// The description of `err`, without the OS error code which `ls` does not print either, e.g.
+/// `Permission denied`.
+fn error_message(err: &io::Error) -> String {
+    let message = err.to_string();
+    match err.raw_os_error() {
+        Some(code) => message
+            .trim_end_matches(&format!(" (os error {code})"))
+            .to_owned(),
+        None => message,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::flags::PermissionFlag;
+    use crate::flags::blocks::{Block, Blocks};
+    use crate::flags::{DateSource, Flags, OutputFormat, PermissionFlag};
 
-    use super::Meta;
+    use super::{error_message, FileType, Meta, MetaRequest, BATCH_SIZE};
+    use crate::flags::Display;
+    use crate::ExitCode;
     use std::fs::File;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_from_paths_in_threads() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let paths: Vec<_> = (0..10)
+            .map(|i| dir.path().join(format!("file{i}")))
+            .collect();
+        for path in paths.iter().filter(|path| !path.ends_with("file3")) {
+            File::create(path).expect("failed to create file");
+        }
+        let paths: Vec<_> = paths.iter().map(|path| path.as_path()).collect();
+
+        for threads in [1, 3] {
+            let metas = Meta::from_paths_in_threads(&paths, &Flags::default(), threads);
+            assert_eq!(10, metas.len());
+            for (i, meta) in metas.iter().enumerate() {
+                match meta {
+                    Ok(meta) => assert_eq!(format!("file{i}"), meta.name.name),
+                    Err(_) => assert_eq!(3, i),
+                }
+            }
+            assert!(metas[3].is_err());
+        }
+    }
+
+    #[test]
+    fn test_meta_request_from_flags() {
+        let flags = Flags {
+            blocks: Blocks(vec![Block::Name]),
+            ..Default::default()
+        };
+        let request = MetaRequest::from(&flags);
+        assert!(!request.owner);
+        assert!(!request.permissions);
+        assert!(!request.access_control);
+        assert!(!request.inode);
+
+        let flags = Flags {
+            blocks: Blocks(vec![Block::User, Block::Name]),
+            ..Default::default()
+        };
+        let request = MetaRequest::from(&flags);
+        assert!(request.owner);
+        assert!(!request.access_control);
+
+        let flags = Flags {
+            blocks: Blocks(vec![Block::Name]),
+            output: OutputFormat::Json,
+            ..Default::default()
+        };
+        let request = MetaRequest::from(&flags);
+        assert!(request.owner && request.permissions && request.access_control && request.inode);
+    }
+
+    #[test]
+    fn test_from_path_for_names_only() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("file");
+        File::create(&path).expect("failed to create file");
+        let flags = Flags {
+            blocks: Blocks(vec![Block::Name]),
+            ..Default::default()
+        };
+
+        let meta = Meta::from_path_for(&path, &MetaRequest::from(&flags)).unwrap();
+        assert!(meta.owner.is_none());
+        assert!(meta.permissions_or_attributes.is_none());
+        assert!(meta.access_control.is_none());
+        assert!(meta.inode.is_none());
+        assert!(matches!(meta.file_type, FileType::File { .. }));
+    }
+
+    #[test]
+    fn test_calculate_entry_count() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        File::create(dir.path().join("aaa")).expect("failed to create file");
+        File::create(dir.path().join("bbb")).expect("failed to create file");
+        std::fs::create_dir(dir.path().join("ccc")).expect("failed to create dir");
+
+        let mut meta =
+            Meta::from_path(dir.path(), false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
+        assert_eq!(meta.entry_count, None);
+        meta.calculate_entry_count();
+        assert_eq!(meta.entry_count, Some(3));
+
+        let mut file_meta = Meta::from_path(
+            &dir.path().join("aaa"),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        file_meta.calculate_entry_count();
+        assert_eq!(file_meta.entry_count, None);
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_from_path_path() {
         let dir = assert_fs::TempDir::new().unwrap();
-        let meta = Meta::from_path(dir.path(), false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(dir.path(), false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
         assert_eq!(meta.path, dir.path())
     }
 
     #[test]
     fn test_from_path_disable_permission() {
         let dir = assert_fs::TempDir::new().unwrap();
-        let meta = Meta::from_path(dir.path(), false, PermissionFlag::Disable).unwrap();
+        let meta = Meta::from_path(
+            dir.path(),
+            false,
+            PermissionFlag::Disable,
+            DateSource::Modified,
+        )
+        .unwrap();
         assert!(meta.permissions_or_attributes.is_none());
         assert!(meta.owner.is_none());
     }
@@ -392,8 +864,8 @@ mod tests {
 
         let path_a = tmp_dir.path().join("aaa.aa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("bbb.bb");
         let path_c = tmp_dir.path().join("ccc.cc");
@@ -408,8 +880,8 @@ mod tests {
         std::os::windows::fs::symlink_file(path_c, &path_b)
             .expect("failed to create broken symlink");
 
-        let meta_b =
-            Meta::from_path(&path_b, true, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_b = Meta::from_path(&path_b, true, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         assert!(
             meta_a.inode.is_some()
@@ -421,14 +893,212 @@ mod tests {
                 && meta_a.access_control.is_some()
         );
 
+        // the dangling link is displayed with its own metadata
         assert!(
-            meta_b.inode.is_none()
-                && meta_b.links.is_none()
-                && meta_b.size.is_none()
-                && meta_b.date.is_none()
-                && meta_b.owner.is_none()
-                && meta_b.permissions_or_attributes.is_none()
-                && meta_b.access_control.is_none()
+            meta_b.inode.is_some()
+                && meta_b.links.is_some()
+                && meta_b.size.is_some()
+                && meta_b.date.is_some()
+                && meta_b.owner.is_some()
+                && meta_b.permissions_or_attributes.is_some()
+                && meta_b.access_control.is_some()
         );
+        assert!(matches!(meta_b.file_type, FileType::SymLink { .. }));
+    }
+
+    #[test]
+    fn test_recurse_into_marks_truncated_directories() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        std::fs::create_dir_all(tmp_dir.path().join("a").join("b")).unwrap();
+        std::fs::create_dir_all(tmp_dir.path().join("empty")).unwrap();
+        File::create(tmp_dir.path().join("file")).expect("failed to create file");
+
+        let meta = Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        let (content, _) = meta.recurse_into(1, &Flags::default(), None).unwrap();
+        for entry in content.unwrap() {
+            assert_eq!(entry.name.name == "a", entry.truncated);
+            assert!(entry.content.is_none());
+        }
+
+        let (content, _) = meta.recurse_into(2, &Flags::default(), None).unwrap();
+        let dir = content
+            .unwrap()
+            .into_iter()
+            .find(|entry| entry.name.name == "a");
+        assert!(!dir.unwrap().truncated);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_recurse_into_skips_symlink_loops() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir = tmp_dir.path().join("dir");
+        std::fs::create_dir(&dir).unwrap();
+        std::os::unix::fs::symlink(tmp_dir.path(), dir.join("loop")).unwrap();
+
+        let mut flags = Flags::default();
+        flags.dereference.0 = true;
+        let meta = Meta::from_path(
+            tmp_dir.path(),
+            true,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        let (content, _) = meta.recurse_into(usize::MAX, &flags, None).unwrap();
+        let dir_entry = content.unwrap().pop().unwrap();
+        let link = dir_entry.content.unwrap().pop().unwrap();
+        assert_eq!("loop", link.name.name);
+        assert!(link.content.is_none());
+        assert!(link.recursion);
+
+        // the directory itself is not listed again under a symlink to its parent
+        std::os::unix::fs::symlink(tmp_dir.path(), dir.join("up")).unwrap();
+        let dir_meta =
+            Meta::from_path(&dir, true, PermissionFlag::Rwx, DateSource::Modified).unwrap();
+        let (content, _) = dir_meta.recurse_into(usize::MAX, &flags, None).unwrap();
+        let up = content
+            .unwrap()
+            .into_iter()
+            .find(|meta| meta.name.name == "up")
+            .unwrap();
+        let again = up.content.unwrap().pop().unwrap();
+        assert_eq!("dir", again.name.name);
+        assert!(again.content.is_none());
+        assert!(again.recursion);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_recurse_into_follow_symlinks() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let (a, b) = (tmp_dir.path().join("a"), tmp_dir.path().join("b"));
+        std::fs::create_dir(&a).unwrap();
+        std::fs::create_dir(&b).unwrap();
+        std::os::unix::fs::symlink(&b, a.join("to_b")).unwrap();
+        std::os::unix::fs::symlink(&a, b.join("to_a")).unwrap();
+        let meta = Meta::from_path(&a, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
+
+        let (content, _) = meta
+            .recurse_into(usize::MAX, &Flags::default(), None)
+            .unwrap();
+        let to_b = content.unwrap().pop().unwrap();
+        assert!(to_b.content.is_none());
+        assert!(!to_b.recursion);
+
+        let mut flags = Flags::default();
+        flags.follow_symlinks.0 = true;
+        let (content, _) = meta.recurse_into(usize::MAX, &flags, None).unwrap();
+        let to_b = content.unwrap().pop().unwrap();
+        assert!(matches!(
+            to_b.file_type,
+            FileType::SymLink { is_dir: true, .. }
+        ));
+        let to_a = to_b.content.unwrap().pop().unwrap();
+        assert_eq!("to_a", to_a.name.name);
+        assert!(to_a.content.is_none());
+        assert!(to_a.recursion);
+    }
+
+    #[test]
+    fn test_recurse_into_hides_gitignored_files() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        std::fs::create_dir(tmp_dir.path().join(".git")).unwrap();
+        std::fs::write(tmp_dir.path().join(".gitignore"), "*.o\n").unwrap();
+        File::create(tmp_dir.path().join("main.c")).expect("failed to create file");
+        File::create(tmp_dir.path().join("main.o")).expect("failed to create file");
+
+        let mut flags = Flags::default();
+        flags.gitignore.0 = true;
+        let meta = Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        let (content, _) = meta.recurse_into(1, &flags, None).unwrap();
+        let names: Vec<String> = content.unwrap().into_iter().map(|m| m.name.name).collect();
+        assert_eq!(vec!["main.c".to_string()], names);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_path_sparse() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let sparse = tmp_dir.path().join("sparse");
+        File::create(&sparse)
+            .and_then(|file| file.set_len(1 << 20))
+            .expect("failed to create file");
+        let dense = tmp_dir.path().join("dense");
+        std::fs::write(&dense, vec![1; 1 << 16]).expect("failed to write file");
+
+        let meta =
+            Meta::from_path(&sparse, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
+        assert!(meta.sparse);
+        let meta =
+            Meta::from_path(&dense, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
+        assert!(!meta.sparse);
+        let meta = Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        assert!(!meta.sparse);
+    }
+
+    #[test]
+    fn test_error_message() {
+        let err = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        assert_eq!("permission denied", error_message(&err));
+        #[cfg(unix)]
+        assert_eq!(
+            "Permission denied",
+            error_message(&std::io::Error::from_raw_os_error(13))
+        );
+    }
+
+    #[test]
+    fn test_stream_content_matches_recurse_into() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        // one more entry than a batch
+        for i in 0..BATCH_SIZE {
+            File::create(tmp_dir.path().join(format!("file{i}"))).expect("failed to create file");
+        }
+        std::fs::create_dir(tmp_dir.path().join("dir")).unwrap();
+
+        let flags = Flags {
+            display: Display::All,
+            ..Flags::default()
+        };
+        let meta = Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .unwrap();
+        let (content, _) = meta.recurse_into(1, &flags, None).unwrap();
+        let expected: Vec<String> = content.unwrap().into_iter().map(|m| m.name.name).collect();
+
+        let mut names = Vec::new();
+        let exit_code = meta
+            .stream_content(&flags, None, |entry| {
+                assert!(entry.content.is_none());
+                names.push(entry.name.name);
+                Ok::<_, std::io::Error>(())
+            })
+            .unwrap();
+        assert_eq!(BATCH_SIZE + 3, names.len());
+        assert_eq!(expected, names);
+        assert!(exit_code == ExitCode::OK);
     }
 }
@@ -1,12 +1,123 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::HyperlinkOption;
+use crate::flags::{HyperlinkOption, QuotingStyle};
 use crate::icon::Icons;
 use crate::meta::filetype::FileType;
 use crate::print_error;
 use crate::url::Url;
+#[cfg(unix)]
+use once_cell::sync::Lazy;
 use std::cmp::{Ordering, PartialOrd};
 use std::ffi::OsStr;
 use std::path::{Component, Path, PathBuf};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// The host name of the machine, put in the file urls of the hyperlinks.
+#[cfg(unix)]
+static HOSTNAME: Lazy<Option<String>> = Lazy::new(|| {
+    let mut buf = [0u8; 256];
+    // SAFETY: the buffer is valid for writes of its whole length
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+        return None;
+    }
+    let len = buf.iter().position(|&b| b == 0)?;
+    String::from_utf8(buf[..len].to_vec()).ok()
+});
+
+/// The `file://host/path` url of an absolute path, with its special characters percent-encoded.
+///
+/// The host is left empty on Windows, where it would turn the url into a network share.
+fn file_url(path: &Path) -> Option<Url> {
+    #[allow(unused_mut)]
+    let mut url = Url::from_file_path(path).ok()?;
+    #[cfg(unix)]
+    if let Some(host) = HOSTNAME.as_deref() {
+        // a host name which is not valid in a url is left out
+        let _ = url.set_host(Some(host));
+    }
+    Some(url)
+}
+
+/// The characters which have a meaning for a shell, and need the name to be quoted.
+const SHELL_SPECIAL_CHARS: &[char] = &[
+    ' ', '\t', '!', '"', '#', '$', '&', '\'', '(', ')', '*', ';', '<', '>', '?', '[', '\\', ']',
+    '^', '`', '{', '|', '}', '~',
+];
+
+fn is_printable(c: char) -> bool {
+    c >= 0x20 as char && c != 0x7f as char
+}
+
+/// Escape a control character like in C, keeping the other characters as they are.
+fn escape_control(c: char) -> String {
+    match c {
+        '\x07' => String::from("\\a"),
+        '\x08' => String::from("\\b"),
+        '\x0b' => String::from("\\v"),
+        '\x0c' => String::from("\\f"),
+        '\n' | '\r' | '\t' => c.escape_default().collect(),
+        c if !is_printable(c) => format!("\\{:03o}", c as u32),
+        // The `escape_default` method on `char` is *almost* what we want here, but
+        // it still escapes non-ASCII UTF-8 characters, which are still printable.
+        c => c.to_string(),
+    }
+}
+
+/// Cut a name wider than `max_width` columns, ending it with an ellipsis within that width.
+fn truncate(name: &str, max_width: usize) -> String {
+    if name.width() <= max_width {
+        return name.to_string();
+    }
+
+    let mut width = 0;
+    let mut truncated: String = name
+        .chars()
+        .take_while(|c| {
+            width += c.width().unwrap_or(0);
+            width < max_width
+        })
+        .collect();
+    truncated.push('\u{2026}'); // …
+    truncated
+}
+
+/// Quote a name for a shell if it has special characters, preferring single quotes.
+fn shell_quote(name: &str) -> String {
+    if !name.contains(SHELL_SPECIAL_CHARS) {
+        name.to_string()
+    } else if name.contains('\'') && !name.contains(['"', '$', '`', '\\', '!']) {
+        format!("\"{name}\"")
+    } else {
+        format!("'{}'", name.replace('\'', "'\\''"))
+    }
+}
+
+/// Quote a name for a shell like [shell_quote], writing its control characters in `$'...'`
+/// segments, e.g. `'a'$'\n''b'` for a name with a line break.
+fn shell_escape(name: &str) -> String {
+    if name.chars().all(is_printable) {
+        return shell_quote(name);
+    }
+
+    let mut escaped = String::new();
+    let mut chars = name.chars().peekable();
+    while let Some(&first) = chars.peek() {
+        let printable = is_printable(first);
+        let mut segment = String::new();
+        while let Some(c) = chars.next_if(|&c| is_printable(c) == printable) {
+            segment.push(c);
+        }
+
+        if printable {
+            escaped += &format!("'{}'", segment.replace('\'', "'\\''"));
+        } else {
+            escaped += &format!(
+                "$'{}'",
+                segment.chars().map(escape_control).collect::<String>()
+            );
+        }
+    }
+    escaped
+}
 
 #[derive(Debug)]
 pub enum DisplayOption<'a> {
@@ -21,6 +132,8 @@ pub struct Name {
     path: PathBuf,
     extension: Option<String>,
     file_type: FileType,
+    /// The path and the file type of the target of a symlink, when it exists.
+    target: Option<(PathBuf, FileType)>,
 }
 
 impl Name {
@@ -39,9 +152,17 @@ impl Name {
             path: PathBuf::from(path),
             extension,
             file_type,
+            target: None,
         }
     }
 
+    /// Keep the target of a symlink, whose type colors the name when
+    /// [with_symlink_target_color](Colors::with_symlink_target_color) is set.
+    pub fn with_target(mut self, path: &Path, file_type: FileType) -> Self {
+        self.target = Some((path.to_path_buf(), file_type));
+        self
+    }
+
     pub fn file_name(&self) -> &str {
         self.path
             .file_name()
@@ -78,36 +199,26 @@ impl Name {
             .collect()
     }
 
-    fn escape(&self, string: &str, literal: bool) -> String {
-        let mut name = string.to_string();
-        if !literal {
-            if name.contains('\\') || name.contains('"') {
-                name = name.replace('\'', "\'\\\'\'");
-                name = format!("\'{}\'", &name);
-            } else if name.contains('\'') {
-                name = format!("\"{}\"", &name);
-            } else if name.contains(' ') || name.contains('$') {
-                name = format!("\'{}\'", &name);
-            }
-        }
-        let string = name;
-        if string
-            .chars()
-            .all(|c| c >= 0x20 as char && c != 0x7f as char)
-        {
-            string
-        } else {
-            let mut chars = String::new();
-            for c in string.chars() {
-                // The `escape_default` method on `char` is *almost* what we want here, but
-                // it still escapes non-ASCII UTF-8 characters, which are still printable.
-                if c >= 0x20 as char && c != 0x7f as char {
-                    chars.push(c);
-                } else {
-                    chars += &c.escape_default().collect::<String>();
-                }
-            }
-            chars
+    fn escape(&self, string: &str, quoting: QuotingStyle) -> String {
+        match quoting {
+            QuotingStyle::Literal => string.chars().map(escape_control).collect(),
+            QuotingStyle::Shell => shell_quote(
+                &string
+                    .chars()
+                    .map(|c| if is_printable(c) { c } else { '?' })
+                    .collect::<String>(),
+            ),
+            QuotingStyle::Auto | QuotingStyle::ShellEscape => shell_escape(string),
+            QuotingStyle::C => format!(
+                "\"{}\"",
+                string
+                    .chars()
+                    .map(|c| match c {
+                        '"' | '\\' => format!("\\{c}"),
+                        c => escape_control(c),
+                    })
+                    .collect::<String>()
+            ),
         }
     }
 
@@ -117,7 +228,7 @@ impl Name {
                 // HyperlinkOption::Auto gets converted to None or Always in core.rs based on tty_available
                 match std::fs::canonicalize(&self.path) {
                     Ok(rp) => {
-                        if let Ok(url) = Url::from_file_path(rp) {
+                        if let Some(url) = file_url(&rp) {
                             // Crossterm does not support hyperlinks as of now
                             // https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
                             format!("\x1B]8;;{url}\x1B\x5C{name}\x1B]8;;\x1B\x5C")
@@ -140,52 +251,51 @@ impl Name {
         }
     }
 
+    /// Render the name, truncated to `max_width` columns with an ellipsis when it is wider. The
+    /// hyperlink still points to the whole name.
     pub fn render(
         &self,
         colors: &Colors,
         icons: &Icons,
         display_option: &DisplayOption,
         hyperlink: HyperlinkOption,
-        literal: bool,
+        quoting: QuotingStyle,
+        max_width: Option<usize>,
     ) -> ColoredString {
-        let content = match display_option {
-            DisplayOption::FileName => {
-                format!(
-                    "{}{}",
-                    icons.get(self),
-                    self.hyperlink(self.escape(self.file_name(), literal), hyperlink)
-                )
+        let name = match display_option {
+            DisplayOption::FileName => self.file_name().to_string(),
+            DisplayOption::Relative { base_path } => {
+                self.relative_path(base_path).to_string_lossy().to_string()
             }
-            DisplayOption::Relative { base_path } => format!(
-                "{}{}",
-                icons.get(self),
-                self.hyperlink(
-                    self.escape(&self.relative_path(base_path).to_string_lossy(), literal),
-                    hyperlink
-                )
-            ),
-            DisplayOption::None => format!(
-                "{}{}",
-                icons.get(self),
-                self.hyperlink(
-                    self.escape(&self.path.to_string_lossy(), literal),
-                    hyperlink
-                )
-            ),
+            DisplayOption::None => self.path.to_string_lossy().to_string(),
+        };
+        let name = match max_width {
+            Some(max_width) => truncate(&name, max_width),
+            None => name,
         };
+        let content = format!(
+            "{}{}",
+            icons.get(self),
+            self.hyperlink(self.escape(&name, quoting), hyperlink)
+        );
 
-        let elem = match self.file_type {
+        let (path, file_type) = match &self.target {
+            Some((path, file_type)) if colors.symlink_target_color() => (path, *file_type),
+            _ => (&self.path, self.file_type),
+        };
+        let elem = match file_type {
+            FileType::BlockDevice => Elem::BlockDevice,
             FileType::CharDevice => Elem::CharDevice,
+            FileType::Pipe => Elem::Pipe,
+            FileType::Socket => Elem::Socket,
             FileType::Directory { uid } => Elem::Dir { uid },
-            FileType::SymLink { .. } => Elem::SymLink,
+            FileType::SymLink { broken: false, .. } => Elem::SymLink,
+            FileType::SymLink { broken: true, .. } => Elem::BrokenSymLink,
             FileType::File { uid, exec } => Elem::File { uid, exec },
-            _ => Elem::File {
-                exec: false,
-                uid: false,
-            },
+            FileType::Special => Elem::Special,
         };
 
-        colors.colorize_using_path(content, &self.path, &elem)
+        colors.colorize_using_path(content, path, &elem)
     }
 
     pub fn extension(&self) -> Option<&str> {
@@ -217,17 +327,18 @@ impl PartialEq for Name {
 
 #[cfg(test)]
 mod test {
+    use super::file_url;
+    use super::truncate;
     use super::DisplayOption;
     use super::Name;
     use crate::color::{self, Colors};
-    use crate::flags::PermissionFlag;
-    use crate::flags::{HyperlinkOption, IconOption, IconTheme as FlagTheme};
+    use crate::flags::{DateSource, PermissionFlag};
+    use crate::flags::{HyperlinkOption, IconOption, IconTheme as FlagTheme, QuotingStyle};
     use crate::icon::Icons;
     use crate::meta::FileType;
     use crate::meta::Meta;
     #[cfg(unix)]
     use crate::meta::Permissions;
-    use crate::url::Url;
     use crossterm::style::{Color, Stylize};
     use std::cmp::Ordering;
     use std::fs::{self, File};
@@ -260,7 +371,8 @@ mod test {
                 &icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                true,
+                QuotingStyle::Literal,
+                None,
             )
         );
     }
@@ -273,7 +385,8 @@ mod test {
         // Create the directory
         let dir_path = tmp_dir.path().join("directory");
         fs::create_dir(&dir_path).expect("failed to create the dir");
-        let meta = Meta::from_path(&dir_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&dir_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let colors = Colors::new(color::ThemeOption::NoLscolors);
 
@@ -284,7 +397,8 @@ mod test {
                 icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                true
+                QuotingStyle::Literal,
+                None
             )
         );
     }
@@ -318,11 +432,94 @@ mod test {
                 icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                true
+                QuotingStyle::Literal,
+                None
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(unix)] // Symlinks are hard on Windows
+    fn test_print_broken_symlink_name() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string());
+
+        // Create the symlink to a missing file
+        let symlink_path = tmp_dir.path().join("broken.tmp");
+        symlink(tmp_dir.path().join("missing.tmp"), &symlink_path)
+            .expect("failed to create symlink");
+        let meta = symlink_path
+            .symlink_metadata()
+            .expect("failed to get metas");
+        let target_meta = symlink_path.metadata().ok();
+
+        let colors = Colors::new(color::ThemeOption::NoLscolors);
+        let file_type = FileType::new(&meta, target_meta.as_ref(), &Permissions::from(&meta));
+        let name = Name::new(&symlink_path, file_type);
+
+        assert!(matches!(file_type, FileType::SymLink { broken: true, .. }));
+        assert_eq!(
+            "broken.tmp".to_string().with(Color::AnsiValue(124)),
+            name.render(
+                &colors,
+                &icons,
+                &DisplayOption::FileName,
+                HyperlinkOption::Never,
+                QuotingStyle::Literal,
+                None
             )
         );
     }
 
+    #[test]
+    #[cfg(unix)] // Symlinks are hard on Windows
+    fn test_print_symlink_name_target_color() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string());
+
+        // Create the executable;
+        let file_path = tmp_dir.path().join("script.sh");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o755))
+            .expect("unable to set permissions to file");
+
+        // Create the symlink
+        let symlink_path = tmp_dir.path().join("link.sh");
+        symlink(&file_path, &symlink_path).expect("failed to create symlink");
+        let meta = symlink_path
+            .symlink_metadata()
+            .expect("failed to get metas");
+        let target_meta = symlink_path.metadata().expect("failed to get metas");
+
+        let file_type = FileType::new(&meta, Some(&target_meta), &Permissions::from(&meta));
+        let target_type = FileType::new(&target_meta, None, &Permissions::from(&target_meta));
+        let name = Name::new(&symlink_path, file_type).with_target(&file_path, target_type);
+        let render = |colors: &Colors| {
+            name.render(
+                colors,
+                &icons,
+                &DisplayOption::FileName,
+                HyperlinkOption::Never,
+                QuotingStyle::Literal,
+                None,
+            )
+        };
+
+        let colors = Colors::new(color::ThemeOption::NoLscolors);
+        assert_eq!(
+            "link.sh".to_string().with(Color::AnsiValue(44)),
+            render(&colors)
+        );
+
+        let colors = colors.with_symlink_target_color();
+        assert_eq!(
+            "link.sh".to_string().with(Color::AnsiValue(40)),
+            render(&colors)
+        );
+    }
+
     #[test]
     #[cfg(unix)] // Symlinks are hard on Windows
     fn test_print_symlink_name_dir() {
@@ -352,7 +549,8 @@ mod test {
                 &icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                true
+                QuotingStyle::Literal,
+                None
             )
         );
     }
@@ -378,13 +576,14 @@ mod test {
         let name = Name::new(&pipe_path, file_type);
 
         assert_eq!(
-            "󰈲 pipe.tmp".to_string().with(Color::AnsiValue(184)),
+            "󰈲 pipe.tmp".to_string().with(Color::AnsiValue(44)),
             name.render(
                 &colors,
                 icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                true
+                QuotingStyle::Literal,
+                None
             )
         );
     }
@@ -397,7 +596,8 @@ mod test {
         // Create the file;
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let colors = Colors::new(color::ThemeOption::NoColor);
 
@@ -409,7 +609,8 @@ mod test {
                     &icons,
                     &DisplayOption::FileName,
                     HyperlinkOption::Never,
-                    true
+                    QuotingStyle::Literal,
+                    None
                 )
                 .to_string()
         );
@@ -423,12 +624,13 @@ mod test {
         // Create the file;
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+        let meta =
+            Meta::from_path(&file_path, false, PermissionFlag::Rwx, DateSource::Modified).unwrap();
 
         let colors = Colors::new(color::ThemeOption::NoColor);
 
         let real_path = std::fs::canonicalize(&file_path).expect("canonicalize");
-        let expected_url = Url::from_file_path(real_path).expect("absolute path");
+        let expected_url = file_url(&real_path).expect("absolute path");
         let expected_text = format!(
             "\x1B]8;;{}\x1B\x5C{}\x1B]8;;\x1B\x5C",
             expected_url, "file.txt"
@@ -442,12 +644,25 @@ mod test {
                     &icons,
                     &DisplayOption::FileName,
                     HyperlinkOption::Always,
-                    true
+                    QuotingStyle::Literal,
+                    None
                 )
                 .to_string()
         );
     }
 
+    #[test]
+    fn test_file_url_encoding() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let real_path = std::fs::canonicalize(tmp_dir.path()).expect("canonicalize");
+
+        let url = file_url(&real_path.join("a b#c%.txt")).expect("absolute path");
+        assert_eq!("file", url.scheme());
+        assert!(url.as_str().ends_with("/a%20b%23c%25.txt"));
+        #[cfg(unix)]
+        assert_eq!(super::HOSTNAME.as_deref(), url.host_str());
+    }
+
     #[test]
     fn test_extensions_with_valid_file() {
         let path = Path::new("some-file.txt");
@@ -656,6 +871,20 @@ mod test {
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
         let name = Name::new(&file_path, file_type);
 
+        assert_eq!(
+            " 'file'$'\\t''tab.txt'"
+                .to_string()
+                .with(Color::AnsiValue(184)),
+            name.render(
+                &colors,
+                &icons,
+                &DisplayOption::FileName,
+                HyperlinkOption::Never,
+                QuotingStyle::default(),
+                None,
+            )
+        );
+
         assert_eq!(
             " file\\ttab.txt".to_string().with(Color::AnsiValue(184)),
             name.render(
@@ -663,7 +892,8 @@ mod test {
                 &icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                false,
+                QuotingStyle::Literal,
+                None,
             )
         );
 
@@ -682,7 +912,8 @@ mod test {
                 &icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                false,
+                QuotingStyle::ShellEscape,
+                None,
             )
         );
 
@@ -701,7 +932,8 @@ mod test {
                 &icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                false,
+                QuotingStyle::ShellEscape,
+                None,
             )
         );
 
@@ -722,7 +954,8 @@ mod test {
                 &icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                false,
+                QuotingStyle::ShellEscape,
+                None,
             )
         );
 
@@ -734,6 +967,20 @@ mod test {
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
         let name = Name::new(&file_path, file_type);
 
+        assert_eq!(
+            " 'file'$'\\n''newline.txt'"
+                .to_string()
+                .with(Color::AnsiValue(184)),
+            name.render(
+                &colors,
+                &icons,
+                &DisplayOption::FileName,
+                HyperlinkOption::Never,
+                QuotingStyle::default(),
+                None,
+            )
+        );
+
         assert_eq!(
             " file\\nnewline.txt"
                 .to_string()
@@ -743,8 +990,88 @@ mod test {
                 &icons,
                 &DisplayOption::FileName,
                 HyperlinkOption::Never,
-                false,
+                QuotingStyle::Literal,
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn test_quoting_styles() {
+        let name = Name::new(
+            Path::new("it's a\nname"),
+            FileType::File {
+                exec: false,
+                uid: false,
+            },
+        );
+        let escape = |quoting| name.escape(&name.name, quoting);
+
+        assert_eq!("it's a\\nname", escape(QuotingStyle::Literal));
+        assert_eq!("\"it's a?name\"", escape(QuotingStyle::Shell));
+        assert_eq!(
+            "'it'\\''s a'$'\\n''name'",
+            escape(QuotingStyle::ShellEscape)
+        );
+        assert_eq!("\"it's a\\nname\"", escape(QuotingStyle::C));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!("short", truncate("short", 5));
+        assert_eq!("long…", truncate("longer", 5));
+        // the wide characters are not cut, the ellipsis fitting in the width
+        assert_eq!("日本…", truncate("日本語.txt", 5));
+        assert_eq!("日…", truncate("日本語.txt", 4));
+        assert_eq!("…", truncate("日本語.txt", 1));
+    }
+
+    #[test]
+    fn test_render_truncated() {
+        let name = Name::new(
+            Path::new("a long name"),
+            FileType::File {
+                exec: false,
+                uid: false,
+            },
+        );
+        let render = |max_width| {
+            name.render(
+                &Colors::new(color::ThemeOption::NoColor),
+                &Icons::new(false, IconOption::Never, FlagTheme::Fancy, " ".to_string()),
+                &DisplayOption::FileName,
+                HyperlinkOption::Never,
+                QuotingStyle::ShellEscape,
+                max_width,
             )
+            .to_string()
+        };
+
+        assert_eq!("'a long name'", render(None));
+        // the quotes are not counted in the width
+        assert_eq!("'a lo…'", render(Some(5)));
+    }
+
+    #[test]
+    fn test_shell_quote_only_when_needed() {
+        let name = Name::new(
+            Path::new("plain-name_1.txt"),
+            FileType::File {
+                exec: false,
+                uid: false,
+            },
+        );
+        assert_eq!(
+            "plain-name_1.txt",
+            name.escape(&name.name, QuotingStyle::Shell)
+        );
+        assert_eq!("'a(1)&b'", name.escape("a(1)&b", QuotingStyle::ShellEscape));
+        // the history expansion of the shells is not done within single quotes
+        assert_eq!("'wow!'", name.escape("wow!", QuotingStyle::ShellEscape));
+        assert_eq!("'it'\\''s!'", name.escape("it's!", QuotingStyle::Shell));
+        assert_eq!(
+            "\"\\\"q\\\\\\033\"",
+            name.escape("\"q\\\x1b", QuotingStyle::C)
         );
     }
 }
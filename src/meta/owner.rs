@@ -1,16 +1,62 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::Flags;
+use crate::flags::Flags;
+use once_cell::sync::Lazy;
+#[cfg(unix)]
+use std::collections::HashMap;
 #[cfg(unix)]
 use std::fs::Metadata;
 #[cfg(unix)]
-use users::{Groups, Users, UsersCache};
+use std::sync::Mutex;
+use unicode_width::UnicodeWidthStr;
 
+/// The names of the users and groups, each id being looked up once per run.
 #[derive(Default)]
 pub struct Cache {
     #[cfg(unix)]
-    users: UsersCache,
+    users: Mutex<HashMap<u32, String>>,
+    #[cfg(unix)]
+    groups: Mutex<HashMap<u32, String>>,
+}
+
+static SHARED_CACHE: Lazy<Cache> = Lazy::new(Cache::default);
+
+impl Cache {
+    /// The cache shared by the sort and the display of a run.
+    pub fn shared() -> &'static Self {
+        &SHARED_CACHE
+    }
+
+    /// The name of the user with this id, or the id when it has no name.
+    #[cfg(unix)]
+    fn user_name(&self, uid: u32) -> String {
+        let mut users = self.users.lock().unwrap_or_else(|err| err.into_inner());
+        users
+            .entry(uid)
+            .or_insert_with(|| match users::get_user_by_uid(uid) {
+                Some(user) => user.name().to_string_lossy().to_string(),
+                None => uid.to_string(),
+            })
+            .clone()
+    }
+
+    /// The name of the group with this id, or the id when it has no name.
     #[cfg(unix)]
-    groups: UsersCache,
+    fn group_name(&self, gid: u32) -> String {
+        let mut groups = self.groups.lock().unwrap_or_else(|err| err.into_inner());
+        groups
+            .entry(gid)
+            .or_insert_with(|| match users::get_group_by_gid(gid) {
+                Some(group) => group.name().to_string_lossy().to_string(),
+                None => gid.to_string(),
+            })
+            .clone()
+    }
+
+    /// Whether the name of the user with this id was looked up.
+    #[cfg(all(unix, test))]
+    pub fn has_user(&self, uid: u32) -> bool {
+        self.users.lock().unwrap().contains_key(&uid)
+    }
 }
 
 #[cfg(unix)]
@@ -32,6 +78,11 @@ impl Owner {
     pub fn new(user: String, group: String) -> Self {
         Self { user, group }
     }
+
+    #[cfg(all(unix, test))]
+    pub fn new(user: u32, group: u32) -> Self {
+        Self { user, group }
+    }
 }
 
 #[cfg(unix)]
@@ -62,45 +113,113 @@ fn truncate(input: &str, after: Option<usize>, marker: Option<String>) -> String
     output
 }
 
+/// Pad the start of `input` up to the `alignment` width.
+fn align_right(input: String, alignment: Option<usize>) -> String {
+    match alignment {
+        Some(align) => format!("{}{input}", " ".repeat(align.saturating_sub(input.width()))),
+        None => input,
+    }
+}
+
 impl Owner {
+    /// The ids of the user and of the group.
+    #[cfg(unix)]
+    pub fn ids(&self) -> (u32, u32) {
+        (self.user, self.group)
+    }
+
+    /// The name of the user, or its id when it has no name.
     // allow unused variables because cache is used in unix, maybe we can cache for windows in the future
     #[allow(unused_variables)]
-    pub fn render_user(&self, colors: &Colors, cache: &Cache, flags: &Flags) -> ColoredString {
+    pub fn user_name(&self, cache: &Cache) -> String {
         #[cfg(unix)]
-        let user = &match cache.users.get_user_by_uid(self.user) {
-            Some(user) => user.name().to_string_lossy().to_string(),
-            None => self.user.to_string(),
-        };
+        let user = cache.user_name(self.user);
         #[cfg(windows)]
-        let user = &self.user;
+        let user = self.user.clone();
 
-        colors.colorize(
-            truncate(
-                user,
-                flags.truncate_owner.after,
-                flags.truncate_owner.marker.clone(),
-            ),
-            &Elem::User,
-        )
+        user
     }
 
+    /// The name of the group, or its id when it has no name.
     // allow unused variables because cache is used in unix, maybe we can cache for windows in the future
     #[allow(unused_variables)]
-    pub fn render_group(&self, colors: &Colors, cache: &Cache, flags: &Flags) -> ColoredString {
+    pub fn group_name(&self, cache: &Cache) -> String {
         #[cfg(unix)]
-        let group = &match cache.groups.get_group_by_gid(self.group) {
-            Some(group) => group.name().to_string_lossy().to_string(),
-            None => self.group.to_string(),
-        };
+        let group = cache.group_name(self.group);
         #[cfg(windows)]
-        let group = &self.group;
+        let group = self.group.clone();
 
-        colors.colorize(
+        group
+    }
+
+    /// The user, its id with `--numeric-uid-gid`, so that its name is not looked up, or its name.
+    pub fn user(&self, cache: &Cache, flags: &Flags) -> String {
+        match flags.numeric_uid_gid.0 {
+            // the owners of the files are not numbers on Windows
+            #[cfg(unix)]
+            true => self.user.to_string(),
+            _ => self.user_name(cache),
+        }
+    }
+
+    /// The user as displayed, its id with `--numeric-uid-gid` or its truncated name.
+    pub fn user_string(&self, cache: &Cache, flags: &Flags) -> String {
+        if flags.numeric_uid_gid.0 {
+            self.user(cache, flags)
+        } else {
             truncate(
-                group,
+                &self.user_name(cache),
                 flags.truncate_owner.after,
                 flags.truncate_owner.marker.clone(),
-            ),
+            )
+        }
+    }
+
+    /// The group, its id with `--numeric-uid-gid`, so that its name is not looked up, or its name.
+    pub fn group(&self, cache: &Cache, flags: &Flags) -> String {
+        match flags.numeric_uid_gid.0 {
+            // the owners of the files are not numbers on Windows
+            #[cfg(unix)]
+            true => self.group.to_string(),
+            _ => self.group_name(cache),
+        }
+    }
+
+    /// The group as displayed, its id with `--numeric-uid-gid` or its truncated name.
+    pub fn group_string(&self, cache: &Cache, flags: &Flags) -> String {
+        if flags.numeric_uid_gid.0 {
+            self.group(cache, flags)
+        } else {
+            truncate(
+                &self.group_name(cache),
+                flags.truncate_owner.after,
+                flags.truncate_owner.marker.clone(),
+            )
+        }
+    }
+
+    pub fn render_user(
+        &self,
+        colors: &Colors,
+        cache: &Cache,
+        flags: &Flags,
+        alignment: Option<usize>,
+    ) -> ColoredString {
+        colors.colorize(
+            align_right(self.user_string(cache, flags), alignment),
+            &Elem::User,
+        )
+    }
+
+    pub fn render_group(
+        &self,
+        colors: &Colors,
+        cache: &Cache,
+        flags: &Flags,
+        alignment: Option<usize>,
+    ) -> ColoredString {
+        colors.colorize(
+            align_right(self.group_string(cache, flags), alignment),
             &Elem::Group,
         )
     }
@@ -135,3 +254,66 @@ mod test_truncate {
         assert_eq!("a…", truncate("ab", Some(1), Some("…".to_string())));
     }
 }
+
+#[cfg(test)]
+mod test_render {
+    use super::align_right;
+
+    #[test]
+    fn test_align_right() {
+        assert_eq!("root", align_right("root".to_string(), None));
+        assert_eq!("  root", align_right("root".to_string(), Some(6)));
+        assert_eq!("nobody", align_right("nobody".to_string(), Some(4)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_render_numeric_uid_gid() {
+        use super::{Cache, Owner};
+        use crate::color::{Colors, ThemeOption};
+        use crate::flags::{Flags, NumericUidGid};
+
+        let owner = Owner::new(4242, 4343);
+        let colors = Colors::new(ThemeOption::NoColor);
+        let flags = Flags {
+            numeric_uid_gid: NumericUidGid(true),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "4242",
+            owner
+                .render_user(&colors, &Cache::default(), &flags, None)
+                .to_string()
+        );
+        assert_eq!(
+            "  4343",
+            owner
+                .render_group(&colors, &Cache::default(), &flags, Some(6))
+                .to_string()
+        );
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test_cache {
+    use super::Cache;
+
+    #[test]
+    fn test_user_name_is_looked_up_once() {
+        let cache = Cache::default();
+        let uid = users::get_current_uid();
+
+        assert_eq!(cache.user_name(uid), cache.user_name(uid));
+        assert_eq!(1, cache.users.lock().unwrap().len());
+    }
+
+    #[test]
+    fn test_group_name_is_looked_up_once() {
+        let cache = Cache::default();
+        let gid = users::get_current_gid();
+
+        assert_eq!(cache.group_name(gid), cache.group_name(gid));
+        assert_eq!(1, cache.groups.lock().unwrap().len());
+    }
+}
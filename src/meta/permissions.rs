@@ -55,6 +55,7 @@ impl From<&Metadata> for Permissions {
 }
 
 impl Permissions {
+    #[cfg(not(windows))]
     fn bits_to_octal(r: bool, w: bool, x: bool) -> u8 {
         (r as u8) * 4 + (w as u8) * 2 + (x as u8)
     }
@@ -104,6 +105,10 @@ impl Permissions {
                 acc.push_str(&x.to_string());
                 acc
             }),
+            // The permissions emulated from the Windows ACLs have no meaningful numeric mode
+            #[cfg(windows)]
+            PermissionFlag::Octal => colors.colorize("----", &Elem::NoAccess).to_string(),
+            #[cfg(not(windows))]
             PermissionFlag::Octal => {
                 let octals = [
                     Self::bits_to_octal(self.setuid, self.setgid, self.sticky),
@@ -130,6 +135,19 @@ impl Permissions {
         ColoredString::new(Colors::default_style(), res)
     }
 
+    /// The numeric mode, e.g. `0o4755`, as shown in octal by [PermissionFlag::Octal].
+    #[cfg(not(windows))]
+    pub fn mode(&self) -> u32 {
+        [
+            Self::bits_to_octal(self.setuid, self.setgid, self.sticky),
+            Self::bits_to_octal(self.user_read, self.user_write, self.user_execute),
+            Self::bits_to_octal(self.group_read, self.group_write, self.group_execute),
+            Self::bits_to_octal(self.other_read, self.other_write, self.other_execute),
+        ]
+        .into_iter()
+        .fold(0, |acc, x| acc * 8 + x as u32)
+    }
+
     #[cfg(not(windows))]
     pub fn is_executable(&self) -> bool {
         self.user_execute || self.group_execute || self.other_execute
@@ -166,12 +184,26 @@ mod modes {
 mod test {
     use super::Flags;
     use super::{PermissionFlag, Permissions};
-    use crate::color::{Colors, ThemeOption};
+    use crate::color::{Colors, Elem, ThemeOption};
+    use crossterm::style::Attribute;
     use std::fs;
     use std::fs::File;
     use std::os::unix::fs::PermissionsExt;
     use tempfile::tempdir;
 
+    #[test]
+    fn permission_mode() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o4751))
+            .expect("unable to set permissions to file");
+        let meta = file_path.metadata().expect("failed to get meta");
+
+        assert_eq!(0o4751, Permissions::from(&meta).mode());
+    }
+
     #[test]
     fn permission_rwx() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -298,6 +330,28 @@ mod test {
         assert_eq!("1777", perms.render(&colors, &flags).content());
     }
 
+    #[test]
+    fn permission_octal_setuid() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // Create the file;
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o4755))
+            .expect("unable to set permissions to file");
+
+        let meta = file_path.metadata().expect("failed to get meta");
+
+        let colors = Colors::new(ThemeOption::NoColor);
+        let flags = Flags {
+            permission: PermissionFlag::Octal,
+            ..Default::default()
+        };
+        let perms = Permissions::from(&meta);
+
+        assert_eq!("4755", perms.render(&colors, &flags).content());
+    }
+
     #[test]
     fn permission_disable() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -318,4 +372,106 @@ mod test {
 
         assert_eq!("-", perms.render(&colors, &flags).content());
     }
+
+    #[test]
+    fn permission_rwx_colored_per_bit() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // Create the file;
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o4751))
+            .expect("unable to set permissions to file");
+        let meta = file_path.metadata().expect("failed to get meta");
+
+        let colors = Colors::new(ThemeOption::NoLscolors);
+        let perms = Permissions::from(&meta);
+
+        let expected: String = [
+            ('r', Elem::Read),
+            ('w', Elem::Write),
+            ('s', Elem::ExecSticky),
+            ('r', Elem::Read),
+            ('-', Elem::NoAccess),
+            ('x', Elem::Exec),
+            ('-', Elem::NoAccess),
+            ('-', Elem::NoAccess),
+            ('x', Elem::Exec),
+        ]
+        .iter()
+        .map(|(chr, elem)| colors.colorize(*chr, elem).to_string())
+        .collect();
+        assert_eq!(
+            expected,
+            perms.render(&colors, &Flags::default()).to_string()
+        );
+
+        let bits: Vec<String> = [
+            Elem::Read,
+            Elem::Write,
+            Elem::Exec,
+            Elem::ExecSticky,
+            Elem::NoAccess,
+        ]
+        .iter()
+        .map(|elem| colors.colorize('x', elem).to_string())
+        .collect();
+        for (i, bit) in bits.iter().enumerate() {
+            assert!(!bits[i + 1..].contains(bit), "{bit:?}");
+        }
+
+        // the permissions which are not granted are dimmed
+        let no_access = colors.colorize('-', &Elem::NoAccess);
+        assert!(no_access.style().attributes.has(Attribute::Dim));
+        let read = colors.colorize('r', &Elem::Read);
+        assert!(!read.style().attributes.has(Attribute::Dim));
+    }
+
+    #[test]
+    fn permission_rwx_no_color() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // Create the file;
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o4751))
+            .expect("unable to set permissions to file");
+        let meta = file_path.metadata().expect("failed to get meta");
+
+        let colors = Colors::new(ThemeOption::NoColor);
+        let perms = Permissions::from(&meta);
+
+        assert_eq!(
+            "rwsr-x--x",
+            perms.render(&colors, &Flags::default()).to_string()
+        );
+    }
+
+    #[test]
+    fn permission_rwx_special_bits() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let colors = Colors::new(ThemeOption::NoColor);
+
+        for (mode, expected) in [
+            (0o4755, "rwsr-xr-x"),
+            (0o2755, "rwxr-sr-x"),
+            (0o1755, "rwxr-xr-t"),
+            (0o4644, "rwSr--r--"),
+            (0o2644, "rw-r-Sr--"),
+            (0o1644, "rw-r--r-T"),
+        ] {
+            let file_path = tmp_dir.path().join(format!("{mode:o}"));
+            File::create(&file_path).expect("failed to create file");
+            fs::set_permissions(&file_path, fs::Permissions::from_mode(mode))
+                .expect("unable to set permissions to file");
+            let meta = file_path.metadata().expect("failed to get meta");
+
+            let perms = Permissions::from(&meta);
+            assert_eq!(
+                expected,
+                perms.render(&colors, &Flags::default()).content(),
+                "{mode:o}"
+            );
+        }
+    }
 }
@@ -15,6 +15,17 @@ pub enum PermissionsOrAttributes {
 }
 
 impl PermissionsOrAttributes {
+    /// The numeric mode of the unix permissions, [None] on Windows where they are not
+    /// applicable.
+    pub fn mode(&self) -> Option<u32> {
+        match self {
+            #[cfg(unix)]
+            PermissionsOrAttributes::Permissions(permissions) => Some(permissions.mode()),
+            #[cfg(windows)]
+            _ => None,
+        }
+    }
+
     pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         match self {
             PermissionsOrAttributes::Permissions(permissions) => permissions.render(colors, flags),
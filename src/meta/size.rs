@@ -1,13 +1,17 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{Flags, SizeFlag};
+use crate::flags::{Flags, SizeFlag, SizeUnit};
 use std::fs::Metadata;
 
+#[cfg(test)]
 const KB: u64 = 1024;
+#[cfg(test)]
 const MB: u64 = 1024_u64.pow(2);
+#[cfg(test)]
 const GB: u64 = 1024_u64.pow(3);
+#[cfg(test)]
 const TB: u64 = 1024_u64.pow(4);
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Unit {
     Byte,
     Kilo,
@@ -19,17 +23,26 @@ pub enum Unit {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Size {
     bytes: u64,
+    /// Whether the size is not the one of a regular file, like the size of a directory entry or
+    /// of a special file, which is painted in a neutral color rather than by its magnitude.
+    neutral: bool,
 }
 
 impl From<&Metadata> for Size {
     fn from(meta: &Metadata) -> Self {
-        Self { bytes: meta.len() }
+        Self {
+            bytes: meta.len(),
+            neutral: !meta.is_file(),
+        }
     }
 }
 
 impl Size {
     pub fn new(bytes: u64) -> Self {
-        Self { bytes }
+        Self {
+            bytes,
+            neutral: false,
+        }
     }
 
     pub fn get_bytes(&self) -> u64 {
@@ -41,19 +54,38 @@ impl Size {
     }
 
     fn get_unit(&self, flags: &Flags) -> Unit {
-        if flags.size == SizeFlag::Bytes {
+        if flags.size == SizeFlag::Bytes || flags.size_unit == SizeUnit::Bytes {
             return Unit::Byte;
         }
 
-        match self.bytes {
-            b if b < KB => Unit::Byte,
-            b if b < MB => Unit::Kilo,
-            b if b < GB => Unit::Mega,
-            b if b < TB => Unit::Giga,
+        let base = flags.size_unit.base();
+        let unit = match self.bytes {
+            b if b < base => Unit::Byte,
+            b if b < base.pow(2) => Unit::Kilo,
+            b if b < base.pow(3) => Unit::Mega,
+            b if b < base.pow(4) => Unit::Giga,
             _ => Unit::Tera,
+        };
+
+        // A size just below a power of the base would be rounded up to a full 1024 KiB (or
+        // 1000 kB), it is displayed as 1.0 of the next unit instead.
+        if Self::rounded(self.bytes, base, unit) < base as f64 {
+            return unit;
+        }
+        match unit {
+            Unit::Kilo => Unit::Mega,
+            Unit::Mega => Unit::Giga,
+            Unit::Giga => Unit::Tera,
+            unit => unit,
         }
     }
 
+    /// The number of `unit` in `bytes`, rounded to one decimal place.
+    fn rounded(bytes: u64, base: u64, unit: Unit) -> f64 {
+        let divisor = base.pow(unit as u32) as f64;
+        ((bytes as f64 / divisor) * 10.0).round() / 10.0
+    }
+
     pub fn render(
         &self,
         colors: &Colors,
@@ -87,17 +119,13 @@ impl Size {
     }
 
     fn paint(&self, colors: &Colors, content: String) -> ColoredString {
-        let bytes = self.get_bytes();
-
-        let elem = if bytes >= GB {
-            &Elem::FileLarge
-        } else if bytes >= MB {
-            &Elem::FileMedium
+        let elem = if self.neutral {
+            Elem::NonFile
         } else {
-            &Elem::FileSmall
+            colors.size_elem(self.bytes)
         };
 
-        colors.colorize(content, elem)
+        colors.colorize(content, &elem)
     }
 
     pub fn render_value(&self, colors: &Colors, flags: &Flags) -> ColoredString {
@@ -110,11 +138,8 @@ impl Size {
         let unit = self.get_unit(flags);
 
         match unit {
-            Unit::Byte => self.bytes.to_string(),
-            Unit::Kilo => self.format_size(((self.bytes as f64 / KB as f64) * 10.0).round() / 10.0),
-            Unit::Mega => self.format_size(((self.bytes as f64 / MB as f64) * 10.0).round() / 10.0),
-            Unit::Giga => self.format_size(((self.bytes as f64 / GB as f64) * 10.0).round() / 10.0),
-            Unit::Tera => self.format_size(((self.bytes as f64 / TB as f64) * 10.0).round() / 10.0),
+            Unit::Byte => flags.size_separator.format(self.bytes),
+            _ => self.format_size(Self::rounded(self.bytes, flags.size_unit.base(), unit)),
         }
     }
 
@@ -127,22 +152,36 @@ impl Size {
     pub fn unit_string(&self, flags: &Flags) -> String {
         let unit = self.get_unit(flags);
 
-        match flags.size {
-            SizeFlag::Default => match unit {
+        match (flags.size, flags.size_unit) {
+            (SizeFlag::Bytes, _) => String::from(""),
+            (SizeFlag::Default, SizeUnit::Si) => match unit {
                 Unit::Byte => String::from('B'),
-                Unit::Kilo => String::from("KB"),
+                Unit::Kilo => String::from("kB"),
                 Unit::Mega => String::from("MB"),
                 Unit::Giga => String::from("GB"),
                 Unit::Tera => String::from("TB"),
             },
-            SizeFlag::Short => match unit {
+            (SizeFlag::Default, _) => match unit {
+                Unit::Byte => String::from('B'),
+                Unit::Kilo => String::from("KiB"),
+                Unit::Mega => String::from("MiB"),
+                Unit::Giga => String::from("GiB"),
+                Unit::Tera => String::from("TiB"),
+            },
+            (SizeFlag::Short, SizeUnit::Si) => match unit {
+                Unit::Byte => String::from('B'),
+                Unit::Kilo => String::from('k'),
+                Unit::Mega => String::from('M'),
+                Unit::Giga => String::from('G'),
+                Unit::Tera => String::from('T'),
+            },
+            (SizeFlag::Short, _) => match unit {
                 Unit::Byte => String::from('B'),
                 Unit::Kilo => String::from('K'),
                 Unit::Mega => String::from('M'),
                 Unit::Giga => String::from('G'),
                 Unit::Tera => String::from('T'),
             },
-            SizeFlag::Bytes => String::from(""),
         }
     }
 }
@@ -151,7 +190,10 @@ impl Size {
 mod test {
     use super::{Size, GB, KB, MB, TB};
     use crate::color::{Colors, ThemeOption};
-    use crate::flags::{Flags, SizeFlag};
+    use crate::flags::{Flags, SizeFlag, SizeSeparator, SizeUnit};
+    use crate::theme::color::ColorTheme;
+    use std::fs;
+    use tempfile::tempdir;
 
     #[test]
     fn render_byte() {
@@ -173,7 +215,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "4.0");
-        assert_eq!(size.unit_string(&flags), "KB");
+        assert_eq!(size.unit_string(&flags), "KiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "K");
     }
@@ -184,7 +226,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "42");
-        assert_eq!(size.unit_string(&flags), "KB");
+        assert_eq!(size.unit_string(&flags), "KiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "K");
     }
@@ -195,7 +237,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "420");
-        assert_eq!(size.unit_string(&flags), "KB");
+        assert_eq!(size.unit_string(&flags), "KiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "K");
     }
@@ -206,7 +248,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "4.0");
-        assert_eq!(size.unit_string(&flags), "MB");
+        assert_eq!(size.unit_string(&flags), "MiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "M");
     }
@@ -217,7 +259,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "42");
-        assert_eq!(size.unit_string(&flags), "MB");
+        assert_eq!(size.unit_string(&flags), "MiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "M");
     }
@@ -228,7 +270,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "420");
-        assert_eq!(size.unit_string(&flags), "MB");
+        assert_eq!(size.unit_string(&flags), "MiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "M");
     }
@@ -239,7 +281,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "4.0");
-        assert_eq!(size.unit_string(&flags), "GB");
+        assert_eq!(size.unit_string(&flags), "GiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "G");
     }
@@ -250,7 +292,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "42");
-        assert_eq!(size.unit_string(&flags), "GB");
+        assert_eq!(size.unit_string(&flags), "GiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "G");
     }
@@ -261,7 +303,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "420");
-        assert_eq!(size.unit_string(&flags), "GB");
+        assert_eq!(size.unit_string(&flags), "GiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "G");
     }
@@ -272,7 +314,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "4.0");
-        assert_eq!(size.unit_string(&flags), "TB");
+        assert_eq!(size.unit_string(&flags), "TiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "T");
     }
@@ -283,7 +325,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "42");
-        assert_eq!(size.unit_string(&flags), "TB");
+        assert_eq!(size.unit_string(&flags), "TiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "T");
     }
@@ -294,7 +336,7 @@ mod test {
         let mut flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "420");
-        assert_eq!(size.unit_string(&flags), "TB");
+        assert_eq!(size.unit_string(&flags), "TiB");
         flags.size = SizeFlag::Short;
         assert_eq!(size.unit_string(&flags), "T");
     }
@@ -305,7 +347,7 @@ mod test {
         let flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "42");
-        assert_eq!(size.unit_string(&flags), "KB");
+        assert_eq!(size.unit_string(&flags), "KiB");
     }
 
     #[test]
@@ -314,7 +356,94 @@ mod test {
         let flags = Flags::default();
 
         assert_eq!(size.value_string(&flags), "42");
-        assert_eq!(size.unit_string(&flags), "KB");
+        assert_eq!(size.unit_string(&flags), "KiB");
+    }
+
+    #[test]
+    fn render_binary_boundaries() {
+        let flags = Flags::default();
+
+        let size = Size::new(KB - 1);
+        assert_eq!(size.value_string(&flags), "1023");
+        assert_eq!(size.unit_string(&flags), "B");
+
+        let size = Size::new(KB);
+        assert_eq!(size.value_string(&flags), "1.0");
+        assert_eq!(size.unit_string(&flags), "KiB");
+
+        // 1023.99 KiB would be rounded to 1024 KiB
+        let size = Size::new(MB - 10);
+        assert_eq!(size.value_string(&flags), "1.0");
+        assert_eq!(size.unit_string(&flags), "MiB");
+    }
+
+    #[test]
+    fn render_si() {
+        let mut flags = Flags {
+            size_unit: SizeUnit::Si,
+            ..Default::default()
+        };
+
+        let size = Size::new(999);
+        assert_eq!(size.value_string(&flags), "999");
+        assert_eq!(size.unit_string(&flags), "B");
+
+        let size = Size::new(1000);
+        assert_eq!(size.value_string(&flags), "1.0");
+        assert_eq!(size.unit_string(&flags), "kB");
+
+        let size = Size::new(KB); // 1.024 kilobytes
+        assert_eq!(size.value_string(&flags), "1.0");
+        assert_eq!(size.unit_string(&flags), "kB");
+
+        let size = Size::new(42_000_000);
+        assert_eq!(size.value_string(&flags), "42");
+        assert_eq!(size.unit_string(&flags), "MB");
+
+        let size = Size::new(999_960);
+        assert_eq!(size.value_string(&flags), "1.0");
+        assert_eq!(size.unit_string(&flags), "MB");
+
+        flags.size = SizeFlag::Short;
+        assert_eq!(size.unit_string(&flags), "M");
+        let size = Size::new(4_200);
+        assert_eq!(size.unit_string(&flags), "k");
+    }
+
+    #[test]
+    fn render_bytes_unit() {
+        let size = Size::new(42 * MB);
+        let flags = Flags {
+            size_unit: SizeUnit::Bytes,
+            ..Default::default()
+        };
+
+        assert_eq!(size.value_string(&flags), (42 * MB).to_string());
+        assert_eq!(size.unit_string(&flags), "B");
+    }
+
+    #[test]
+    fn render_bytes_with_separator() {
+        let flags = Flags {
+            size: SizeFlag::Bytes,
+            size_separator: SizeSeparator::Comma,
+            ..Default::default()
+        };
+        let colors = Colors::new(ThemeOption::NoColor);
+
+        let size = Size::new(MB);
+        assert_eq!(size.value_string(&flags), "1,048,576");
+        assert_eq!(
+            size.render(&colors, &flags, Some(11)).to_string(),
+            "  1,048,576 "
+        );
+
+        let size = Size::new(0);
+        assert_eq!(size.value_string(&flags), "0");
+        assert_eq!(
+            size.render(&colors, &flags, Some(9)).to_string(),
+            "        0 "
+        );
     }
 
     #[test]
@@ -329,4 +458,31 @@ mod test {
         assert_eq!(size.render(&colors, &flags, Some(2)).to_string(), "42K");
         assert_eq!(size.render(&colors, &flags, Some(3)).to_string(), " 42K");
     }
+
+    #[test]
+    fn render_neutral_color_for_directories() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        fs::write(&file_path, "content").unwrap();
+        let flags = Flags::default();
+
+        let colors = Colors::new(ThemeOption::Default);
+        let theme = ColorTheme::default_dark();
+        let file = Size::from(&fs::metadata(&file_path).unwrap());
+        assert_eq!(
+            Some(theme.size.small),
+            file.render_value(&colors, &flags).style().foreground_color
+        );
+        let dir = Size::from(&fs::metadata(tmp_dir.path()).unwrap());
+        assert_eq!(
+            Some(theme.size.none),
+            dir.render_value(&colors, &flags).style().foreground_color
+        );
+
+        let colors = Colors::new(ThemeOption::NoColor);
+        assert_eq!(
+            None,
+            dir.render_value(&colors, &flags).style().foreground_color
+        );
+    }
 }
@@ -1,28 +1,33 @@
 use crate::color::{ColoredString, Colors, Elem};
 use crate::flags::Flags;
-use std::fs::read_link;
+use std::fs::{canonicalize, read_link};
 use std::path::Path;
 
-#[derive(Clone, Debug)]
+/// The target of a symlink, empty for the other entries.
+#[derive(Clone, Debug, Default)]
 pub struct SymLink {
     target: Option<String>,
+    destination: Option<String>,
     valid: bool,
 }
 
 impl From<&Path> for SymLink {
     fn from(path: &Path) -> Self {
         if let Ok(target) = read_link(path) {
-            if target.is_absolute() || path.parent().is_none() {
-                return Self {
-                    valid: target.exists(),
-                    target: Some(
-                        target
-                            .to_str()
-                            .expect("failed to convert symlink to str")
-                            .to_string(),
-                    ),
-                };
-            }
+            let destination = match path.parent() {
+                Some(parent) if !target.is_absolute() => canonicalize(parent)
+                    .unwrap_or(parent.to_path_buf())
+                    .join(&target),
+                _ => target.clone(),
+            };
+            let valid = destination.exists();
+            // The links to links and the `..` are resolved down to the final path, which a
+            // broken link does not have
+            let destination = if valid {
+                canonicalize(&destination).unwrap_or(destination)
+            } else {
+                destination
+            };
 
             return Self {
                 target: Some(
@@ -31,12 +36,14 @@ impl From<&Path> for SymLink {
                         .expect("failed to convert symlink to str")
                         .to_string(),
                 ),
-                valid: path.parent().unwrap().join(target).exists(),
+                destination: destination.to_str().map(|d| d.to_string()),
+                valid,
             };
         }
 
         Self {
             target: None,
+            destination: None,
             valid: false,
         }
     }
@@ -47,6 +54,17 @@ impl SymLink {
         self.target.as_ref().map(|target| target.to_string())
     }
 
+    /// Whether this is a symlink whose target does not exist.
+    pub fn is_broken(&self) -> bool {
+        self.target.is_some() && !self.valid
+    }
+
+    /// The path the link points to, resolved down to the final entry when the target exists,
+    /// or only against the link's directory for a broken link.
+    pub fn destination(&self) -> Option<&str> {
+        self.destination.as_deref()
+    }
+
     pub fn render(&self, colors: &Colors, flag: &Flags) -> ColoredString {
         if let Some(target_string) = self.symlink_string() {
             let elem = if self.valid {
@@ -56,7 +74,7 @@ impl SymLink {
             };
 
             let strings: &[ColoredString] = &[
-                ColoredString::new(Colors::default_style(), format!(" {} ", flag.symlink_arrow)), // ⇒ \u{21d2}
+                colors.colorize(format!(" {} ", flag.symlink_arrow), &Elem::SymLinkArrow), // ⇒ \u{21d2}
                 colors.colorize(target_string, elem),
             ];
 
@@ -87,6 +105,7 @@ mod tests {
     fn test_symlink_render_default_valid_target_nocolor() {
         let link = SymLink {
             target: Some("/target".to_string()),
+            destination: Some("/target".to_string()),
             valid: true,
         };
         let argv = ["lsd"];
@@ -105,6 +124,7 @@ mod tests {
     fn test_symlink_render_default_invalid_target_nocolor() {
         let link = SymLink {
             target: Some("/target".to_string()),
+            destination: Some("/target".to_string()),
             valid: false,
         };
         let argv = ["lsd"];
@@ -123,12 +143,38 @@ mod tests {
     fn test_symlink_render_default_invalid_target_withcolor() {
         let link = SymLink {
             target: Some("/target".to_string()),
+            destination: Some("/target".to_string()),
             valid: false,
         };
         let argv = ["lsd"];
         let cli = Cli::try_parse_from(argv).unwrap();
         assert_eq!(
-            format!("{}", " ⇒ \u{1b}[38;5;124m/target\u{1b}[39m"),
+            format!(
+                "{}",
+                "\u{1b}[38;5;245m ⇒ \u{1b}[39m\u{1b}[38;5;124m/target\u{1b}[39m"
+            ),
+            link.render(
+                &Colors::new(ThemeOption::NoLscolors),
+                &Flags::configure_from(&cli, &Config::with_none()).unwrap()
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_symlink_render_default_valid_target_withcolor() {
+        let link = SymLink {
+            target: Some("/target".to_string()),
+            destination: Some("/target".to_string()),
+            valid: true,
+        };
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            format!(
+                "{}",
+                "\u{1b}[38;5;245m ⇒ \u{1b}[39m\u{1b}[38;5;44m/target\u{1b}[39m"
+            ),
             link.render(
                 &Colors::new(ThemeOption::NoLscolors),
                 &Flags::configure_from(&cli, &Config::with_none()).unwrap()
@@ -136,4 +182,23 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    fn test_symlink_render_without_symlink_color() {
+        let link = SymLink {
+            target: Some("/target".to_string()),
+            destination: Some("/target".to_string()),
+            valid: false,
+        };
+        let argv = ["lsd", "--no-symlink-color"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            format!("{}", " ⇒ /target"),
+            link.render(
+                &Colors::new(ThemeOption::NoLscolors).without_symlink_color(),
+                &Flags::configure_from(&cli, &Config::with_none()).unwrap()
+            )
+            .to_string()
+        );
+    }
 }
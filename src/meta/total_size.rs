@@ -0,0 +1,171 @@
+use crate::print_error;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+/// The maximum number of threads reading the directories of a walk.
+const MAX_THREADS: usize = 8;
+
+/// The state of a walk, shared by its threads.
+#[derive(Default)]
+struct Walk {
+    /// The directories left to read.
+    queue: Vec<PathBuf>,
+    /// The number of directories queued or being read.
+    pending: usize,
+    size: u64,
+    /// The first error met, reported once the walk is done.
+    first_error: Option<String>,
+    error_count: usize,
+}
+
+/// Sum the size of a file, or of a directory and all its descendants.
+///
+/// The symlinks are not followed, so they can not make the walk loop. The entries which can not
+/// be read are skipped, and a single warning is printed for the whole walk.
+pub fn total_size(path: &Path) -> u64 {
+    let metadata = match path.symlink_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            print_error!("{}: {}.", path.display(), err);
+            return 0;
+        }
+    };
+    if metadata.is_file() {
+        return metadata.len();
+    } else if !metadata.is_dir() {
+        return 0;
+    }
+
+    let walk = Mutex::new(Walk {
+        queue: vec![path.to_path_buf()],
+        pending: 1,
+        size: metadata.len(),
+        ..Walk::default()
+    });
+    let wakeup = Condvar::new();
+
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(MAX_THREADS);
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| walk_queue(&walk, &wakeup));
+        }
+    });
+
+    let walk = walk.into_inner().unwrap();
+    if let Some(first_error) = walk.first_error {
+        match walk.error_count {
+            1 => print_error!("{}.", first_error),
+            count => print_error!(
+                "{}, and {} other entries could not be read.",
+                first_error,
+                count - 1
+            ),
+        }
+    }
+    walk.size
+}
+
+/// Read the queued directories until none is left, queueing their subdirectories.
+fn walk_queue(walk: &Mutex<Walk>, wakeup: &Condvar) {
+    loop {
+        let dir = {
+            let mut walk = walk.lock().unwrap();
+            loop {
+                if let Some(dir) = walk.queue.pop() {
+                    break dir;
+                }
+                if walk.pending == 0 {
+                    return;
+                }
+                walk = wakeup.wait(walk).unwrap();
+            }
+        };
+
+        let (size, subdirs, errors) = read_dir(&dir);
+
+        let mut walk = walk.lock().unwrap();
+        walk.size += size;
+        walk.pending += subdirs.len();
+        walk.pending -= 1;
+        walk.queue.extend(subdirs);
+        walk.error_count += errors.len();
+        if walk.first_error.is_none() {
+            walk.first_error = errors.into_iter().next();
+        }
+        wakeup.notify_all();
+    }
+}
+
+/// Sum the size of the entries of a directory, returning its subdirectories and the errors met.
+fn read_dir(dir: &Path) -> (u64, Vec<PathBuf>, Vec<String>) {
+    let mut size = 0;
+    let mut subdirs = Vec::new();
+    let mut errors = Vec::new();
+
+    let entries = match dir.read_dir() {
+        Ok(entries) => entries,
+        Err(err) => return (0, subdirs, vec![format!("{}: {}", dir.display(), err)]),
+    };
+    for entry in entries {
+        // the metadata of an entry does not follow the symlinks
+        match entry.and_then(|entry| Ok((entry.path(), entry.metadata()?))) {
+            Ok((path, metadata)) => {
+                if metadata.is_dir() {
+                    size += metadata.len();
+                    subdirs.push(path);
+                } else if metadata.is_file() {
+                    size += metadata.len();
+                }
+            }
+            Err(err) => errors.push(format!("{}: {}", dir.display(), err)),
+        }
+    }
+
+    (size, subdirs, errors)
+}
+
+#[cfg(test)]
+mod test {
+    use super::total_size;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_total_size_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        fs::write(&file_path, "1234").unwrap();
+
+        assert_eq!(4, total_size(&file_path));
+    }
+
+    #[test]
+    fn test_total_size_nested_directories() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let nested = tmp_dir.path().join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(tmp_dir.path().join("file"), "1234").unwrap();
+        fs::write(nested.join("file"), "12345678").unwrap();
+
+        let dirs_size: u64 = [tmp_dir.path(), &tmp_dir.path().join("a"), &nested]
+            .iter()
+            .map(|dir| dir.symlink_metadata().unwrap().len())
+            .sum();
+        assert_eq!(dirs_size + 12, total_size(tmp_dir.path()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_total_size_symlink_loop() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        std::os::unix::fs::symlink(tmp_dir.path(), tmp_dir.path().join("loop")).unwrap();
+        fs::write(tmp_dir.path().join("file"), "1234").unwrap();
+
+        let dir_size = tmp_dir.path().symlink_metadata().unwrap().len();
+        assert_eq!(dir_size + 4, total_size(tmp_dir.path()));
+    }
+}
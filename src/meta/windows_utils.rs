@@ -325,6 +325,52 @@ fn has_path_attribute(
 /// [`Path`]
 ///
 /// [`hidden`]: windows::Win32::Storage::FileSystem::FILE_ATTRIBUTE_HIDDEN
+/// Get the file index of a path, which uniquely identifies a file on its volume like an inode
+/// number, or [None] if the file cannot be opened.
+pub fn get_file_index(path: &Path) -> Option<u64> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION, FILE_FLAG_BACKUP_SEMANTICS,
+    };
+
+    // Opening a directory requires the backup semantics, no access is needed to get the index
+    let file = std::fs::OpenOptions::new()
+        .access_mode(0)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS.0)
+        .open(path)
+        .ok()?;
+
+    let mut info = BY_HANDLE_FILE_INFORMATION::default();
+    let success =
+        unsafe { GetFileInformationByHandle(HANDLE(file.as_raw_handle() as isize), &mut info) };
+
+    success
+        .as_bool()
+        .then(|| (u64::from(info.nFileIndexHigh) << 32) | u64::from(info.nFileIndexLow))
+}
+
+/// Get the size a path takes on its volume, which is less than its size when it is compressed or
+/// sparse, or [None] if it cannot be queried.
+pub fn get_compressed_size(path: &Path) -> Option<u64> {
+    use windows::Win32::Storage::FileSystem::GetCompressedFileSizeW;
+
+    let windows_path = buf_from_os(path.as_os_str());
+    let mut high = 0;
+    let low = unsafe {
+        GetCompressedFileSizeW(
+            windows::core::PCWSTR(windows_path.as_ptr()),
+            Some(&mut high),
+        )
+    };
+    // the low part is all ones on a failure, which is only told apart by the last error
+    if low == u32::MAX && io::Error::last_os_error().raw_os_error() != Some(0) {
+        return None;
+    }
+    Some((u64::from(high) << 32) | u64::from(low))
+}
+
 pub fn is_path_hidden(path: &Path) -> bool {
     has_path_attribute(
         path,
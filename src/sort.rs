@@ -1,35 +1,295 @@
-use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder};
-use crate::meta::Meta;
+//! This module defines how the listing is sorted, see [assemble_sorters] to get the [Sorter]
+//! configured by the [Flags].
+//!
+//! The directory grouping never depends on the sort order, which only reverses the value stages:
+//!
+//! | dir grouping | sort order | result                                              |
+//! |--------------|------------|-----------------------------------------------------|
+//! | none         | default    | every entry sorted by the column                    |
+//! | none         | reverse    | every entry sorted by the reversed column           |
+//! | first        | default    | directories then files, each sorted by the column   |
+//! | first        | reverse    | directories then files, each sorted by the reversed |
+//! | last         | default    | files then directories, each sorted by the column   |
+//! | last         | reverse    | files then directories, each sorted by the reversed |
+//!
+//! The entries which are equal on the sorted column are listed by name. Reversing the sort also
+//! reverses this name order, unless [ReverseAll] is unset to keep it.
+
+use crate::flags::{
+    CaseSensitivity, Collation, DirGrouping, Flags, HiddenGrouping, ReverseAll, SortColumn,
+    SortOrder, SymlinkGrouping,
+};
+use crate::git::GitStatus;
+use crate::meta::{Date, FileType, GitFileStatus, Meta, OwnerCache};
+use crate::print_error;
+use once_cell::sync::Lazy;
+use std::borrow::Cow;
 use std::cmp::Ordering;
-use vsort::compare;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 pub type SortFn = fn(&Meta, &Meta) -> Ordering;
 
-pub fn assemble_sorters(flags: &Flags) -> Vec<(SortOrder, SortFn)> {
-    let mut sorters: Vec<(SortOrder, SortFn)> = vec![];
-    match flags.sorting.dir_grouping {
-        DirGrouping::First => {
-            sorters.push((SortOrder::Default, with_dirs_first));
+static CUSTOM_SORTERS: Lazy<Mutex<HashMap<String, SortFn>>> = Lazy::new(Default::default);
+
+/// A chain of sort stages used to order a listing.
+///
+/// Two metas are compared by the following stages, the first one which does not consider them
+/// equal deciding of their order:
+/// 1. the `.` and `..` entries, always first,
+/// 2. the directory grouping, with its own [SortOrder] independent of the value stages,
+/// 3. the symlinks grouping, with its own [SortOrder] too,
+/// 4. the hidden files grouping, with its own [SortOrder] too,
+/// 5. the value stages for directories, if they are set on a sorter grouping directories and
+///    both metas are directories,
+/// 6. the value stages, in the order they were added.
+#[derive(Clone, Debug, Default)]
+pub struct Sorter {
+    dir_grouping: Option<SortOrder>,
+    symlink_grouping: Option<SortOrder>,
+    hidden_grouping: Option<SortOrder>,
+    dir_sorters: Option<Vec<(SortOrder, SortFn)>>,
+    sorters: Vec<(SortOrder, SortFn)>,
+}
+
+impl Sorter {
+    /// Create a sorter applying the given value stages to every meta.
+    pub fn new(sorters: Vec<(SortOrder, SortFn)>) -> Self {
+        Self {
+            sorters,
+            ..Default::default()
+        }
+    }
+
+    /// Group the directories first, or last with [SortOrder::Reverse].
+    pub fn with_dir_grouping(mut self, order: SortOrder) -> Self {
+        self.dir_grouping = Some(order);
+        self
+    }
+
+    /// Group the symlinks first, or last with [SortOrder::Reverse].
+    pub fn with_symlink_grouping(mut self, order: SortOrder) -> Self {
+        self.symlink_grouping = Some(order);
+        self
+    }
+
+    /// Group the hidden files first, or last with [SortOrder::Reverse].
+    pub fn with_hidden_grouping(mut self, order: SortOrder) -> Self {
+        self.hidden_grouping = Some(order);
+        self
+    }
+
+    /// Whether the sorter considers every pair of metas equal, so that sorting can be skipped.
+    pub fn is_noop(&self) -> bool {
+        self.dir_grouping.is_none()
+            && self.symlink_grouping.is_none()
+            && self.hidden_grouping.is_none()
+            && self.sorters.is_empty()
+    }
+
+    /// Use different value stages to compare directories with each other.
+    ///
+    /// These are only used when the directories are grouped, so that directories and files
+    /// never get compared with different stages.
+    pub fn with_dir_sorters(mut self, sorters: Vec<(SortOrder, SortFn)>) -> Self {
+        self.dir_sorters = Some(sorters);
+        self
+    }
+}
+
+/// Stages replacing the ones configured by the [Flags], for the parts of the output needing a
+/// specific order whatever the sort options.
+#[derive(Clone, Debug, Default)]
+pub struct SortOverride {
+    pub sorters: Option<Vec<(SortOrder, SortFn)>>,
+    pub dir_grouping: Option<DirGrouping>,
+}
+
+impl SortOverride {
+    /// The order of the children in a tree: directories first, then a natural sort of the names
+    /// ignoring their leading dot. Only the sort order is kept from the flags, unless the
+    /// directories are sorted among the files, which keeps the sort options.
+    pub fn tree(flags: &Flags) -> Self {
+        if flags.sorting.dirs_among_files.0 {
+            return Self::default();
         }
-        DirGrouping::Last => {
-            sorters.push((SortOrder::Reverse, with_dirs_first));
+        Self {
+            sorters: Some(vec![(flags.sorting.order, by_natural_name)]),
+            dir_grouping: Some(DirGrouping::First),
         }
-        DirGrouping::None => {}
+    }
+}
+
+pub fn assemble_sorters(flags: &Flags) -> Sorter {
+    assemble_sorters_with(flags, &SortOverride::default())
+}
+
+/// Assemble the sorter configured by the flags, with the stages set in `overrides` replacing the
+/// configured ones.
+pub fn assemble_sorters_with(flags: &Flags, overrides: &SortOverride) -> Sorter {
+    let mut sorters: Vec<(SortOrder, SortFn)> = if let Some(sorters) = &overrides.sorters {
+        sorters.clone()
+    } else if flags.sorting.keys.0.is_empty() {
+        match column_sorter(&flags.sorting.column, flags) {
+            Some(sorter) => vec![(flags.sorting.order, sorter)],
+            // Keep the directory order, without even grouping the directories
+            None => return Sorter::default(),
+        }
+    } else {
+        flags
+            .sorting
+            .keys
+            .0
+            .iter()
+            .filter_map(|(column, order)| {
+                let order = match (flags.sorting.order, order) {
+                    (SortOrder::Default, order) => *order,
+                    (SortOrder::Reverse, SortOrder::Default) => SortOrder::Reverse,
+                    (SortOrder::Reverse, SortOrder::Reverse) => SortOrder::Default,
+                };
+                column_sorter(column, flags).map(|sorter| (order, sorter))
+            })
+            .collect()
     };
+    if overrides.sorters.is_none() {
+        // The entries which are equal on the sorted columns are listed by name
+        let order = match flags.sorting.reverse_all {
+            ReverseAll(true) => flags.sorting.order,
+            ReverseAll(false) => SortOrder::Default,
+        };
+        sorters.push((order, by_name));
+    }
 
-    match flags.sorting.column {
-        SortColumn::Name => sorters.push((flags.sorting.order, by_name)),
-        SortColumn::Size => sorters.push((flags.sorting.order, by_size)),
-        SortColumn::Time => sorters.push((flags.sorting.order, by_date)),
-        SortColumn::Version => sorters.push((flags.sorting.order, by_version)),
-        SortColumn::Extension => sorters.push((flags.sorting.order, by_extension)),
-        SortColumn::GitStatus => sorters.push((flags.sorting.order, by_git_status)),
-        SortColumn::None => {}
+    let sorter = Sorter::new(sorters);
+    let dir_grouping = if flags.sorting.dirs_among_files.0 {
+        DirGrouping::None
+    } else {
+        overrides.dir_grouping.unwrap_or(flags.sorting.dir_grouping)
+    };
+    let sorter = match dir_grouping {
+        DirGrouping::First => group_directories_first(sorter),
+        DirGrouping::Last => sorter.with_dir_grouping(SortOrder::Reverse),
+        DirGrouping::None => sorter,
+    };
+    let sorter = match flags.sorting.symlink_grouping {
+        SymlinkGrouping::First => sorter.with_symlink_grouping(SortOrder::Default),
+        SymlinkGrouping::Last => sorter.with_symlink_grouping(SortOrder::Reverse),
+        SymlinkGrouping::None => sorter,
+    };
+    match flags.sorting.hidden_grouping {
+        HiddenGrouping::First => sorter.with_hidden_grouping(SortOrder::Default),
+        HiddenGrouping::Last => sorter.with_hidden_grouping(SortOrder::Reverse),
+        HiddenGrouping::None => sorter,
     }
-    sorters
 }
 
-pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering {
+/// Register a comparator to sort by with `--sort custom:<name>`, replacing the one previously
+/// registered with the same name.
+///
+/// The comparator only has to order the entries by its own criteria: the directories grouping
+/// and the name tie-break are applied by the [Sorter] like for the built-in columns.
+pub fn register_sorter(name: &str, sorter: SortFn) {
+    CUSTOM_SORTERS
+        .lock()
+        .unwrap()
+        .insert(name.to_owned(), sorter);
+}
+
+/// Get the comparator of a column, [None] for [SortColumn::None].
+fn column_sorter(column: &SortColumn, flags: &Flags) -> Option<SortFn> {
+    let sorter: SortFn = match column {
+        SortColumn::Name => match (flags.sorting.collation, flags.sorting.case_sensitivity) {
+            (Collation::Bytewise, CaseSensitivity::Insensitive) => by_name,
+            (Collation::Bytewise, CaseSensitivity::Sensitive) => by_name_case_sensitive,
+            (Collation::Unicode, CaseSensitivity::Insensitive) => by_name_collated,
+            (Collation::Unicode, CaseSensitivity::Sensitive) => by_name_collated_case_sensitive,
+        },
+        SortColumn::Size => size_cmp,
+        SortColumn::Time => date_cmp,
+        SortColumn::Version => by_version,
+        SortColumn::Extension => by_extension,
+        SortColumn::GitStatus => by_git_status,
+        SortColumn::Entries => by_entries,
+        SortColumn::Inode => by_inode,
+        SortColumn::Target => by_target,
+        SortColumn::Permission => by_permission,
+        // the owners of the files are not numbers on Windows
+        #[cfg(unix)]
+        SortColumn::Owner if flags.numeric_uid_gid.0 => by_owner_id,
+        SortColumn::Owner => by_owner,
+        SortColumn::Custom(name) => match CUSTOM_SORTERS.lock().unwrap().get(name) {
+            Some(sorter) => *sorter,
+            None => {
+                print_error!("No sorter registered as '{}', sorting by name.", name);
+                by_name
+            }
+        },
+        SortColumn::None => return None,
+    };
+    Some(sorter)
+}
+
+/// Wrap a sorter so that directories are listed before the files, whatever its value stages and
+/// their order are.
+pub fn group_directories_first(sorter: Sorter) -> Sorter {
+    sorter.with_dir_grouping(SortOrder::Default)
+}
+
+pub fn by_meta(sorter: &Sorter, a: &Meta, b: &Meta) -> Ordering {
+    let mut sorters = &sorter.sorters;
+
+    match with_dot_entries_first(a, b) {
+        Ordering::Equal => {}
+        ordering => return ordering,
+    }
+
+    if let Some(direction) = sorter.dir_grouping {
+        match by_sorters(&[(direction, with_dirs_first)], a, b) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+    }
+
+    if let Some(direction) = sorter.symlink_grouping {
+        match by_sorters(&[(direction, with_symlinks_first)], a, b) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+    }
+
+    if let Some(direction) = sorter.hidden_grouping {
+        match by_sorters(&[(direction, with_hidden_first)], a, b) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+    }
+
+    if sorter.dir_grouping.is_some() {
+        if let Some(dir_sorters) = &sorter.dir_sorters {
+            if a.file_type.is_dirlike() && b.file_type.is_dirlike() {
+                sorters = dir_sorters;
+            }
+        }
+    }
+
+    // Whatever the sort order, a broken symlink comes after a valid entry it is equal to
+    by_sorters(sorters, a, b).then(a.symlink.is_broken().cmp(&b.symlink.is_broken()))
+}
+
+/// The `.` then `..` entries of `--all` come first, whatever the sort and the groupings.
+fn with_dot_entries_first(a: &Meta, b: &Meta) -> Ordering {
+    let rank = |meta: &Meta| match (meta.dot_entry, meta.name.name.as_str()) {
+        (true, ".") => 0,
+        (true, _) => 1,
+        (false, _) => 2,
+    };
+    rank(a).cmp(&rank(b))
+}
+
+fn by_sorters(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering {
     for (direction, sorter) in sorters.iter() {
         match (sorter)(a, b) {
             Ordering::Equal => continue,
@@ -44,48 +304,332 @@ pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering
     Ordering::Equal
 }
 
-fn with_dirs_first(a: &Meta, b: &Meta) -> Ordering {
+/// Put the directories before the other files, and consider two directories or two files equal.
+///
+/// Symlinks pointing to a directory are grouped with the directories, whatever the sort order.
+pub fn with_dirs_first(a: &Meta, b: &Meta) -> Ordering {
     b.file_type.is_dirlike().cmp(&a.file_type.is_dirlike())
 }
 
-fn by_size(a: &Meta, b: &Meta) -> Ordering {
-    match (&a.size, &b.size) {
-        (Some(a_size), Some(b_size)) => b_size.get_bytes().cmp(&a_size.get_bytes()),
+fn with_symlinks_first(a: &Meta, b: &Meta) -> Ordering {
+    let is_symlink = |meta: &Meta| matches!(meta.file_type, FileType::SymLink { .. });
+    is_symlink(b).cmp(&is_symlink(a))
+}
+
+fn with_hidden_first(a: &Meta, b: &Meta) -> Ordering {
+    let is_hidden = |meta: &Meta| meta.name.name.starts_with('.');
+    is_hidden(b).cmp(&is_hidden(a))
+}
+
+/// Sort by size, the largest first, after the entries without a size. Broken symlinks are
+/// considered empty.
+///
+/// Entries of the same size are sorted by [by_name].
+pub fn by_size(a: &Meta, b: &Meta) -> Ordering {
+    size_cmp(a, b).then_with(|| by_name(a, b))
+}
+
+fn size_cmp(a: &Meta, b: &Meta) -> Ordering {
+    let bytes = |meta: &Meta| match meta.symlink.is_broken() {
+        true => Some(0),
+        false => meta.size.as_ref().map(|size| size.get_bytes()),
+    };
+    match (bytes(a), bytes(b)) {
+        (Some(a_bytes), Some(b_bytes)) => b_bytes.cmp(&a_bytes),
         (Some(_), None) => Ordering::Greater,
         (None, Some(_)) => Ordering::Less,
         (None, None) => Ordering::Equal,
     }
 }
 
-fn by_name(a: &Meta, b: &Meta) -> Ordering {
-    a.name.cmp(&b.name)
+/// Sort by name, case-insensitively.
+///
+/// Names only differing by their case are then compared case-sensitively, so that the ordering
+/// is total and `Foo` always comes before `foo`.
+pub fn by_name(a: &Meta, b: &Meta) -> Ordering {
+    a.name.cmp(&b.name).then(a.name.name.cmp(&b.name.name))
+}
+
+fn by_name_case_sensitive(a: &Meta, b: &Meta) -> Ordering {
+    a.name.name.cmp(&b.name.name)
+}
+
+fn by_name_collated(a: &Meta, b: &Meta) -> Ordering {
+    name_key(a, false)
+        .cmp(&name_key(b, false))
+        .then_with(|| by_name(a, b))
+}
+
+fn by_name_collated_case_sensitive(a: &Meta, b: &Meta) -> Ordering {
+    name_key(a, true)
+        .cmp(&name_key(b, true))
+        .then_with(|| by_name_case_sensitive(a, b))
+}
+
+/// Get the collation key cached in the meta, or build it when it is missing.
+fn name_key(meta: &Meta, case_sensitive: bool) -> Cow<'_, str> {
+    match &meta.name_key {
+        Some(key) => Cow::Borrowed(key),
+        None => Cow::Owned(collation_key(&meta.name.name, case_sensitive)),
+    }
+}
+
+/// Compute once, before sorting, the keys which are too costly to be built on every comparison.
+///
+/// Only the collation keys of the names are concerned, the other comparators reading values
+/// already stored in the [Meta].
+pub fn cache_sort_keys(flags: &Flags, metas: &mut [Meta]) {
+    if flags.sorting.collation != Collation::Unicode || !flags.sorting.sorts_by(SortColumn::Name) {
+        return;
+    }
+
+    let case_sensitive = flags.sorting.case_sensitivity == CaseSensitivity::Sensitive;
+    for meta in metas {
+        meta.name_key = Some(collation_key(&meta.name.name, case_sensitive));
+        if let Some(content) = &mut meta.content {
+            cache_sort_keys(flags, content);
+        }
+    }
 }
 
-fn by_date(a: &Meta, b: &Meta) -> Ordering {
-    b.date.cmp(&a.date).then(a.name.cmp(&b.name))
+/// Build the primary key of a name: its compatibility decomposition without the combining
+/// marks, so that accented letters and ligatures compare like their base letters.
+fn collation_key(name: &str, case_sensitive: bool) -> String {
+    let mut key = String::with_capacity(name.len());
+    for c in name.nfkd().filter(|c| !is_combining_mark(*c)) {
+        match c {
+            'ß' => key.push_str("ss"),
+            c if case_sensitive => key.push(c),
+            c => key.extend(c.to_lowercase()),
+        }
+    }
+    key
+}
+
+/// Sort by date, the most recent first. Broken symlinks are dated from the epoch.
+///
+/// Entries with the same date are sorted by [by_name].
+pub fn by_date(a: &Meta, b: &Meta) -> Ordering {
+    date_cmp(a, b).then_with(|| by_name(a, b))
+}
+
+fn date_cmp(a: &Meta, b: &Meta) -> Ordering {
+    let date = |meta: &Meta| match meta.symlink.is_broken() {
+        true => Some(Date::from(SystemTime::UNIX_EPOCH)),
+        false => meta.date.clone(),
+    };
+    date(b).cmp(&date(a))
 }
 
 fn by_version(a: &Meta, b: &Meta) -> Ordering {
-    compare(&a.name.name, &b.name.name)
+    version_cmp(&a.name.name, &b.name.name)
+}
+
+/// Natural sort of the names, with hidden files mixed with the other ones.
+fn by_natural_name(a: &Meta, b: &Meta) -> Ordering {
+    let a_name = &a.name.name;
+    let b_name = &b.name.name;
+    version_cmp(
+        a_name.trim_start_matches('.'),
+        b_name.trim_start_matches('.'),
+    )
+    .then_with(|| version_cmp(a_name, b_name))
+}
+
+/// Compare two strings as a sequence of digit and non-digit runs.
+///
+/// Digit runs are compared by their numerical value, without parsing them into an integer so
+/// that arbitrarily long runs never overflow. Non-digit runs are compared case-insensitively.
+/// When both strings are equivalent (e.g. `file007` and `file7`, or `a` and `A`), the raw
+/// strings are compared to keep the ordering total.
+fn version_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_runs = VersionRuns(a);
+    let mut b_runs = VersionRuns(b);
+
+    loop {
+        let ordering = match (a_runs.next(), b_runs.next()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_run), Some(b_run)) => {
+                let a_is_digit = a_run.starts_with(|c: char| c.is_ascii_digit());
+                let b_is_digit = b_run.starts_with(|c: char| c.is_ascii_digit());
+                if a_is_digit && b_is_digit {
+                    let a_run = a_run.trim_start_matches('0');
+                    let b_run = b_run.trim_start_matches('0');
+                    a_run.len().cmp(&b_run.len()).then(a_run.cmp(b_run))
+                } else {
+                    a_run
+                        .chars()
+                        .flat_map(char::to_lowercase)
+                        .cmp(b_run.chars().flat_map(char::to_lowercase))
+                }
+            }
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// An iterator over the alternating runs of ascii digits and non-digits of a string.
+struct VersionRuns<'a>(&'a str);
+
+impl<'a> Iterator for VersionRuns<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let is_digit = self.0.chars().next()?.is_ascii_digit();
+        let end = self
+            .0
+            .find(|c: char| c.is_ascii_digit() != is_digit)
+            .unwrap_or(self.0.len());
+        let (run, rest) = self.0.split_at(end);
+        self.0 = rest;
+        Some(run)
+    }
 }
 
 fn by_extension(a: &Meta, b: &Meta) -> Ordering {
     a.name.extension().cmp(&b.name.extension())
 }
 
+/// Directories are sorted by their number of entries, the most populated first, and before the
+/// other files which are sorted by size.
+fn by_entries(a: &Meta, b: &Meta) -> Ordering {
+    match (a.entry_count, b.entry_count) {
+        (Some(a_count), Some(b_count)) => b_count.cmp(&a_count),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => size_cmp(a, b),
+    }
+}
+
+/// Without inodes (e.g. on Windows) this falls back to sorting by name.
+fn by_inode(a: &Meta, b: &Meta) -> Ordering {
+    let a_index = a.inode.and_then(|inode| inode.index());
+    let b_index = b.inode.and_then(|inode| inode.index());
+    match (a_index, b_index) {
+        (Some(a_index), Some(b_index)) => a_index.cmp(&b_index),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Symlinks are compared by the resolved path they point to and come after the other entries,
+/// which are only compared by name.
+fn by_target(a: &Meta, b: &Meta) -> Ordering {
+    a.symlink.destination().cmp(&b.symlink.destination())
+}
+
+/// Sort by the numeric mode, the most permissive first so that e.g. world-writable files
+/// surface. Without unix permissions (e.g. on Windows) this falls back to sorting by name.
+fn by_permission(a: &Meta, b: &Meta) -> Ordering {
+    let mode = |meta: &Meta| {
+        meta.permissions_or_attributes
+            .as_ref()
+            .and_then(|permissions| permissions.mode())
+    };
+    match (mode(a), mode(b)) {
+        (Some(a_mode), Some(b_mode)) => b_mode.cmp(&a_mode),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Sort by the name of the user, then by the name of the group. The entries without an owner
+/// (e.g. with `--permission disable`) come first.
+fn by_owner(a: &Meta, b: &Meta) -> Ordering {
+    // the comparators are called O(n log n) times, so the owners are looked up in the cache
+    let cache = OwnerCache::shared();
+    let names = |meta: &Meta| {
+        meta.owner
+            .as_ref()
+            .map(|owner| (owner.user_name(cache), owner.group_name(cache)))
+    };
+    names(a).cmp(&names(b))
+}
+
+/// Sort by the id of the user, then by the id of the group, for `--numeric-uid-gid`. The
+/// entries without an owner come first.
+#[cfg(unix)]
+fn by_owner_id(a: &Meta, b: &Meta) -> Ordering {
+    let ids = |meta: &Meta| meta.owner.as_ref().map(|owner| owner.ids());
+    ids(a).cmp(&ids(b))
+}
+
+/// Files are ranked from the most to the least noteworthy status: conflicted, modified,
+/// untracked, staged and finally clean (including the files outside of a repository).
 fn by_git_status(a: &Meta, b: &Meta) -> Ordering {
-    a.git_status.cmp(&b.git_status)
+    git_status_rank(&b.git_status).cmp(&git_status_rank(&a.git_status))
+}
+
+fn git_status_rank(status: &Option<GitFileStatus>) -> u8 {
+    let Some(status) = status else {
+        return 0;
+    };
+
+    match (status.index, status.workdir) {
+        (_, GitStatus::Conflicted) => 4,
+        (
+            _,
+            GitStatus::Modified | GitStatus::Deleted | GitStatus::Renamed | GitStatus::Typechange,
+        ) => 3,
+        (_, GitStatus::NewInWorkdir) => 2,
+        (GitStatus::Default | GitStatus::Unmodified | GitStatus::Ignored, _) => 0,
+        _ => 1,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::flags::{Flags, PermissionFlag};
+    use crate::flags::sorting::{DirsAmongFiles, SortKeys};
+    use crate::flags::{DateSource, Flags, PermissionFlag};
     use std::fs::{create_dir, File};
     use std::io::prelude::*;
     use std::process::Command;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_sort_dot_entries_first() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        File::create(tmp_dir.path().join("aaa")).expect("failed to create file");
+        create_dir(tmp_dir.path().join("zzz")).expect("failed to create dir");
+        let meta = Meta::from_path(
+            tmp_dir.path(),
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .expect("failed to get meta");
+
+        let mut flags = Flags {
+            display: crate::flags::Display::All,
+            ..Default::default()
+        };
+        let (content, _) = meta.recurse_into(1, &flags, None).unwrap();
+        let mut content = content.unwrap();
+
+        for (order, column, dir_grouping) in [
+            (SortOrder::Default, SortColumn::Name, DirGrouping::None),
+            (SortOrder::Reverse, SortColumn::Name, DirGrouping::None),
+            (SortOrder::Reverse, SortColumn::Size, DirGrouping::Last),
+        ] {
+            flags.sorting.order = order;
+            flags.sorting.column = column.clone();
+            flags.sorting.dir_grouping = dir_grouping;
+            let sorter = assemble_sorters(&flags);
+            content.sort_by(|a, b| by_meta(&sorter, a, b));
+
+            let names: Vec<_> = content.iter().map(|meta| meta.name.name.as_str()).collect();
+            assert_eq!(
+                [".", ".."],
+                names[..2],
+                "{order:?} {column:?} {dir_grouping:?}"
+            );
+        }
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_name_with_dirs_first() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -93,14 +637,14 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z =
-            Meta::from_path(&path_z, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::First;
@@ -123,14 +667,14 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z =
-            Meta::from_path(&path_z, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::Last;
@@ -140,10 +684,108 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
 
         // Sort with file first reversed (this files stay first)
+        flags.sorting.order = SortOrder::Reverse;
+
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_assemble_sorters_symlink_to_dir_grouped_with_dirs() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_dir = tmp_dir.path().join("bbb");
+        create_dir(&path_dir).expect("failed to create dir");
+        let meta_dir = Meta::from_path(&path_dir, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        let path_link = tmp_dir.path().join("ccc");
+        std::os::unix::fs::symlink(&path_dir, &path_link).expect("failed to create symlink");
+        let meta_link =
+            Meta::from_path(&path_link, false, PermissionFlag::Rwx, DateSource::Modified)
+                .expect("failed to get meta");
+
+        let path_file = tmp_dir.path().join("aaa");
+        File::create(&path_file).expect("failed to create file");
+        let meta_file =
+            Meta::from_path(&path_file, false, PermissionFlag::Rwx, DateSource::Modified)
+                .expect("failed to get meta");
+
+        let sorted_names = |flags: &Flags| {
+            let sorters = assemble_sorters(flags);
+            let mut metas = vec![meta_file.clone(), meta_link.clone(), meta_dir.clone()];
+            metas.sort_by(|a, b| by_meta(&sorters, a, b));
+            metas
+                .into_iter()
+                .map(|m| m.name.name)
+                .collect::<Vec<String>>()
+        };
+
+        let mut flags = Flags::default();
+        flags.sorting.order = SortOrder::Reverse;
+
+        flags.sorting.dir_grouping = DirGrouping::First;
+        assert_eq!(sorted_names(&flags), vec!["ccc", "bbb", "aaa"]);
+
+        flags.sorting.dir_grouping = DirGrouping::Last;
+        assert_eq!(sorted_names(&flags), vec!["aaa", "ccc", "bbb"]);
+    }
+
+    #[test]
+    fn test_sort_sorter_with_dir_sorters() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let create_meta = |name: &str, content: Option<&[u8]>| {
+            let path = tmp_dir.path().join(name);
+            match content {
+                Some(content) => File::create(&path)
+                    .expect("failed to create file")
+                    .write_all(content)
+                    .expect("failed to write to file"),
+                None => create_dir(&path).expect("failed to create dir"),
+            }
+            Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                .expect("failed to get meta")
+        };
+
+        let metas = vec![
+            create_meta("aaa", Some(b"1")),
+            create_meta("bbb", None),
+            create_meta("ccc", Some(b"1, 2, 3")),
+            create_meta("ddd", None),
+        ];
+
+        // directories by name, then files by size, smallest first
+        let sorter = Sorter::new(vec![(SortOrder::Reverse, by_size)])
+            .with_dir_grouping(SortOrder::Default)
+            .with_dir_sorters(vec![(SortOrder::Default, by_name)]);
+
+        let mut sorted = metas.clone();
+        sorted.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            sorted
+                .iter()
+                .map(|m| m.name.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["bbb", "ddd", "aaa", "ccc"]
+        );
+
+        // the directory sorters are ignored without grouping
+        let sorter = Sorter::new(vec![(SortOrder::Default, by_name)])
+            .with_dir_sorters(vec![(SortOrder::Reverse, by_name)]);
+
+        let mut sorted = metas;
+        sorted.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            sorted
+                .iter()
+                .map(|m| m.name.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["aaa", "bbb", "ccc", "ddd"]
+        );
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_name_unordered() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -151,14 +793,14 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("aaa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("zzz");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z =
-            Meta::from_path(&path_z, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::None;
@@ -181,14 +823,14 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z =
-            Meta::from_path(&path_z, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::None;
@@ -204,6 +846,43 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
     }
 
+    #[test]
+    fn test_sort_assemble_sorters_by_name_case() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let metas: Vec<Meta> = ["apple", "Banana", "cherry", "banana"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+        let sorted_names = |flags: &Flags| {
+            let sorters = assemble_sorters(flags);
+            let mut metas = metas.clone();
+            metas.sort_by(|a, b| by_meta(&sorters, a, b));
+            metas
+                .into_iter()
+                .map(|m| m.name.name)
+                .collect::<Vec<String>>()
+        };
+
+        let mut flags = Flags::default();
+        flags.sorting.case_sensitivity = CaseSensitivity::Insensitive;
+        assert_eq!(
+            sorted_names(&flags),
+            vec!["apple", "Banana", "banana", "cherry"]
+        );
+
+        flags.sorting.case_sensitivity = CaseSensitivity::Sensitive;
+        assert_eq!(
+            sorted_names(&flags),
+            vec!["Banana", "apple", "banana", "cherry"]
+        );
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_time() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -211,8 +890,8 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("aaa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         // Create the file;
         let path_z = tmp_dir.path().join("zzz");
@@ -238,8 +917,8 @@ mod tests {
             .success();
 
         assert!(success, "failed to change file timestamp");
-        let meta_z =
-            Meta::from_path(&path_z, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.column = SortColumn::Time;
@@ -261,39 +940,49 @@ mod tests {
         // Create the file with rs extension;
         let path_a = tmp_dir.path().join("aaa.rs");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         // Create the file with rs extension;
         let path_z = tmp_dir.path().join("zzz.rs");
         File::create(&path_z).expect("failed to create file");
-        let meta_z =
-            Meta::from_path(&path_z, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         // Create the file with js extension;
         let path_j = tmp_dir.path().join("zzz.js");
         File::create(&path_j).expect("failed to create file");
-        let meta_j =
-            Meta::from_path(&path_j, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_j = Meta::from_path(&path_j, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         // Create the file with txt extension;
         let path_t = tmp_dir.path().join("zzz.txt");
         File::create(&path_t).expect("failed to create file");
-        let meta_t =
-            Meta::from_path(&path_t, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_t = Meta::from_path(&path_t, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        // Create the file without extension;
+        let path_n = tmp_dir.path().join("zzz");
+        File::create(&path_n).expect("failed to create file");
+        let meta_n = Meta::from_path(&path_n, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.column = SortColumn::Extension;
 
-        // Sort by extension
+        // Sort by extension, same extensions are sorted by name
         let sorter = assemble_sorters(&flags);
-        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Equal);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
 
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_a, &meta_j), Ordering::Greater);
 
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_a, &meta_t), Ordering::Less);
+
+        // Files without extension come first
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_n, &meta_j), Ordering::Less);
     }
 
     #[test]
@@ -302,18 +991,18 @@ mod tests {
 
         let path_a = tmp_dir.path().join("2");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("11");
         File::create(&path_b).expect("failed to create file");
-        let meta_b =
-            Meta::from_path(&path_b, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_b = Meta::from_path(&path_b, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let path_c = tmp_dir.path().join("12");
         File::create(&path_c).expect("failed to create file");
-        let meta_c =
-            Meta::from_path(&path_c, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_c = Meta::from_path(&path_c, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.column = SortColumn::Version;
@@ -325,29 +1014,56 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_b, &meta_c), Ordering::Less);
     }
 
+    #[test]
+    fn test_version_cmp() {
+        assert_eq!(version_cmp("img2.png", "img10.png"), Ordering::Less);
+        assert_eq!(version_cmp("img10.png", "img1.png"), Ordering::Greater);
+        assert_eq!(version_cmp("img10.png", "img10.png"), Ordering::Equal);
+
+        // leading zeros
+        assert_eq!(version_cmp("file007", "file8"), Ordering::Less);
+        assert_eq!(version_cmp("file007", "file7"), Ordering::Less);
+        assert_eq!(version_cmp("file7", "file007"), Ordering::Greater);
+
+        // digit runs overflowing u64
+        assert_eq!(
+            version_cmp("a18446744073709551616", "a18446744073709551615"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            version_cmp("a99999999999999999999999", "a100"),
+            Ordering::Greater
+        );
+
+        // mixed case
+        assert_eq!(version_cmp("B2", "a10"), Ordering::Greater);
+        assert_eq!(version_cmp("file2", "FILE10"), Ordering::Less);
+        assert_eq!(version_cmp("A", "a"), Ordering::Less);
+    }
+
     #[test]
     fn test_sort_assemble_sorters_no_sort() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
 
         let path_a = tmp_dir.path().join("aaa.aa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("aaa");
         create_dir(&path_b).expect("failed to create dir");
-        let meta_b =
-            Meta::from_path(&path_b, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_b = Meta::from_path(&path_b, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let path_c = tmp_dir.path().join("zzz.zz");
         File::create(&path_c).expect("failed to create file");
-        let meta_c =
-            Meta::from_path(&path_c, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_c = Meta::from_path(&path_c, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let path_d = tmp_dir.path().join("zzz");
         create_dir(&path_d).expect("failed to create dir");
-        let meta_d =
-            Meta::from_path(&path_d, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_d = Meta::from_path(&path_d, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.column = SortColumn::None;
@@ -369,6 +1085,797 @@ mod tests {
 
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_c, &meta_d), Ordering::Equal);
+        assert!(sorter.is_noop());
+
+        // Directories are not grouped either
+        flags.sorting.dir_grouping = DirGrouping::First;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_b), Ordering::Equal);
+        assert!(sorter.is_noop());
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_entries() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_a = tmp_dir.path().join("aaa");
+        create_dir(&path_a).expect("failed to create dir");
+        File::create(path_a.join("one")).expect("failed to create file");
+        let mut meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+        meta_a.calculate_entry_count();
+
+        let path_b = tmp_dir.path().join("bbb");
+        create_dir(&path_b).expect("failed to create dir");
+        File::create(path_b.join("one")).expect("failed to create file");
+        File::create(path_b.join("two")).expect("failed to create file");
+        let mut meta_b = Meta::from_path(&path_b, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+        meta_b.calculate_entry_count();
+
+        let path_c = tmp_dir.path().join("ccc");
+        File::create(&path_c)
+            .expect("failed to create file")
+            .write_all(b"1, 2, 3")
+            .expect("failed to write to file");
+        let mut meta_c = Meta::from_path(&path_c, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+        meta_c.calculate_entry_count();
+
+        let path_d = tmp_dir.path().join("ddd");
+        File::create(&path_d).expect("failed to create file");
+        let mut meta_d = Meta::from_path(&path_d, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+        meta_d.calculate_entry_count();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Entries;
+        let sorter = assemble_sorters(&flags);
+
+        // the most populated directory comes first
+        assert_eq!(by_meta(&sorter, &meta_b, &meta_a), Ordering::Less);
+        // directories come before files
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_c), Ordering::Less);
+        // files are sorted by size
+        assert_eq!(by_meta(&sorter, &meta_c, &meta_d), Ordering::Less);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_assemble_sorters_by_inode() {
+        use std::os::unix::fs::MetadataExt;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_a = tmp_dir.path().join("aaa");
+        File::create(&path_a).expect("failed to create file");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        let path_z = tmp_dir.path().join("zzz");
+        File::create(&path_z).expect("failed to create file");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        let (lower, higher) = if path_a.metadata().unwrap().ino() < path_z.metadata().unwrap().ino()
+        {
+            (&meta_a, &meta_z)
+        } else {
+            (&meta_z, &meta_a)
+        };
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Inode;
+
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, lower, higher), Ordering::Less);
+        assert_eq!(by_meta(&sorter, higher, lower), Ordering::Greater);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_assemble_sorters_by_target() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_file = tmp_dir.path().join("zzz");
+        File::create(&path_file).expect("failed to create file");
+
+        let path_valid = tmp_dir.path().join("aaa");
+        std::os::unix::fs::symlink(&path_file, &path_valid).expect("failed to create symlink");
+
+        let path_root = tmp_dir.path().join("bbb");
+        std::os::unix::fs::symlink("/", &path_root).expect("failed to create symlink");
+        let path_missing = tmp_dir.path().join("ccc");
+        std::os::unix::fs::symlink("missing", &path_missing).expect("failed to create symlink");
+        // A link to the link "aaa" ends at "zzz" too
+        let path_chain = tmp_dir.path().join("ddd");
+        std::os::unix::fs::symlink("aaa", &path_chain).expect("failed to create symlink");
+
+        let paths = [
+            &path_file,
+            &path_valid,
+            &path_root,
+            &path_missing,
+            &path_chain,
+        ];
+        let mut metas: Vec<Meta> = paths
+            .iter()
+            .map(|path| {
+                Meta::from_path(path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Target;
+        let sorter = assemble_sorters(&flags);
+
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        // The link to "/" comes first, then the broken link to "missing" which sorts before
+        // "zzz" in the temp dir, the two links ending at "zzz" being listed by name
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(names, vec!["zzz", "bbb", "ccc", "aaa", "ddd"]);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_name_collation() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = ["cafe", "café", "cafz", "Äpfel", "apfel", "azur", "Straße"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+        let names =
+            |metas: &[Meta]| -> Vec<String> { metas.iter().map(|m| m.name.name.clone()).collect() };
+
+        let mut flags = Flags::default();
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            names(&metas),
+            vec!["apfel", "azur", "cafe", "cafz", "café", "Straße", "Äpfel"]
+        );
+
+        flags.sorting.collation = Collation::Unicode;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            names(&metas),
+            vec!["apfel", "Äpfel", "azur", "cafe", "café", "cafz", "Straße"]
+        );
+
+        flags.sorting.case_sensitivity = CaseSensitivity::Sensitive;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            names(&metas),
+            vec!["Äpfel", "Straße", "apfel", "azur", "cafe", "café", "cafz"]
+        );
+    }
+
+    #[test]
+    fn test_sort_group_directories_first() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_a = tmp_dir.path().join("zzz");
+        create_dir(&path_a).expect("failed to create dir");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        let path_z = tmp_dir.path().join("aaa");
+        File::create(&path_z).expect("failed to create file");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        for order in [SortOrder::Default, SortOrder::Reverse] {
+            let sorter = group_directories_first(Sorter::new(vec![(order, by_name)]));
+            assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
+            assert_eq!(by_meta(&sorter, &meta_z, &meta_a), Ordering::Greater);
+        }
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_hidden_grouping() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = [
+            ("zzz", true),
+            (".zzz", false),
+            ("aaa", false),
+            (".aaa", true),
+        ]
+        .iter()
+        .map(|(name, is_dir)| {
+            let path = tmp_dir.path().join(name);
+            if *is_dir {
+                create_dir(&path).expect("failed to create dir");
+            } else {
+                File::create(&path).expect("failed to create file");
+            }
+            Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                .expect("failed to get meta")
+        })
+        .collect();
+        let names =
+            |metas: &[Meta]| -> Vec<String> { metas.iter().map(|m| m.name.name.clone()).collect() };
+
+        let mut flags = Flags::default();
+        flags.sorting.hidden_grouping = HiddenGrouping::First;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec![".aaa", ".zzz", "aaa", "zzz"]);
+
+        flags.sorting.hidden_grouping = HiddenGrouping::Last;
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["zzz", "aaa", ".zzz", ".aaa"]);
+
+        flags.sorting.dir_grouping = DirGrouping::First;
+        flags.sorting.hidden_grouping = HiddenGrouping::First;
+        flags.sorting.order = SortOrder::Default;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec![".aaa", "zzz", ".zzz", "aaa"]);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_keys() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = [("aaa", 2), ("bbb", 1), ("ccc", 2), ("ddd", 1)]
+            .iter()
+            .map(|(name, size)| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path)
+                    .expect("failed to create file")
+                    .write_all(&vec![b'x'; *size])
+                    .expect("failed to write to file");
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+        let names =
+            |metas: &[Meta]| -> Vec<String> { metas.iter().map(|m| m.name.name.clone()).collect() };
+
+        let mut flags = Flags::default();
+        flags.sorting.keys = SortKeys(vec![
+            (SortColumn::Size, SortOrder::Reverse),
+            (SortColumn::Name, SortOrder::Reverse),
+        ]);
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["ddd", "bbb", "ccc", "aaa"]);
+
+        // The reverse flag flips every key
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["aaa", "ccc", "bbb", "ddd"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_assemble_sorters_by_permission() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = [("aaa", 0o600), ("bbb", 0o777), ("ccc", 0o600)]
+            .iter()
+            .map(|(name, mode)| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(*mode))
+                    .expect("unable to set permissions to file");
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Permission;
+        let sorter = assemble_sorters(&flags);
+
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(names, vec!["bbb", "aaa", "ccc"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_assemble_sorters_by_owner() {
+        use crate::meta::Owner;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // An id without a name is compared as is, and sorts before "root"
+        let unknown = 4_000_000;
+        let owners = [
+            ("aaa", Owner::new(0, unknown)),
+            ("bbb", Owner::new(0, 0)),
+            ("ccc", Owner::new(unknown, 0)),
+            ("ddd", Owner::new(0, 0)),
+        ];
+        let mut metas: Vec<Meta> = owners
+            .into_iter()
+            .map(|(name, owner)| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                let mut meta =
+                    Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                        .expect("failed to get meta");
+                meta.owner = Some(owner);
+                meta
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Owner;
+        let sorter = assemble_sorters(&flags);
+
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(names, vec!["ccc", "aaa", "bbb", "ddd"]);
+
+        // The ids are compared as numbers, "4000000" coming after 0
+        flags.numeric_uid_gid = crate::flags::NumericUidGid(true);
+        let sorter = assemble_sorters(&flags);
+
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(names, vec!["bbb", "ddd", "aaa", "ccc"]);
+    }
+
+    #[test]
+    fn test_sort_by_size_and_date_break_ties_like_by_name() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("foo");
+        File::create(&path).expect("failed to create file");
+        // the same file, so the size and the date are equal
+        let meta = |name: &str| {
+            let mut meta = Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                .expect("failed to get meta");
+            meta.name.name = name.to_owned();
+            meta
+        };
+        let (upper, lower) = (meta("Foo"), meta("foo"));
+
+        assert_eq!(Ordering::Less, by_name(&upper, &lower));
+        assert_eq!(Ordering::Less, by_size(&upper, &lower));
+        assert_eq!(Ordering::Less, by_date(&upper, &lower));
+        assert_eq!(Ordering::Greater, by_size(&lower, &upper));
+        assert_eq!(Ordering::Greater, by_date(&lower, &upper));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_by_owner_numeric_does_not_look_up_names() {
+        use crate::meta::Owner;
+
+        // Ids no other test uses, as the cache is shared by the whole run
+        let (first, second) = (4_100_001, 4_100_002);
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let mut metas: Vec<Meta> = [("aaa", second), ("bbb", first)]
+            .into_iter()
+            .map(|(name, id)| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                let mut meta =
+                    Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                        .expect("failed to get meta");
+                meta.owner = Some(Owner::new(id, id));
+                meta
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Owner;
+        flags.numeric_uid_gid = crate::flags::NumericUidGid(true);
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!("bbb", metas[0].name.name);
+        assert!(!OwnerCache::shared().has_user(first));
+        assert!(!OwnerCache::shared().has_user(second));
+
+        flags.numeric_uid_gid = crate::flags::NumericUidGid(false);
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert!(OwnerCache::shared().has_user(first));
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_dir_grouping_truth_table() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let entries = [("aaa", true), ("bbb", false), ("ccc", true), ("ddd", false)];
+        let metas: Vec<Meta> = entries
+            .iter()
+            .map(|(name, is_dir)| {
+                let path = tmp_dir.path().join(name);
+                if *is_dir {
+                    create_dir(&path).expect("failed to create dir");
+                    File::create(path.join("content")).expect("failed to create file");
+                } else {
+                    File::create(&path)
+                        .expect("failed to create file")
+                        .write_all(name.as_bytes())
+                        .expect("failed to write to file");
+                }
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+
+        for dir_grouping in [DirGrouping::None, DirGrouping::First, DirGrouping::Last] {
+            for order in [SortOrder::Default, SortOrder::Reverse] {
+                for column in [SortColumn::Name, SortColumn::Size, SortColumn::Time] {
+                    let mut flags = Flags::default();
+                    flags.sorting.dir_grouping = dir_grouping;
+                    flags.sorting.order = order;
+                    flags.sorting.column = column.clone();
+                    let sorter = assemble_sorters(&flags);
+
+                    let mut sorted = metas.clone();
+                    sorted.sort_by(|a, b| by_meta(&sorter, a, b));
+
+                    let combination = format!("{dir_grouping:?} {order:?} {column:?}");
+                    let dirs: Vec<bool> = sorted.iter().map(|m| m.file_type.is_dirlike()).collect();
+                    match dir_grouping {
+                        DirGrouping::First => {
+                            assert_eq!(dirs, vec![true, true, false, false], "{combination}")
+                        }
+                        DirGrouping::Last => {
+                            assert_eq!(dirs, vec![false, false, true, true], "{combination}")
+                        }
+                        DirGrouping::None => {}
+                    }
+
+                    let value_sorter = column_sorter(&column, &flags).unwrap();
+                    for pair in sorted.windows(2) {
+                        if pair[0].file_type.is_dirlike() != pair[1].file_type.is_dirlike()
+                            && dir_grouping != DirGrouping::None
+                        {
+                            continue;
+                        }
+                        assert_ne!(
+                            by_sorters(&[(order, value_sorter)], &pair[0], &pair[1]),
+                            Ordering::Greater,
+                            "{combination}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_with_tree_override() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let entries = [
+            ("file10", false),
+            (".file2", false),
+            ("zzz", true),
+            ("file1", false),
+        ];
+        let mut metas: Vec<Meta> = entries
+            .iter()
+            .map(|(name, is_dir)| {
+                let path = tmp_dir.path().join(name);
+                if *is_dir {
+                    create_dir(&path).expect("failed to create dir");
+                } else {
+                    File::create(&path)
+                        .expect("failed to create file")
+                        .write_all(name.as_bytes())
+                        .expect("failed to write to file");
+                }
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Size;
+        let sorter = assemble_sorters_with(&flags, &SortOverride::tree(&flags));
+
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(names, vec!["zzz", "file1", ".file2", "file10"]);
+    }
+
+    #[test]
+    fn test_sort_dirs_among_files_by_size() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        create_dir(&dir_path).expect("failed to create dir");
+        let dir = Meta::from_path(&dir_path, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+        let dir_size = dir.size.as_ref().unwrap().get_bytes() as usize;
+
+        // a file smaller than the directory, and a larger one
+        let mut metas = vec![dir];
+        for (name, size) in [("small", dir_size - 1), ("large", dir_size + 1)] {
+            let path = tmp_dir.path().join(name);
+            std::fs::write(&path, vec![0; size]).expect("failed to write to file");
+            metas.push(
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta"),
+            );
+        }
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Size;
+        flags.sorting.dir_grouping = DirGrouping::First;
+        flags.sorting.dirs_among_files = DirsAmongFiles(true);
+
+        for sorter in [
+            assemble_sorters(&flags),
+            assemble_sorters_with(&flags, &SortOverride::tree(&flags)),
+        ] {
+            metas.sort_by(|a, b| by_meta(&sorter, a, b));
+            let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+            assert_eq!(names, vec!["large", "dir", "small"]);
+        }
+    }
+
+    #[test]
+    fn test_sort_cache_sort_keys() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path = tmp_dir.path().join("Café");
+        File::create(&path).expect("failed to create file");
+        let meta = Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        let mut flags = Flags::default();
+        let mut metas = vec![meta];
+        cache_sort_keys(&flags, &mut metas);
+        assert_eq!(metas[0].name_key, None);
+
+        flags.sorting.collation = Collation::Unicode;
+        cache_sort_keys(&flags, &mut metas);
+        assert_eq!(metas[0].name_key.as_deref(), Some("cafe"));
+
+        flags.sorting.case_sensitivity = CaseSensitivity::Sensitive;
+        cache_sort_keys(&flags, &mut metas);
+        assert_eq!(metas[0].name_key.as_deref(), Some("Cafe"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_broken_symlink() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_file = tmp_dir.path().join("file").join("aaa");
+        create_dir(path_file.parent().unwrap()).expect("failed to create dir");
+        File::create(&path_file)
+            .expect("failed to create file")
+            .write_all(b"1, 2, 3")
+            .expect("failed to write to file");
+        let meta_file =
+            Meta::from_path(&path_file, false, PermissionFlag::Rwx, DateSource::Modified)
+                .expect("failed to get meta");
+
+        let path_link = tmp_dir.path().join("link").join("aaa");
+        create_dir(path_link.parent().unwrap()).expect("failed to create dir");
+        std::os::unix::fs::symlink("missing", &path_link).expect("failed to create symlink");
+        let meta_link =
+            Meta::from_path(&path_link, false, PermissionFlag::Rwx, DateSource::Modified)
+                .expect("failed to get meta");
+        assert!(meta_link.symlink.is_broken());
+
+        let path_empty = tmp_dir.path().join("bbb");
+        File::create(&path_empty).expect("failed to create file");
+        let meta_empty = Meta::from_path(
+            &path_empty,
+            false,
+            PermissionFlag::Rwx,
+            DateSource::Modified,
+        )
+        .expect("failed to get meta");
+
+        let mut flags = Flags::default();
+        for order in [SortOrder::Default, SortOrder::Reverse] {
+            flags.sorting.order = order;
+            for column in [SortColumn::Name, SortColumn::Size, SortColumn::Time] {
+                flags.sorting.column = column.clone();
+                let sorter = assemble_sorters(&flags);
+                let ordering = by_meta(&sorter, &meta_file, &meta_link);
+                if column == SortColumn::Name {
+                    assert_eq!(ordering, Ordering::Less, "{order:?} {column:?}");
+                } else {
+                    assert_ne!(ordering, Ordering::Equal, "{order:?} {column:?}");
+                }
+            }
+        }
+
+        // The broken link is as empty as "bbb" and as old as can be
+        flags.sorting.order = SortOrder::Default;
+        flags.sorting.column = SortColumn::Size;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_link, &meta_empty), Ordering::Less);
+        flags.sorting.column = SortColumn::Time;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_link, &meta_empty), Ordering::Greater);
+        assert_eq!(by_meta(&sorter, &meta_link, &meta_file), Ordering::Greater);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_assemble_sorters_symlink_grouping() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_dir = tmp_dir.path().join("zzz");
+        create_dir(&path_dir).expect("failed to create dir");
+        let path_file = tmp_dir.path().join("ddd");
+        File::create(&path_file).expect("failed to create file");
+        std::os::unix::fs::symlink(&path_dir, tmp_dir.path().join("aaa"))
+            .expect("failed to create symlink");
+        std::os::unix::fs::symlink(&path_file, tmp_dir.path().join("bbb"))
+            .expect("failed to create symlink");
+
+        let mut metas: Vec<Meta> = ["aaa", "bbb", "ddd", "zzz"]
+            .iter()
+            .map(|name| {
+                Meta::from_path(
+                    &tmp_dir.path().join(name),
+                    false,
+                    PermissionFlag::Rwx,
+                    DateSource::Modified,
+                )
+                .expect("failed to get meta")
+            })
+            .collect();
+        let names =
+            |metas: &[Meta]| -> Vec<String> { metas.iter().map(|m| m.name.name.clone()).collect() };
+
+        let mut flags = Flags::default();
+        flags.sorting.symlink_grouping = SymlinkGrouping::Last;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["ddd", "zzz", "aaa", "bbb"]);
+
+        // The symlink to a directory is grouped with the directories first
+        flags.sorting.dir_grouping = DirGrouping::First;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["zzz", "aaa", "ddd", "bbb"]);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_reverse_all() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = [("aaa", 1), ("bbb", 2), ("ccc", 1)]
+            .iter()
+            .map(|(name, size)| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path)
+                    .expect("failed to create file")
+                    .write_all(&vec![b'x'; *size])
+                    .expect("failed to write to file");
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+        let names =
+            |metas: &[Meta]| -> Vec<String> { metas.iter().map(|m| m.name.name.clone()).collect() };
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Size;
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["ccc", "aaa", "bbb"]);
+
+        flags.sorting.reverse_all = ReverseAll(false);
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["aaa", "ccc", "bbb"]);
+
+        // Without reversing, the option makes no difference
+        flags.sorting.order = SortOrder::Default;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["bbb", "aaa", "ccc"]);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_custom() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = ["a", "ccc", "bb", "dd"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                    .expect("failed to get meta")
+            })
+            .collect();
+        let names =
+            |metas: &[Meta]| -> Vec<String> { metas.iter().map(|m| m.name.name.clone()).collect() };
+
+        register_sorter("length", |a, b| a.name.name.len().cmp(&b.name.name.len()));
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Custom("length".into());
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["a", "bb", "dd", "ccc"]);
+
+        // An unknown sorter falls back to the name
+        flags.sorting.column = SortColumn::Custom("unknown".into());
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["a", "bb", "ccc", "dd"]);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_git_status() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let statuses = [
+            (
+                "clean",
+                Some((GitStatus::Unmodified, GitStatus::Unmodified)),
+            ),
+            ("outside", None),
+            ("staged", Some((GitStatus::Modified, GitStatus::Unmodified))),
+            (
+                "untracked",
+                Some((GitStatus::Unmodified, GitStatus::NewInWorkdir)),
+            ),
+            ("both", Some((GitStatus::Modified, GitStatus::Modified))),
+            (
+                "modified",
+                Some((GitStatus::Unmodified, GitStatus::Modified)),
+            ),
+            (
+                "conflicted",
+                Some((GitStatus::Unmodified, GitStatus::Conflicted)),
+            ),
+        ];
+        let mut metas: Vec<Meta> = statuses
+            .iter()
+            .map(|(name, status)| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                let mut meta =
+                    Meta::from_path(&path, false, PermissionFlag::Rwx, DateSource::Modified)
+                        .expect("failed to get meta");
+                meta.git_status = status.map(|(index, workdir)| GitFileStatus { index, workdir });
+                meta
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::GitStatus;
+        let sorter = assemble_sorters(&flags);
+
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            metas
+                .iter()
+                .map(|m| m.name.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "conflicted",
+                "both",
+                "modified",
+                "untracked",
+                "staged",
+                "clean",
+                "outside"
+            ]
+        );
     }
 
     #[test]
@@ -380,16 +1887,16 @@ mod tests {
             .expect("failed to create file")
             .write_all(b"1, 2, 3")
             .expect("failed to write to file");
-        let meta_a =
-            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("bbb.bb");
         File::create(&path_b)
             .expect("failed to create file")
             .write_all(b"1, 2, 3, 4, 5, 6, 7, 8, 9, 10")
             .expect("failed to write file");
-        let meta_b =
-            Meta::from_path(&path_b, false, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_b = Meta::from_path(&path_b, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
         let path_c = tmp_dir.path().join("ccc.cc");
         let path_d = tmp_dir.path().join("ddd.dd");
@@ -404,19 +1911,44 @@ mod tests {
         std::os::windows::fs::symlink_file(path_d, &path_c)
             .expect("failed to create broken symlink");
 
-        let meta_c =
-            Meta::from_path(&path_c, true, PermissionFlag::Rwx).expect("failed to get meta");
+        let meta_c = Meta::from_path(&path_c, true, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
 
+        // The broken symlink is considered empty
         assert_eq!(by_size(&meta_a, &meta_a), Ordering::Equal);
         assert_eq!(by_size(&meta_a, &meta_b), Ordering::Greater);
-        assert_eq!(by_size(&meta_a, &meta_c), Ordering::Greater);
+        assert_eq!(by_size(&meta_a, &meta_c), Ordering::Less);
 
         assert_eq!(by_size(&meta_b, &meta_a), Ordering::Less);
         assert_eq!(by_size(&meta_b, &meta_b), Ordering::Equal);
-        assert_eq!(by_size(&meta_b, &meta_c), Ordering::Greater);
+        assert_eq!(by_size(&meta_b, &meta_c), Ordering::Less);
 
-        assert_eq!(by_size(&meta_c, &meta_a), Ordering::Less);
-        assert_eq!(by_size(&meta_c, &meta_b), Ordering::Less);
+        assert_eq!(by_size(&meta_c, &meta_a), Ordering::Greater);
+        assert_eq!(by_size(&meta_c, &meta_b), Ordering::Greater);
         assert_eq!(by_size(&meta_c, &meta_c), Ordering::Equal);
     }
+
+    #[test]
+    fn test_sort_by_size_same_size() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_a = tmp_dir.path().join("aaa");
+        File::create(&path_a)
+            .expect("failed to create file")
+            .write_all(b"1, 2, 3")
+            .expect("failed to write to file");
+        let meta_a = Meta::from_path(&path_a, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        let path_z = tmp_dir.path().join("zzz");
+        File::create(&path_z)
+            .expect("failed to create file")
+            .write_all(b"3, 2, 1")
+            .expect("failed to write to file");
+        let meta_z = Meta::from_path(&path_z, false, PermissionFlag::Rwx, DateSource::Modified)
+            .expect("failed to get meta");
+
+        assert_eq!(by_size(&meta_a, &meta_z), Ordering::Less);
+        assert_eq!(by_size(&meta_z, &meta_a), Ordering::Greater);
+    }
 }
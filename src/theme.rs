@@ -73,12 +73,27 @@ impl Theme {
             return Err(Error::InvalidPath("No valid theme file found".to_string()));
         };
 
-        match fs::read_to_string(valid) {
+        // the callers fall back to the default theme, so a broken file is only warned about
+        match fs::read_to_string(&valid) {
             Ok(yaml) => match Self::with_yaml(&yaml) {
                 Ok(t) => Ok(t),
-                Err(e) => Err(Error::InvalidFormat(e)),
+                Err(e) => {
+                    print_error!(
+                        "{}: invalid theme, using the default one: {}.",
+                        valid.display(),
+                        e
+                    );
+                    Err(Error::InvalidFormat(e))
+                }
             },
-            Err(e) => Err(Error::ReadFailed(e)),
+            Err(e) => {
+                print_error!(
+                    "{}: can not read the theme, using the default one: {}.",
+                    valid.display(),
+                    e
+                );
+                Err(Error::ReadFailed(e))
+            }
         }
     }
 
@@ -97,6 +97,7 @@ pub struct ColorTheme {
     pub tree_edge: Color,
     pub links: Links,
     pub git_status: GitStatus,
+    pub git_name: GitName,
 
     #[serde(skip)]
     pub file_type: FileType,
@@ -197,6 +198,8 @@ pub struct Symlink {
     pub broken: Color,
     #[serde(deserialize_with = "deserialize_color")]
     pub missing_target: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub arrow: Color,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -225,6 +228,10 @@ pub struct Size {
     pub medium: Color,
     #[serde(deserialize_with = "deserialize_color")]
     pub large: Color,
+    /// The number of bytes from which a size is medium.
+    pub medium_threshold: u64,
+    /// The number of bytes from which a size is large.
+    pub large_threshold: u64,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -276,12 +283,28 @@ pub struct GitStatus {
     pub conflicted: Color,
 }
 
+/// The colors of the names of the entries changed in git, overriding the ones of their type.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct GitName {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub modified: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub untracked: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub staged: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub conflicted: Color,
+}
+
 impl Default for Permission {
     fn default() -> Self {
         Permission {
-            read: Color::DarkGreen,
-            write: Color::DarkYellow,
-            exec: Color::DarkRed,
+            read: Color::Yellow,
+            write: Color::Red,
+            exec: Color::Green,
             exec_sticky: Color::AnsiValue(5),
             no_access: Color::AnsiValue(245), // Grey
             octal: Color::AnsiValue(6),
@@ -338,6 +361,7 @@ impl Default for Symlink {
             default: Color::AnsiValue(44),         // DarkTurquoise
             broken: Color::AnsiValue(124),         // Red3
             missing_target: Color::AnsiValue(124), // Red3
+            arrow: Color::AnsiValue(245),          // Grey
         }
     }
 }
@@ -353,10 +377,12 @@ impl Default for Date {
 impl Default for Size {
     fn default() -> Self {
         Size {
-            none: Color::AnsiValue(245),   // Grey
-            small: Color::AnsiValue(229),  // Wheat1
-            medium: Color::AnsiValue(216), // LightSalmon1
-            large: Color::AnsiValue(172),  // Orange3
+            none: Color::AnsiValue(245),         // Grey
+            small: Color::AnsiValue(229),        // Wheat1
+            medium: Color::AnsiValue(216),       // LightSalmon1
+            large: Color::AnsiValue(172),        // Orange3
+            medium_threshold: 1024 * 1024,       // 1 MiB
+            large_threshold: 1024 * 1024 * 1024, // 1 GiB
         }
     }
 }
@@ -394,6 +420,17 @@ impl Default for GitStatus {
     }
 }
 
+impl Default for GitName {
+    fn default() -> Self {
+        GitName {
+            modified: Color::DarkYellow,
+            untracked: Color::AnsiValue(245), // Grey
+            staged: Color::DarkGreen,
+            conflicted: Color::DarkRed,
+        }
+    }
+}
+
 impl Default for ColorTheme {
     fn default() -> Self {
         // TODO(zwpaper): check terminal color and return light or dark
@@ -415,6 +452,7 @@ impl ColorTheme {
             links: Links::default(),
             tree_edge: Color::AnsiValue(245), // Grey
             git_status: Default::default(),
+            git_name: Default::default(),
         }
     }
 }
@@ -429,9 +467,9 @@ mod tests {
 user: 230
 group: 187
 permission:
-  read: dark_green
-  write: dark_yellow
-  exec: dark_red
+  read: yellow
+  write: red
+  exec: green
   exec-sticky: 5
   no-access: 245
 date:
@@ -476,6 +514,20 @@ tree-edge: 245
         );
     }
 
+    #[test]
+    fn test_invalid_theme_file() {
+        use crate::theme::Error;
+        use std::fs;
+        let dir = assert_fs::TempDir::new().unwrap();
+        let theme = dir.path().join("theme.yaml");
+        fs::write(&theme, "size:\n  small: not-a-color\n").unwrap();
+
+        assert!(matches!(
+            Theme::from_path::<ColorTheme>(theme.to_str().unwrap()),
+            Err(Error::InvalidFormat(_))
+        ));
+    }
+
     #[test]
     fn test_empty_theme_return_default() {
         // Must contain one field at least
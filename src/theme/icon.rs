@@ -1,56 +1,44 @@
+use crate::print_error;
 use serde::Deserialize;
 use std::collections::HashMap;
 
-enum ByFilename {
-    Name,
-    Extension,
-}
-
-fn deserialize_by_filename<'de, D>(
-    deserializer: D,
-    by: ByFilename,
-) -> Result<HashMap<String, String>, D::Error>
-where
-    D: serde::de::Deserializer<'de>,
-{
-    let default = match by {
-        ByFilename::Name => IconTheme::get_default_icons_by_name(),
-        ByFilename::Extension => IconTheme::get_default_icons_by_extension(),
-    };
-    HashMap::<_, _>::deserialize(deserializer)
-        .map(|input| default.into_iter().chain(input).collect())
-}
-
-fn deserialize_by_name<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
-where
-    D: serde::de::Deserializer<'de>,
-{
-    deserialize_by_filename(deserializer, ByFilename::Name)
-}
-
-fn deserialize_by_extension<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
-where
-    D: serde::de::Deserializer<'de>,
-{
-    deserialize_by_filename(deserializer, ByFilename::Extension)
+/// The icons of a theme file, applied over the default icons of the chosen theme.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct CustomIcons {
+    name: HashMap<String, String>,
+    extension: HashMap<String, String>,
+    filetype: CustomByType,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
+struct CustomByType {
+    dir: Option<String>,
+    file: Option<String>,
+    pipe: Option<String>,
+    socket: Option<String>,
+    executable: Option<String>,
+    device_char: Option<String>,
+    device_block: Option<String>,
+    special: Option<String>,
+    symlink_dir: Option<String>,
+    symlink_file: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(from = "CustomIcons")]
 pub struct IconTheme {
-    #[serde(deserialize_with = "deserialize_by_name")]
     pub name: HashMap<String, String>,
-    #[serde(deserialize_with = "deserialize_by_extension")]
     pub extension: HashMap<String, String>,
     pub filetype: ByType,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "kebab-case")]
-#[serde(deny_unknown_fields)]
-#[serde(default)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct ByType {
     pub dir: String,
     pub file: String,
@@ -64,6 +52,13 @@ pub struct ByType {
     pub symlink_file: String,
 }
 
+/// A theme file applies over the fancy icons.
+impl From<CustomIcons> for IconTheme {
+    fn from(custom: CustomIcons) -> Self {
+        IconTheme::default().with_custom(custom)
+    }
+}
+
 impl Default for IconTheme {
     fn default() -> Self {
         IconTheme {
@@ -98,10 +93,10 @@ impl ByType {
             file: "\u{1f4c4}".into(),
             pipe: "\u{1f4e9}".into(),
             socket: "\u{1f4ec}".into(),
-            executable: "\u{1f3d7}".into(),
-            symlink_dir: "\u{1f5c2}".into(),
+            executable: "\u{26a1}".into(),
+            symlink_dir: "\u{1f517}".into(),
             symlink_file: "\u{1f516}".into(),
-            device_char: "\u{1f5a8}".into(),
+            device_char: "\u{1f4e0}".into(),
             device_block: "\u{1f4bd}".into(),
             special: "\u{1f4df}".into(),
         }
@@ -117,6 +112,63 @@ impl IconTheme {
         }
     }
 
+    /// Replace the icons by the ones of a theme file, the empty ones being ignored with a
+    /// warning.
+    pub fn with_custom(mut self, custom: CustomIcons) -> Self {
+        for (section, icons, custom) in [
+            ("name", &mut self.name, custom.name),
+            ("extension", &mut self.extension, custom.extension),
+        ] {
+            for (key, icon) in custom {
+                if icon.is_empty() {
+                    print_error!(
+                        "icons: empty icon for the {} {}, ignoring it.",
+                        section,
+                        key
+                    );
+                } else {
+                    // the names and extensions are looked up in lowercase
+                    icons.insert(key.to_lowercase(), icon);
+                }
+            }
+        }
+
+        let CustomByType {
+            dir,
+            file,
+            pipe,
+            socket,
+            executable,
+            device_char,
+            device_block,
+            special,
+            symlink_dir,
+            symlink_file,
+        } = custom.filetype;
+        let by_type = &mut self.filetype;
+        for (name, icon, custom) in [
+            ("dir", &mut by_type.dir, dir),
+            ("file", &mut by_type.file, file),
+            ("pipe", &mut by_type.pipe, pipe),
+            ("socket", &mut by_type.socket, socket),
+            ("executable", &mut by_type.executable, executable),
+            ("device-char", &mut by_type.device_char, device_char),
+            ("device-block", &mut by_type.device_block, device_block),
+            ("special", &mut by_type.special, special),
+            ("symlink-dir", &mut by_type.symlink_dir, symlink_dir),
+            ("symlink-file", &mut by_type.symlink_file, symlink_file),
+        ] {
+            match custom {
+                Some(custom) if custom.is_empty() => {
+                    print_error!("icons: empty icon for the filetype {}, ignoring it.", name);
+                }
+                Some(custom) => *icon = custom,
+                None => {}
+            }
+        }
+        self
+    }
+
     // pub only for testing in icons.rs
     pub fn get_default_icons_by_name() -> HashMap<String, String> {
         // Note: filenames must be lower-case
@@ -770,6 +822,26 @@ filetype:
         assert_eq!(def.filetype.dir, yaml.filetype.dir,);
     }
 
+    #[test]
+    fn test_unicode_icons_are_double_width() {
+        use unicode_width::UnicodeWidthStr;
+        let filetype = IconTheme::unicode().filetype;
+        for icon in [
+            filetype.dir,
+            filetype.file,
+            filetype.pipe,
+            filetype.socket,
+            filetype.executable,
+            filetype.device_char,
+            filetype.device_block,
+            filetype.special,
+            filetype.symlink_dir,
+            filetype.symlink_file,
+        ] {
+            assert_eq!(2, icon.width(), "{icon}");
+        }
+    }
+
     #[test]
     fn test_default_theme() {
         let def = IconTheme::default();
@@ -816,6 +888,18 @@ filetype:
         assert_eq!(empty.filetype.dir, "");
     }
 
+    #[test]
+    fn test_empty_icons_are_ignored() {
+        let theme: IconTheme = Theme::with_yaml(
+            "name:\n  cargo.lock: ''\nextension:\n  go: ''\n  foo: ''\nfiletype:\n  file: ''",
+        )
+        .unwrap();
+        assert_eq!(theme.name.get("cargo.lock").unwrap(), "\u{e68b}");
+        assert_eq!(theme.extension.get("go").unwrap(), "\u{e627}");
+        assert_eq!(theme.extension.get("foo"), None);
+        assert_eq!(theme.filetype.file, "\u{f016}");
+    }
+
     #[test]
     fn test_custom_icon_by_name() {
         // When a user sets to use 📦-icon for a cargo.toml file,
@@ -832,6 +916,14 @@ filetype:
         assert_eq!(theme.name.get("cargo.lock").unwrap(), "\u{e68b}");
     }
 
+    #[test]
+    fn test_custom_icon_by_name_case_insensitive() {
+        let theme: IconTheme =
+            Theme::with_yaml("name:\n  Dockerfile: 🐳\nextension:\n  RS: 🦀").unwrap();
+        assert_eq!(theme.name.get("dockerfile").unwrap(), "🐳");
+        assert_eq!(theme.extension.get("rs").unwrap(), "🦀");
+    }
+
     #[test]
     fn test_custom_icon_by_extension() {
         // When a user sets to use 🦀-icon for *.rs files,
@@ -25,6 +25,158 @@ fn test_list_empty_directory() {
         .stdout(predicate::eq(""));
 }
 
+#[test]
+fn test_no_escape_sequence_when_piped() {
+    let dir = tempdir();
+    dir.child("file.rs").touch().unwrap();
+    dir.child("dir").create_dir_all().unwrap();
+
+    for args in [&["-l"][..], &["--tree"], &["-1", "--hyperlink", "auto"]] {
+        let output = cmd()
+            .arg("--ignore-config")
+            .args(args)
+            .arg(dir.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert!(output.stdout.windows(7).any(|w| w == b"file.rs"));
+        assert!(!output.stdout.contains(&b'\x1b'), "{args:?}");
+    }
+
+    let output = cmd()
+        .arg("--ignore-config")
+        .arg("--color=always")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.stdout.contains(&b'\x1b'));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_classify() {
+    let dir = tempdir();
+    dir.child("dir").create_dir_all().unwrap();
+    dir.child("file").touch().unwrap();
+    dir.child("exec").touch().unwrap();
+    std::fs::set_permissions(
+        dir.child("exec").path(),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    fs::symlink("file", dir.path().join("link")).unwrap();
+    let status = std::process::Command::new("mkfifo")
+        .arg(dir.path().join("pipe"))
+        .status()
+        .expect("failed to exec mkfifo");
+    assert!(status.success(), "failed to exec mkfifo");
+
+    let classified = "dir/\nexec*\nfile\nlink@ ⇒ file\npipe|\n";
+    cmd()
+        .arg("--ignore-config")
+        .arg("-F")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(classified));
+
+    // the executable bit is still read without the permissions
+    cmd()
+        .arg("--ignore-config")
+        .arg("-F")
+        .arg("-l")
+        .arg("--permission=disable")
+        .arg("--blocks=name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("exec*\n"));
+}
+
+#[test]
+fn test_list_several_paths() {
+    let dir = tempdir();
+    dir.child("b").touch().unwrap();
+    dir.child("a").touch().unwrap();
+    dir.child("d2/y").touch().unwrap();
+    dir.child("d1/x").touch().unwrap();
+
+    // the files are listed together first, then each directory under its header
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .args(["d2", "b", "d1", "a"])
+        .assert()
+        .stdout(predicate::eq("a\nb\n\nd1:\nx\n\nd2:\ny\n"));
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .args(["d2", "d1"])
+        .assert()
+        .stdout(predicate::eq("d1:\nx\n\nd2:\ny\n"));
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("d1")
+        .assert()
+        .stdout(predicate::eq("x\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_list_repeated_paths_once() {
+    let dir = tempdir();
+    dir.child("d/x").touch().unwrap();
+    fs::symlink("d", dir.path().join("l1")).unwrap();
+    fs::symlink("d", dir.path().join("l2")).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .args(["d", "./d", "d/"])
+        .arg(dir.path().join("d"))
+        .assert()
+        .stdout(predicate::eq("x\n"));
+
+    // the symlinks are distinct entries, unless they are dereferenced
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .args(["-d", "--sort=none", "l2", "l1", "./l2"])
+        .assert()
+        .stdout(predicate::eq("l2 ⇒ d\nl1 ⇒ d\n"));
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .args(["-d", "--dereference", "l2", "l1", "d"])
+        .assert()
+        .stdout(predicate::eq("l2\n"));
+}
+
+#[test]
+fn test_grid_width() {
+    let dir = tempdir();
+    for name in ["aa", "bb", "cc", "dd", "ee"] {
+        dir.child(name).touch().unwrap();
+    }
+
+    // the grid is kept even though the output is not a terminal
+    cmd()
+        .env("COLUMNS", "80")
+        .arg("--ignore-config")
+        .args(["--width", "10"])
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("aa  dd\nbb  ee\ncc  \n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .args(["--width", "0"])
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("aa\nbb\ncc\ndd\nee\n"));
+}
+
 #[test]
 fn test_list_almost_all_empty_directory() {
     let matched = "";
@@ -99,6 +251,48 @@ fn test_list_all_populated_directory() {
         .stdout(predicate::str::is_match("\\.\n\\.\\.\none\ntwo\n$").unwrap());
 }
 
+#[test]
+fn test_list_all_dot_entries_first_by_size() {
+    let dir = tempdir();
+    dir.child("small").write_str("1").unwrap();
+    dir.child("large").write_str(&"1".repeat(100_000)).unwrap();
+    for order in [&["--sizesort"][..], &["--sizesort", "--reverse"]] {
+        cmd()
+            .arg("--all")
+            .args(order)
+            .arg("--ignore-config")
+            .arg(dir.path())
+            .assert()
+            .stdout(predicate::str::is_match("^\\.\n\\.\\.\n").unwrap());
+    }
+}
+
+#[test]
+fn test_reverse_keeps_the_directories_grouped() {
+    let dir = tempdir();
+    dir.child("d1").create_dir_all().unwrap();
+    dir.child("d2").create_dir_all().unwrap();
+    dir.child("f1").touch().unwrap();
+    dir.child("f2").touch().unwrap();
+
+    cmd()
+        .arg("-r")
+        .arg("--group-dirs")
+        .arg("first")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout("d2\nd1\nf2\nf1\n");
+    cmd()
+        .arg("-r")
+        .arg("--group-dirs")
+        .arg("last")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout("f2\nf1\nd2\nd1\n");
+}
+
 #[test]
 fn test_almost_sort_with_folder() {
     let tmp = tempdir();
@@ -180,6 +374,82 @@ fn test_list_block_inode_populated_directory_with_long() {
         .stdout(predicate::str::is_match(matched).unwrap());
 }
 
+#[test]
+fn test_list_long_aligns_sizes_right() {
+    let dir = tempdir();
+    dir.child("one").write_str("1").unwrap();
+    // a sparse file, which does not take a GiB on the disk
+    std::fs::File::create(dir.path().join("big"))
+        .and_then(|file| file.set_len(1 << 30))
+        .unwrap();
+
+    cmd()
+        .arg("--long")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout("1.0 GiB big\n  1 B   one\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_list_size_blocks_with_total() {
+    let dir = tempdir();
+    dir.child("empty").touch().unwrap();
+    // a sparse file, which has no block allocated
+    std::fs::File::create(dir.path().join("sparse"))
+        .and_then(|file| file.set_len(1 << 30))
+        .unwrap();
+
+    cmd()
+        .arg("-s")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout("total 0\n0 empty\n0 sparse\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_classify_sparse_file() {
+    let dir = tempdir();
+    dir.child("dense").write_str("1").unwrap();
+    std::fs::File::create(dir.path().join("sparse"))
+        .and_then(|file| file.set_len(1 << 30))
+        .unwrap();
+
+    cmd()
+        .arg("--classify")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout("dense\nsparse~\n");
+}
+
+#[test]
+fn test_list_recursive_sections() {
+    let dir = tempdir();
+    dir.child("a/b/file").touch().unwrap();
+    dir.child("a/skipped").touch().unwrap();
+    dir.child("a/.hidden/file").touch().unwrap();
+    dir.child("c").touch().unwrap();
+
+    let root = dir.path().display();
+    let sep = std::path::MAIN_SEPARATOR;
+    cmd()
+        .arg("--recursive")
+        .arg("--ignore-glob")
+        .arg("skipped")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(format!(
+            "{root}:\na\nc\n\n{root}{sep}a:\nb\n\n{root}{sep}a{sep}b:\nfile\n"
+        ));
+}
+
 #[test]
 fn test_list_inode_with_long_ok() {
     let dir = tempdir();
@@ -311,7 +581,8 @@ fn test_dereference_link_broken_link_output() {
         .arg("--ignore-config")
         .arg(&link)
         .assert()
-        .stdout(predicate::str::starts_with("l????????? ? ? ? ?"));
+        .stdout(predicate::str::starts_with("l"))
+        .stdout(predicate::str::contains(" ? ").not());
 
     cmd()
         .arg("-l")
@@ -319,7 +590,8 @@ fn test_dereference_link_broken_link_output() {
         .arg("--ignore-config")
         .arg(link)
         .assert()
-        .stdout(predicate::str::starts_with("l????????? ? ? ? ?"));
+        .stdout(predicate::str::starts_with("l"))
+        .stdout(predicate::str::contains(" ? ").not());
 }
 
 #[cfg(unix)]
@@ -363,6 +635,48 @@ fn test_no_show_folder_content_of_symlink_for_long() {
         .stdout(predicate::str::contains("⇒").not());
 }
 
+#[cfg(unix)]
+#[test]
+fn test_dereference_command_line_symlink_arg() {
+    let dir = tempdir();
+    dir.child("target").child("inside").touch().unwrap();
+    let link = dir.path().join("link");
+    fs::symlink("target", &link).unwrap();
+
+    cmd()
+        .arg("-lH")
+        .arg("--ignore-config")
+        .arg(link)
+        .assert()
+        .stdout(predicate::str::starts_with(".rw"))
+        .stdout(predicate::str::contains("inside"))
+        .stdout(predicate::str::contains("⇒").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dereference_command_line_keeps_nested_symlinks() {
+    let dir = tempdir();
+    dir.child("target").child("inside").touch().unwrap();
+    dir.child("listed").create_dir_all().unwrap();
+    fs::symlink("../target", dir.path().join("listed/link")).unwrap();
+
+    cmd()
+        .arg("-lH")
+        .arg("--ignore-config")
+        .arg(dir.path().join("listed"))
+        .assert()
+        .stdout(predicate::str::starts_with("lrw"))
+        .stdout(predicate::str::contains("link ⇒ ../target"));
+
+    cmd()
+        .arg("-lL")
+        .arg("--ignore-config")
+        .arg(dir.path().join("listed"))
+        .assert()
+        .stdout(predicate::str::starts_with("drw"));
+}
+
 #[cfg(unix)]
 #[test]
 fn test_show_folder_of_symlink_for_long_multi() {
@@ -605,6 +919,8 @@ fn test_lower_case_name_icon_match() {
     cmd()
         .arg("--icon")
         .arg("always")
+        .arg("--icon-theme")
+        .arg("fancy")
         .arg("--ignore-config")
         .arg(test_file)
         .assert()
@@ -621,6 +937,8 @@ fn test_upper_case_name_icon_match() {
     cmd()
         .arg("--icon")
         .arg("always")
+        .arg("--icon-theme")
+        .arg("fancy")
         .arg("--ignore-config")
         .arg(test_file)
         .assert()
@@ -637,6 +955,8 @@ fn test_lower_case_ext_icon_match() {
     cmd()
         .arg("--icon")
         .arg("always")
+        .arg("--icon-theme")
+        .arg("fancy")
         .arg("--ignore-config")
         .arg(test_file)
         .assert()
@@ -653,6 +973,8 @@ fn test_upper_case_ext_icon_match() {
     cmd()
         .arg("--icon")
         .arg("always")
+        .arg("--icon-theme")
+        .arg("fancy")
         .arg("--ignore-config")
         .arg(test_file)
         .assert()
@@ -711,6 +1033,24 @@ fn test_cannot_access_file_exit_status() {
     assert_eq!(status, 2)
 }
 
+#[test]
+fn test_invalid_option_exit_status() {
+    cmd().arg("--no-such-option").assert().code(2);
+}
+
+#[test]
+fn test_invalid_config_file_exit_status() {
+    let dir = tempdir();
+
+    cmd()
+        .arg("--config-file")
+        .arg(dir.path().join("does_not_exist.yaml"))
+        .arg(dir.path())
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Provided file path is invalid"));
+}
+
 #[cfg(unix)]
 #[test]
 fn test_cannot_access_subdir_exit_status() {
@@ -733,6 +1073,34 @@ fn test_cannot_access_subdir_exit_status() {
     assert_eq!(status, 1)
 }
 
+#[cfg(unix)]
+#[test]
+fn test_cannot_open_dir_lists_the_next_target() {
+    let tmp = tempdir();
+    tmp.child("locked").create_dir_all().unwrap();
+    tmp.child("next/file").touch().unwrap();
+
+    let locked = tmp.child("locked").path().to_path_buf();
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+    // the permissions do not stop root
+    if std::fs::read_dir(&locked).is_ok() {
+        return;
+    }
+
+    let message = format!(
+        "lsd: cannot open directory '{}': Permission denied\n",
+        locked.display()
+    );
+    cmd()
+        .arg("--ignore-config")
+        .arg(&locked)
+        .arg(tmp.child("next").path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::ends_with("next:\nfile\n"))
+        .stderr(predicate::str::starts_with(message));
+}
+
 #[test]
 fn test_date_custom_format_supports_nanos_with_length() {
     let dir = tempdir();
@@ -800,3 +1168,153 @@ fn test_multiple_files() {
         .assert()
         .stdout(predicate::str::is_match(".").unwrap());
 }
+
+#[test]
+fn test_sort_by_registered_sorter() {
+    use clap::Parser;
+    use lsd::app::Cli;
+    use lsd::config_file::Config;
+    use lsd::core::Core;
+    use lsd::flags::Flags;
+    use lsd::meta::Meta;
+
+    fn by_length(a: &Meta, b: &Meta) -> std::cmp::Ordering {
+        a.name.name.len().cmp(&b.name.name.len())
+    }
+    lsd::sort::register_sorter("length", by_length);
+
+    let dir = tempdir();
+    dir.child("ccc").touch().unwrap();
+    dir.child("a").touch().unwrap();
+    dir.child("bb").touch().unwrap();
+
+    let argv = ["lsd", "-1", "--icon", "never", "--sort", "custom:length"];
+    let cli = Cli::try_parse_from(argv).unwrap();
+    let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+    let (output, _) = Core::new(flags).render(vec![dir.path().to_path_buf()]);
+    assert_eq!("a\nbb\nccc\n", output);
+}
+
+#[test]
+fn test_sort_with_public_comparators() {
+    use lsd::flags::{DateSource, PermissionFlag, SortOrder};
+    use lsd::meta::{Meta, MetaRequest};
+    use lsd::sort::{by_meta, by_name, by_size, with_dirs_first, Sorter};
+
+    let dir = tempdir();
+    dir.child("small").write_str("1").unwrap();
+    dir.child("large").write_str("12345").unwrap();
+    dir.child("zdir").create_dir_all().unwrap();
+    let mut metas: Vec<Meta> = ["small", "large", "zdir"]
+        .iter()
+        .map(|name| {
+            let request = MetaRequest::all(false, PermissionFlag::Rwx, DateSource::Modified);
+            Meta::from_path_for(&dir.path().join(name), &request).unwrap()
+        })
+        .collect();
+    let names = |metas: &[Meta]| -> Vec<String> {
+        metas.iter().map(|meta| meta.name.name.clone()).collect()
+    };
+
+    let sorter = Sorter::new(vec![
+        (SortOrder::Default, with_dirs_first),
+        (SortOrder::Reverse, by_size),
+    ]);
+    metas.sort_by(|a, b| by_meta(&sorter, a, b));
+    assert_eq!(vec!["zdir", "small", "large"], names(&metas));
+
+    let sorter = Sorter::new(vec![(SortOrder::Reverse, by_name)]);
+    metas.sort_by(|a, b| by_meta(&sorter, a, b));
+    assert_eq!(vec!["zdir", "small", "large"], names(&metas));
+}
+
+#[test]
+fn test_sort_group_directories_first() {
+    use lsd::flags::{DateSource, PermissionFlag, SortOrder};
+    use lsd::meta::{Meta, MetaRequest};
+    use lsd::sort::{by_meta, by_name, group_directories_first, Sorter};
+
+    let dir = tempdir();
+    dir.child("aaa").touch().unwrap();
+    dir.child("zzz").touch().unwrap();
+    dir.child("bbb").create_dir_all().unwrap();
+    let request = MetaRequest::all(false, PermissionFlag::Rwx, DateSource::Modified);
+    let mut metas: Vec<Meta> = ["aaa", "zzz", "bbb"]
+        .iter()
+        .map(|name| Meta::from_path_for(&dir.path().join(name), &request).unwrap())
+        .collect();
+
+    let sorter = group_directories_first(Sorter::new(vec![(SortOrder::Reverse, by_name)]));
+    metas.sort_by(|a, b| by_meta(&sorter, a, b));
+    let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+    assert_eq!(vec!["bbb", "zzz", "aaa"], names);
+}
+
+#[test]
+fn test_sort_with_dir_sorters() {
+    use lsd::flags::{DateSource, PermissionFlag, SortOrder};
+    use lsd::meta::{Meta, MetaRequest};
+    use lsd::sort::{by_meta, by_name, by_size, group_directories_first, Sorter};
+
+    let dir = tempdir();
+    dir.child("small").write_str("1").unwrap();
+    dir.child("large").write_str("12345").unwrap();
+    dir.child("bdir").create_dir_all().unwrap();
+    dir.child("adir").create_dir_all().unwrap();
+    let request = MetaRequest::all(false, PermissionFlag::Rwx, DateSource::Modified);
+    let mut metas: Vec<Meta> = ["small", "bdir", "large", "adir"]
+        .iter()
+        .map(|name| Meta::from_path_for(&dir.path().join(name), &request).unwrap())
+        .collect();
+
+    // the directories ascending by name, the files from the largest
+    let sorter = group_directories_first(Sorter::new(vec![(SortOrder::Default, by_size)]))
+        .with_dir_sorters(vec![(SortOrder::Default, by_name)]);
+    metas.sort_by(|a, b| by_meta(&sorter, a, b));
+    let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+    assert_eq!(vec!["adir", "bdir", "large", "small"], names);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_custom_icons_apply_to_the_auto_theme() {
+    let home = tempdir();
+    home.child(".config/lsd/icons.yaml")
+        .write_str("name:\n  one: X\nfiletype:\n  file: F\n")
+        .unwrap();
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    cmd()
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .env_remove("NERD_FONT")
+        .arg("-1")
+        .arg("--ignore-config")
+        .arg("--icon")
+        .arg("always")
+        .arg("--icon-theme")
+        .arg("auto")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("X one\nF two\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_broken_link_colored_without_ls_colors() {
+    let dir = tempdir();
+    fs::symlink("missing", dir.path().join("broken")).unwrap();
+
+    // the red of the theme, rather than the style of the valid links
+    cmd()
+        .env_remove("LS_COLORS")
+        .arg("-l")
+        .arg("--color=always")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\x1b[38;5;124mbroken\x1b[39m"))
+        .stdout(predicate::str::contains("\x1b[38;5;124mmissing\x1b[39m"));
+}